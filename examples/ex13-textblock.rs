@@ -23,6 +23,8 @@ impl GameState for State {
             .ln()
             .line_wrap("Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.")
             .ln().ln()
+            .markup("Markup works too: #[red]Danger!#[] The #[bg:dark_blue]{fg:gold}dragon{/} breathes fire#[].")
+            .ln().ln()
             .fg(RGB::named(rltk::CYAN))
             .append("FPS: ")
             .fg(RGB::named(rltk::MAGENTA))
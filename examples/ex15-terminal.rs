@@ -0,0 +1,43 @@
+// This example runs a tiny game in your terminal (including over SSH), rather than in a
+// window. The game state is exactly what you'd write for the OpenGL backend; only the
+// initialization and main loop calls change.
+//////////////////////////////////////////////////////////////
+
+extern crate rltk;
+use rltk::{Rltk, GameState, Console, RGB, VirtualKeyCode};
+
+struct State {
+    x : i32,
+    y : i32
+}
+
+impl GameState for State {
+    fn tick(&mut self, ctx : &mut Rltk) {
+        match ctx.key {
+            None => {}
+            Some(key) => {
+                match key {
+                    VirtualKeyCode::Left => self.x = i32::max(0, self.x - 1),
+                    VirtualKeyCode::Right => self.x = i32::min(79, self.x + 1),
+                    VirtualKeyCode::Up => self.y = i32::max(2, self.y - 1),
+                    VirtualKeyCode::Down => self.y = i32::min(23, self.y + 1),
+                    VirtualKeyCode::Q | VirtualKeyCode::Escape => ctx.quit(),
+                    _ => {}
+                }
+            }
+        }
+
+        ctx.cls();
+        ctx.print_color(1, 0, RGB::named(rltk::YELLOW), RGB::named(rltk::BLACK), "Cursor keys to move, Q to quit. ☺");
+        ctx.print_color(50, 0, RGB::named(rltk::CYAN), RGB::named(rltk::BLACK), &format!("FPS: {}", ctx.fps));
+        ctx.draw_box(0, 1, 79, 23, RGB::named(rltk::GREY), RGB::named(rltk::BLACK));
+        ctx.set(self.x, self.y, RGB::named(rltk::YELLOW), RGB::named(rltk::BLACK), rltk::to_cp437('@'));
+    }
+}
+
+fn main() {
+    // No window and no resources are needed: just the size of the terminal area to use.
+    let context = Rltk::init_terminal(80, 25);
+    let gs : State = State{ x : 40, y : 12 };
+    rltk::terminal::main_loop(context, Box::new(gs));
+}
@@ -0,0 +1,73 @@
+// This example streams a game's consoles to a spectator. Start the viewer first:
+//     cargo run --example ex16-streaming --features serialization -- view
+// and then, in another terminal, the game:
+//     cargo run --example ex16-streaming --features serialization -- play
+// The viewer replays every frame the game draws, from the stream of changed cells.
+//////////////////////////////////////////////////////////////
+
+extern crate rltk;
+use rltk::{Rltk, GameState, Console, RGB};
+use rltk::streaming::{ConsoleStreamReader, StreamFrame};
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver};
+
+const ADDRESS : &str = "127.0.0.1:9876";
+
+// The game: a bouncing hello world, as in example 1.
+struct Game {
+    y : i32,
+    going_down : bool
+}
+
+impl GameState for Game {
+    fn tick(&mut self, ctx : &mut Rltk) {
+        ctx.cls();
+        ctx.print_color(1, self.y, RGB::named(rltk::YELLOW), RGB::named(rltk::BLACK), "Hello, spectators ☺");
+        ctx.print_color(1, 0, RGB::named(rltk::CYAN), RGB::named(rltk::BLACK), &format!("FPS: {}", ctx.fps));
+
+        if self.going_down {
+            self.y += 1;
+            if self.y > 48 { self.going_down = false; }
+        } else {
+            self.y -= 1;
+            if self.y < 2 { self.going_down = true; }
+        }
+    }
+}
+
+// The viewer: applies frames received from a background thread.
+struct Viewer {
+    frames : Receiver<StreamFrame>
+}
+
+impl GameState for Viewer {
+    fn tick(&mut self, ctx : &mut Rltk) {
+        while let Ok(frame) = self.frames.try_recv() {
+            frame.apply(ctx);
+        }
+    }
+}
+
+fn main() {
+    let mode = std::env::args().nth(1).unwrap_or_else(|| "play".to_string());
+
+    if mode == "view" {
+        let listener = TcpListener::bind(ADDRESS).expect("Unable to listen");
+        let (tx, rx) = channel();
+        std::thread::spawn(move || {
+            let (socket, _) = listener.accept().expect("No game connected");
+            let mut reader = ConsoleStreamReader::new(BufReader::new(socket));
+            while let Ok(Some(frame)) = reader.read_frame() {
+                if tx.send(frame).is_err() { break; }
+            }
+        });
+
+        let context = Rltk::init_simple8x8(80, 50, "Example 16 - Spectating", "resources");
+        rltk::main_loop(context, Box::new(Viewer{ frames : rx }));
+    } else {
+        let mut context = Rltk::init_simple8x8(80, 50, "Example 16 - Streaming", "resources");
+        context.stream_to(Box::new(TcpStream::connect(ADDRESS).expect("Start the viewer first")));
+        rltk::main_loop(context, Box::new(Game{ y : 1, going_down : true }));
+    }
+}
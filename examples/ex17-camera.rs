@@ -0,0 +1,121 @@
+// This example builds on example 3, but the map is much bigger than the screen. The map is
+// drawn once to a VirtualConsole, and a Camera decides which part of it to show - following
+// the player around with a dead-zone, and telling us which map cell is under the mouse.
+//
+// Comments that duplicate previous examples have been removed for brevity.
+//////////////////////////////////////////////////////////////
+
+extern crate rltk;
+use rltk::{Rltk, GameState, Console, RGB, VirtualKeyCode, VirtualConsole, Camera, Point, Rect};
+
+extern crate rand;
+use crate::rand::Rng;
+
+const MAP_WIDTH : i32 = 200;
+const MAP_HEIGHT : i32 = 120;
+
+#[derive(PartialEq, Copy, Clone)]
+enum TileType { Wall, Floor }
+
+struct State {
+    map : Vec<TileType>,
+    player_position : Point,
+    world : VirtualConsole,
+    camera : Camera
+}
+
+pub fn xy_idx(x : i32, y : i32) -> usize {
+    (y as usize * MAP_WIDTH as usize) + x as usize
+}
+
+impl State {
+    pub fn new() -> State {
+        let mut map = vec![TileType::Floor; (MAP_WIDTH * MAP_HEIGHT) as usize];
+        for x in 0 .. MAP_WIDTH {
+            map[xy_idx(x, 0)] = TileType::Wall;
+            map[xy_idx(x, MAP_HEIGHT-1)] = TileType::Wall;
+        }
+        for y in 0 .. MAP_HEIGHT {
+            map[xy_idx(0, y)] = TileType::Wall;
+            map[xy_idx(MAP_WIDTH-1, y)] = TileType::Wall;
+        }
+
+        let player_position = Point::new(MAP_WIDTH / 2, MAP_HEIGHT / 2);
+        let mut rng = rand::thread_rng();
+        for _i in 0..2400 {
+            let x = rng.gen_range(1, MAP_WIDTH-1);
+            let y = rng.gen_range(1, MAP_HEIGHT-1);
+            if Point::new(x, y) != player_position {
+                map[xy_idx(x, y)] = TileType::Wall;
+            }
+        }
+
+        // The map never changes, so we only need to draw it to the virtual console once.
+        let mut world = VirtualConsole::new(MAP_WIDTH as u32, MAP_HEIGHT as u32);
+        for y in 0 .. MAP_HEIGHT {
+            for x in 0 .. MAP_WIDTH {
+                match map[xy_idx(x, y)] {
+                    TileType::Floor => { world.print_color(x, y, RGB::from_f32(0.5, 0.5, 0.5), RGB::from_f32(0., 0., 0.), "."); }
+                    TileType::Wall => { world.print_color(x, y, RGB::from_f32(0.0, 1.0, 0.0), RGB::from_f32(0., 0., 0.), "#"); }
+                }
+            }
+        }
+
+        // The camera uses the top 48 rows of the screen; the bottom two are a status line.
+        let mut camera = Camera::new(Rect::with_size(0, 0, 80, 48), MAP_WIDTH, MAP_HEIGHT).with_dead_zone(20, 12);
+        camera.center_on(player_position);
+
+        State{ map, player_position, world, camera }
+    }
+
+    pub fn move_player(&mut self, delta_x : i32, delta_y : i32) {
+        let destination = Point::new(self.player_position.x + delta_x, self.player_position.y + delta_y);
+        if self.map[xy_idx(destination.x, destination.y)] == TileType::Floor {
+            self.player_position = destination;
+        }
+    }
+}
+
+impl GameState for State {
+    fn tick(&mut self, ctx : &mut Rltk) {
+        match ctx.key {
+            None => {}
+            Some(key) => {
+                match key {
+                    VirtualKeyCode::Up | VirtualKeyCode::Numpad8 => { self.move_player(0, -1); }
+                    VirtualKeyCode::Down | VirtualKeyCode::Numpad2 => { self.move_player(0, 1); }
+                    VirtualKeyCode::Left | VirtualKeyCode::Numpad4 => { self.move_player(-1, 0); }
+                    VirtualKeyCode::Right | VirtualKeyCode::Numpad6 => { self.move_player(1, 0); }
+                    _ => {}
+                }
+            }
+        }
+
+        // Scroll only when the player leaves the dead-zone in the middle of the screen.
+        self.camera.follow(self.player_position);
+
+        ctx.cls();
+        self.camera.render(&self.world, ctx);
+
+        if let Some(screen) = self.camera.world_to_screen(self.player_position) {
+            ctx.print_color(screen.x, screen.y, RGB::from_f32(1.0, 1.0, 0.0), RGB::from_f32(0., 0., 0.), "@");
+        }
+
+        // Highlight whatever is under the mouse, and tell the player where it is on the map.
+        match self.camera.mouse_world_pos(ctx) {
+            Some(world) => {
+                let screen = self.camera.world_to_screen(world).unwrap();
+                ctx.set_bg(screen.x, screen.y, RGB::from_f32(0.0, 0.0, 1.0));
+                ctx.print(1, 49, &format!("Mouse is over map cell {},{}          ", world.x, world.y));
+            }
+            None => { ctx.print(1, 49, "Mouse is not over the map"); }
+        }
+        ctx.print(1, 48, &format!("Player at {},{} - camera at {},{}", self.player_position.x, self.player_position.y, self.camera.position.x, self.camera.position.y));
+    }
+}
+
+fn main() {
+    let context = Rltk::init_simple8x8(80, 50, "RLTK Example 17 - Camera", "resources");
+    let gs = State::new();
+    rltk::main_loop(context, Box::new(gs));
+}
@@ -0,0 +1,91 @@
+// This example shows off the immediate-mode widgets in rltk::widgets. Every frame we create a
+// Ui, and call a function for each widget; the function draws it and tells us if the player
+// interacted with it. Anything a widget needs to remember between frames lives in State.
+//
+// Comments that duplicate previous examples have been removed for brevity.
+//////////////////////////////////////////////////////////////
+
+extern crate rltk;
+use rltk::{Rltk, GameState, Console, Rect, MessageLog, RGB};
+use rltk::widgets::{Ui, ListState, PanelState, TextInput, TextInputResult};
+
+struct State {
+    tab : usize,
+    sound : bool,
+    music : bool,
+    inventory : ListState,
+    help : PanelState,
+    name : TextInput,
+    log : MessageLog,
+    confirm_quit : bool
+}
+
+const ITEMS : [&str; 12] = ["Dagger", "Short sword", "Leather armor", "Health potion", "Health potion",
+    "Scroll of fireball", "Torch", "Rope", "Lockpicks", "Rations", "Gold ring", "Strange amulet"];
+
+impl GameState for State {
+    fn tick(&mut self, ctx : &mut Rltk) {
+        let log_area = Rect::with_size(1, 40, 78, 9);
+        self.log.handle_input(ctx, log_area);
+
+        ctx.cls();
+        let mut ui = Ui::new(ctx);
+        if self.confirm_quit {
+            ui.block_input();
+        }
+
+        ui.console().draw_box(0, 0, 79, 38, RGB::from_f32(0.5, 0.5, 0.5), RGB::from_f32(0.0, 0.0, 0.0));
+        ui.tab_bar(2, 1, &["Inventory", "Options", "Help"], &mut self.tab);
+
+        match self.tab {
+            0 => {
+                if let Some(item) = ui.list(Rect::with_size(2, 3, 30, 8), &ITEMS, &mut self.inventory) {
+                    self.log.log(format!("You examine the {}.", ITEMS[item].to_lowercase()));
+                }
+                ui.tooltip(Rect::with_size(2, 3, 30, 8), &["Click an item, or use the", "cursor keys and Enter."]);
+            }
+            1 => {
+                if ui.checkbox(2, 3, "Sound effects", &mut self.sound) {
+                    self.log.log(format!("Sound effects {}.", if self.sound { "on" } else { "off" }));
+                }
+                if ui.checkbox(2, 4, "Music", &mut self.music) {
+                    self.log.log(format!("Music {}.", if self.music { "on" } else { "off" }));
+                }
+                ui.console().print(2, 6, "Name:");
+                if ui.text_input(8, 6, 20, &mut self.name) == TextInputResult::Submitted {
+                    self.log.log(format!("Your name is now {}.", self.name.text()));
+                }
+            }
+            _ => {
+                ui.scroll_panel(Rect::with_size(2, 3, 40, 10), 30, &mut self.help, |panel| {
+                    for line in 0 .. 30 {
+                        panel.print(0, line, &format!("Help line {} - scroll with the wheel", line + 1));
+                    }
+                });
+            }
+        }
+
+        if ui.button(2, 36, "Quit") {
+            self.confirm_quit = true;
+        }
+
+        if self.confirm_quit {
+            match ui.dialog("Quit", "Are you sure you want to leave? Your progress will not be saved.", &["Yes", "No"]) {
+                Some(0) => ctx.quit(),
+                Some(_) => self.confirm_quit = false,
+                None => {}
+            }
+        }
+
+        self.log.render(ctx, log_area);
+    }
+}
+
+fn main() {
+    let context = Rltk::init_simple8x8(80, 50, "RLTK Example 18 - Widgets", "resources");
+    let mut log = MessageLog::new(100);
+    log.log("Welcome! Try the tabs, the inventory list and the Quit button.");
+    let gs = State{ tab: 0, sound: true, music: false, inventory: ListState::default(), help: PanelState::default(),
+        name: TextInput::new(16).with_filter(|c| c.is_alphanumeric() || c == ' ').with_text("Grognak"), log, confirm_quit: false };
+    rltk::main_loop(context, Box::new(gs));
+}
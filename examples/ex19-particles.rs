@@ -0,0 +1,73 @@
+// This example shows off particles. A ParticleSystem holds emitters; each tick we update it with
+// the frame time and render the particles into a sparse console layered over the map, so the
+// map shows through wherever there is no particle. Click anywhere to set off an explosion.
+//
+// Comments that duplicate previous examples have been removed for brevity.
+//////////////////////////////////////////////////////////////
+
+extern crate rltk;
+use rltk::{Rltk, GameState, Console, RGB, ParticleSystem, Emitter, Gradient};
+
+struct State {
+    particles : ParticleSystem,
+    explosion : Emitter
+}
+
+impl GameState for State {
+    fn tick(&mut self, ctx : &mut Rltk) {
+        if ctx.left_pressed {
+            let (x, y) = ctx.mouse_pos();
+            let mut explosion = self.explosion.clone();
+            explosion.x = x as f32;
+            explosion.y = y as f32;
+            let id = self.particles.add_emitter(explosion);
+            self.particles.burst(id, 40);
+        }
+        self.particles.update(ctx.frame_time_ms);
+
+        // The map, on the base layer
+        ctx.set_active_console(0);
+        ctx.cls();
+        for y in 0 .. 50 {
+            for x in 0 .. 80 {
+                let glyph = if x == 0 || y == 0 || x == 79 || y == 49 { '#' } else { '.' };
+                ctx.print_color(x, y, RGB::named(rltk::GREY30), RGB::named(rltk::BLACK), &glyph.to_string());
+            }
+        }
+        ctx.print_color(2, 2, RGB::named(rltk::WHITE), RGB::named(rltk::BLACK), "Click to set off an explosion");
+
+        // The particles, on the sparse layer above it
+        ctx.set_active_console(1);
+        ctx.cls();
+        self.particles.render(ctx);
+    }
+}
+
+fn main() {
+    let mut context = Rltk::init_simple8x8(80, 50, "RLTK Example 19 - Particles", "resources");
+    context.register_console_no_bg(rltk::SparseConsole::init(80, 50, &context.gl), 0);
+
+    // A seeded system gives the same particles every run
+    let mut particles = ParticleSystem::new(19);
+    let fire = Gradient::new(&[RGB::named(rltk::YELLOW), RGB::named(rltk::ORANGE), RGB::named(rltk::RED), RGB::named(rltk::GREY20)]);
+    particles.add_emitter(Emitter::new(40.0, 40.0)
+        .with_glyphs(&[b'*', b'+', b'.'])
+        .with_gradient(fire.clone())
+        .with_direction(-20.0, 20.0)
+        .with_speed(4.0, 9.0)
+        .with_lifetime(800.0, 1600.0)
+        .with_rate(40.0));
+
+    let explosion = Emitter::new(0.0, 0.0)
+        .with_glyphs(&[b'*', 15])
+        .with_gradient(fire)
+        .with_speed(5.0, 20.0)
+        .with_lifetime(300.0, 700.0)
+        .with_gravity(0.0, 20.0)
+        .with_rate(0.0)
+        .with_duration(0.0)
+        .with_fade(true);
+
+    let gs = State{ particles, explosion };
+    rltk::main_loop(context, Box::new(gs));
+}
@@ -1,8 +1,13 @@
 #version 330 core
-layout (location = 0) in vec3 aPos;
-layout (location = 1) in vec3 aColor;
-layout (location = 2) in vec3 bColor;
-layout (location = 3) in vec2 aTexCoord;
+layout (location = 0) in vec2 aCorner;
+layout (location = 1) in vec2 aCell;
+layout (location = 2) in vec3 aColor;
+layout (location = 3) in vec3 bColor;
+layout (location = 4) in float aGlyph;
+
+// x/y hold the console size in characters, and the offset in screen coordinates
+uniform vec3 consoleSize;
+uniform vec3 consoleOffset;
 
 out vec3 ourColor;
 out vec3 ourBackground;
@@ -10,8 +15,13 @@ out vec2 TexCoord;
 
 void main()
 {
-	gl_Position = vec4(aPos, 1.0);
+	vec2 step = 2.0 / consoleSize.xy;
+	vec2 pos = vec2(-1.0, -1.0) + ((aCell + aCorner) * step) + consoleOffset.xy;
+	gl_Position = vec4(pos, 0.0, 1.0);
 	ourColor = aColor;
 	ourBackground = bColor;
-	TexCoord = vec2(aTexCoord.x, aTexCoord.y);
-}
\ No newline at end of file
+
+	// Glyphs are laid out 16x16 on the font sheet, with glyph 0 at the top-left
+	vec2 glyph = vec2(mod(aGlyph, 16.0), 15.0 - floor(aGlyph / 16.0));
+	TexCoord = (glyph + aCorner) / 16.0;
+}
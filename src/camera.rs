@@ -0,0 +1,186 @@
+use super::{Console, Point, Rect, Rltk, VirtualConsole};
+
+/// A scrolling view onto a map that is larger (or smaller) than the screen area it is drawn in.
+/// The camera tracks which world cell sits at the top-left of its viewport, and converts between
+/// world and screen coordinates.
+pub struct Camera {
+    /// The world coordinate shown in the top-left cell of the viewport.
+    pub position: Point,
+
+    /// The area of the screen (in console cells) the camera draws into.
+    pub viewport: Rect,
+
+    /// The size of the map being viewed.
+    pub map_width: i32,
+    pub map_height: i32,
+
+    /// The size of the box in the middle of the viewport inside which the target can move
+    /// without the camera scrolling. (0,0) keeps the target centered.
+    pub dead_zone: (i32, i32)
+}
+
+impl Camera {
+    /// Creates a camera drawing into `viewport`, looking at the top-left of a map.
+    pub fn new(viewport : Rect, map_width : i32, map_height : i32) -> Camera {
+        let mut camera = Camera{
+            position: Point::new(0, 0),
+            viewport,
+            map_width,
+            map_height,
+            dead_zone: (0, 0)
+        };
+        camera.clamp();
+        camera
+    }
+
+    /// Sets the size of the dead-zone, the box the target can move around in without scrolling.
+    pub fn with_dead_zone(mut self, width : i32, height : i32) -> Camera {
+        self.dead_zone = (width, height);
+        self
+    }
+
+    /// Moves the camera so the target is in the middle of the viewport (as far as the map edges allow).
+    pub fn center_on(&mut self, target : Point) {
+        self.position = Point::new(
+            target.x - self.viewport.width() / 2,
+            target.y - self.viewport.height() / 2
+        );
+        self.clamp();
+    }
+
+    /// Scrolls the camera just far enough to keep the target inside the dead-zone, and then
+    /// clamps it to the map edges. Call this once per tick with the player's position.
+    pub fn follow(&mut self, target : Point) {
+        let dead_zone = self.dead_zone_rect();
+        let screen_x = target.x - self.position.x;
+        let screen_y = target.y - self.position.y;
+
+        if screen_x < dead_zone.x1 {
+            self.position.x -= dead_zone.x1 - screen_x;
+        } else if screen_x >= dead_zone.x2 {
+            self.position.x += screen_x - (dead_zone.x2 - 1);
+        }
+        if screen_y < dead_zone.y1 {
+            self.position.y -= dead_zone.y1 - screen_y;
+        } else if screen_y >= dead_zone.y2 {
+            self.position.y += screen_y - (dead_zone.y2 - 1);
+        }
+
+        self.clamp();
+    }
+
+    /// The dead-zone, relative to the viewport's top-left. It is always at least one cell.
+    fn dead_zone_rect(&self) -> Rect {
+        let width = i32::max(1, i32::min(self.dead_zone.0, self.viewport.width()));
+        let height = i32::max(1, i32::min(self.dead_zone.1, self.viewport.height()));
+        Rect::with_size(
+            (self.viewport.width() - width) / 2,
+            (self.viewport.height() - height) / 2,
+            width, height
+        )
+    }
+
+    /// Keeps the view on the map. If the map is smaller than the viewport on an axis, it is
+    /// centered on that axis instead.
+    fn clamp(&mut self) {
+        self.position.x = Camera::clamp_axis(self.position.x, self.viewport.width(), self.map_width);
+        self.position.y = Camera::clamp_axis(self.position.y, self.viewport.height(), self.map_height);
+    }
+
+    fn clamp_axis(position : i32, view_size : i32, map_size : i32) -> i32 {
+        if map_size <= view_size {
+            -((view_size - map_size) / 2)
+        } else {
+            i32::max(0, i32::min(position, map_size - view_size))
+        }
+    }
+
+    /// The region of the world currently in view. It can extend past the map edges if the map is
+    /// smaller than the viewport.
+    pub fn visible_area(&self) -> Rect {
+        Rect::with_size(self.position.x, self.position.y, self.viewport.width(), self.viewport.height())
+    }
+
+    /// Converts a world coordinate to a screen cell, or None if it is outside the viewport.
+    pub fn world_to_screen(&self, pt : Point) -> Option<Point> {
+        if !self.visible_area().point_in_rect(pt) { return None; }
+        Some(Point::new(
+            pt.x - self.position.x + self.viewport.x1,
+            pt.y - self.position.y + self.viewport.y1
+        ))
+    }
+
+    /// Converts a screen cell to a world coordinate, or None if it is outside the viewport or
+    /// doesn't correspond to a map cell.
+    pub fn screen_to_world(&self, pt : Point) -> Option<Point> {
+        if !self.viewport.point_in_rect(pt) { return None; }
+        let world = Point::new(
+            pt.x - self.viewport.x1 + self.position.x,
+            pt.y - self.viewport.y1 + self.position.y
+        );
+        if world.x < 0 || world.y < 0 || world.x >= self.map_width || world.y >= self.map_height { return None; }
+        Some(world)
+    }
+
+    /// Returns the world coordinate under the mouse cursor (on the active console), if there is one.
+    pub fn mouse_world_pos(&self, ctx : &Rltk) -> Option<Point> {
+        let (x, y) = ctx.mouse_pos();
+        self.screen_to_world(Point::new(x, y))
+    }
+
+    /// Copies the visible part of a map drawn on a virtual console into the viewport on `target`.
+    pub fn render(&self, world : &VirtualConsole, target : &mut dyn Console) {
+        let visible = self.visible_area().clip_to(&Rect::with_size(0, 0, world.width as i32, world.height as i32));
+        if visible.is_empty() { return; }
+        let dest = self.world_to_screen(Point::new(visible.x1, visible.y1)).unwrap();
+        world.blit(visible, target, dest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Camera;
+    use super::super::{Point, Rect, VirtualConsole, Console, RGB};
+
+    #[test]
+    fn test_center_and_clamp() {
+        let mut camera = Camera::new(Rect::with_size(0, 0, 80, 50), 200, 200);
+        camera.center_on(Point::new(100, 100));
+        assert_eq!(camera.position, Point::new(60, 75));
+        assert_eq!(camera.world_to_screen(Point::new(100, 100)), Some(Point::new(40, 25)));
+
+        camera.center_on(Point::new(2, 199));
+        assert_eq!(camera.position, Point::new(0, 150));
+    }
+
+    #[test]
+    // The camera only scrolls when the target leaves the dead-zone.
+    fn test_follow_dead_zone() {
+        let mut camera = Camera::new(Rect::with_size(0, 0, 20, 20), 100, 100).with_dead_zone(10, 10);
+        camera.center_on(Point::new(50, 50));
+        let start = camera.position;
+
+        camera.follow(Point::new(53, 48));
+        assert_eq!(camera.position, start);
+
+        camera.follow(Point::new(60, 50));
+        assert_eq!(camera.position, Point::new(start.x + 6, start.y));
+        assert_eq!(camera.world_to_screen(Point::new(60, 50)), Some(Point::new(14, 10)));
+    }
+
+    #[test]
+    // Maps smaller than the viewport are centered, and cells off the map have no world position.
+    fn test_small_map() {
+        let camera = Camera::new(Rect::with_size(10, 5, 20, 10), 10, 10);
+        assert_eq!(camera.position, Point::new(-5, 0));
+        assert_eq!(camera.screen_to_world(Point::new(15, 5)), Some(Point::new(0, 0)));
+        assert_eq!(camera.screen_to_world(Point::new(14, 5)), None);
+        assert_eq!(camera.screen_to_world(Point::new(0, 0)), None);
+
+        let mut world = VirtualConsole::new(10, 10);
+        world.set(0, 0, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0), b'#');
+        let mut screen = VirtualConsole::new(40, 20);
+        camera.render(&world, &mut screen);
+        assert_eq!(screen.get(15, 5).unwrap().glyph, b'#');
+    }
+}
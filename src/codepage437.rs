@@ -275,6 +275,284 @@ pub fn to_cp437(c : char) -> u8 {
     }
 }
 
+/// Converts a CP437 glyph back to its unicode equivalent. Glyphs without a printable
+/// equivalent (0 and 255) become a space.
+pub fn to_unicode(glyph : u8) -> char {
+    match glyph {
+        1 => { '☺' }
+        2 => { '☻' }
+        3 => { '♥' }
+        4 => { '♦' }
+        5 => { '♣' }
+        6 => { '♠' }
+        7 => { '•' }
+        8 => { '◘' }
+        9 => { '○' }
+        10 => { '◙' }
+        11 => { '♂' }
+        12 => { '♀' }
+        13 => { '♪' }
+        14 => { '♫' }
+        15 => { '☼' }
+
+        16 => { '►' }
+        17 => { '◄' }
+        18 => { '↕' }
+        19 => { '‼' }
+        20 => { '¶' }
+        21 => { '§' }
+        22 => { '▬' }
+        23 => { '↨' }
+        24 => { '↑' }
+        25 => { '↓' }
+        26 => { '→' }
+        27 => { '←' }
+        28 => { '∟' }
+        29 => { '↔' }
+        30 => { '▲' }
+        31 => { '▼' }
+
+        32 => { ' ' }
+        33 => { '!' }
+        34 => { '"' }
+        35 => { '#' }
+        36 => { '$' }
+        37 => { '%' }
+        38 => { '&' }
+        39 => { '\'' }
+        40 => { '(' }
+        41 => { ')' }
+        42 => { '*' }
+        43 => { '+' }
+        44 => { ',' }
+        45 => { '-' }
+        46 => { '.' }
+        47 => { '/' }
+
+        48 => { '0' }
+        49 => { '1' }
+        50 => { '2' }
+        51 => { '3' }
+        52 => { '4' }
+        53 => { '5' }
+        54 => { '6' }
+        55 => { '7' }
+        56 => { '8' }
+        57 => { '9' }
+        58 => { ':' }
+        59 => { ';' }
+        60 => { '<' }
+        61 => { '=' }
+        62 => { '>' }
+        63 => { '?' }
+
+        64 => { '@' }
+        65 => { 'A' }
+        66 => { 'B' }
+        67 => { 'C' }
+        68 => { 'D' }
+        69 => { 'E' }
+        70 => { 'F' }
+        71 => { 'G' }
+        72 => { 'H' }
+        73 => { 'I' }
+        74 => { 'J' }
+        75 => { 'K' }
+        76 => { 'L' }
+        77 => { 'M' }
+        78 => { 'N' }
+        79 => { 'O' }
+
+        80 => { 'P' }
+        81 => { 'Q' }
+        82 => { 'R' }
+        83 => { 'S' }
+        84 => { 'T' }
+        85 => { 'U' }
+        86 => { 'V' }
+        87 => { 'W' }
+        88 => { 'X' }
+        89 => { 'Y' }
+        90 => { 'Z' }
+        91 => { '[' }
+        92 => { '\\' }
+        93 => { ']' }
+        94 => { '^' }
+        95 => { '_' }
+
+        96 => { '`' }
+        97 => { 'a' }
+        98 => { 'b' }
+        99 => { 'c' }
+        100 => { 'd' }
+        101 => { 'e' }
+        102 => { 'f' }
+        103 => { 'g' }
+        104 => { 'h' }
+        105 => { 'i' }
+        106 => { 'j' }
+        107 => { 'k' }
+        108 => { 'l' }
+        109 => { 'm' }
+        110 => { 'n' }
+        111 => { 'o' }
+
+        112 => { 'p' }
+        113 => { 'q' }
+        114 => { 'r' }
+        115 => { 's' }
+        116 => { 't' }
+        117 => { 'u' }
+        118 => { 'v' }
+        119 => { 'w' }
+        120 => { 'x' }
+        121 => { 'y' }
+        122 => { 'z' }
+        123 => { '{' }
+        124 => { '|' }
+        125 => { '}' }
+        126 => { '~' }
+        127 => { '⌂' }
+
+        128 => { 'Ç' }
+        129 => { 'ü' }
+        130 => { 'é' }
+        131 => { 'â' }
+        132 => { 'ä' }
+        133 => { 'à' }
+        134 => { 'å' }
+        135 => { 'ç' }
+        136 => { 'ê' }
+        137 => { 'ë' }
+        138 => { 'è' }
+        139 => { 'ï' }
+        140 => { 'î' }
+        141 => { 'ì' }
+        142 => { 'Ä' }
+        143 => { 'Å' }
+
+        144 => { 'É' }
+        145 => { 'æ' }
+        146 => { 'Æ' }
+        147 => { 'ô' }
+        148 => { 'ö' }
+        149 => { 'ò' }
+        150 => { 'û' }
+        151 => { 'ù' }
+        152 => { 'ÿ' }
+        153 => { 'Ö' }
+        154 => { 'Ü' }
+        155 => { '¢' }
+        156 => { '£' }
+        157 => { '¥' }
+        158 => { '₧' }
+        159 => { 'ƒ' }
+
+        160 => { 'á' }
+        161 => { 'í' }
+        162 => { 'ó' }
+        163 => { 'ú' }
+        164 => { 'ñ' }
+        165 => { 'Ñ' }
+        166 => { 'ª' }
+        167 => { 'º' }
+        168 => { '¿' }
+        169 => { '⌐' }
+        170 => { '¬' }
+        171 => { '½' }
+        172 => { '¼' }
+        173 => { '¡' }
+        174 => { '«' }
+        175 => { '»' }
+
+        176 => { '░' }
+        177 => { '▒' }
+        178 => { '▓' }
+        179 => { '│' }
+        180 => { '┤' }
+        181 => { '╡' }
+        182 => { '╢' }
+        183 => { '╖' }
+        184 => { '╕' }
+        185 => { '╣' }
+        186 => { '║' }
+        187 => { '╗' }
+        188 => { '╝' }
+        189 => { '╜' }
+        190 => { '╛' }
+        191 => { '┐' }
+
+        192 => { '└' }
+        193 => { '┴' }
+        194 => { '┬' }
+        195 => { '├' }
+        196 => { '─' }
+        197 => { '┼' }
+        198 => { '╞' }
+        199 => { '╟' }
+        200 => { '╚' }
+        201 => { '╔' }
+        202 => { '╩' }
+        203 => { '╦' }
+        204 => { '╠' }
+        205 => { '═' }
+        206 => { '╬' }
+        207 => { '╧' }
+
+        208 => { '╨' }
+        209 => { '╤' }
+        210 => { '╥' }
+        211 => { '╙' }
+        212 => { '╘' }
+        213 => { '╒' }
+        214 => { '╓' }
+        215 => { '╫' }
+        216 => { '╪' }
+        217 => { '┘' }
+        218 => { '┌' }
+        219 => { '█' }
+        220 => { '▄' }
+        221 => { '▌' }
+        222 => { '▐' }
+        223 => { '▀' }
+
+        224 => { 'α' }
+        225 => { 'ß' }
+        226 => { 'Γ' }
+        227 => { 'π' }
+        228 => { 'Σ' }
+        229 => { 'σ' }
+        230 => { 'µ' }
+        231 => { 'τ' }
+        232 => { 'Φ' }
+        233 => { 'Θ' }
+        234 => { 'Ω' }
+        235 => { 'δ' }
+        236 => { '∞' }
+        237 => { 'φ' }
+        238 => { 'ε' }
+        239 => { '∩' }
+
+        240 => { '≡' }
+        241 => { '±' }
+        242 => { '≥' }
+        243 => { '≤' }
+        244 => { '⌠' }
+        245 => { '⌡' }
+        246 => { '÷' }
+        247 => { '≈' }
+        248 => { '°' }
+        249 => { '∙' }
+        250 => { '·' }
+        251 => { '√' }
+        252 => { 'ⁿ' }
+        253 => { '²' }
+        254 => { '■' }
+
+        _ => { ' ' }
+    }
+}
+
 /// Converts a string into a vector of u8, CP437 representations of the string
 pub fn string_to_cp437<S : ToString>(input : S) -> Vec<u8> {
     let s = input.to_string();
@@ -288,7 +566,7 @@ pub fn string_to_cp437<S : ToString>(input : S) -> Vec<u8> {
 
 #[cfg(test)]
 mod tests {
-    use super::string_to_cp437;
+    use super::{string_to_cp437, to_cp437, to_unicode};
 
     #[test]
     // Tests that we make an RGB triplet at defaults and it is black.
@@ -313,4 +591,13 @@ mod tests {
         let convert = string_to_cp437("☺☻♥♦♣♠•◘○◙♂♀♪♫☼");
         assert_eq!(test, convert);
     }
-}
\ No newline at end of file
+
+    #[test]
+    // Tests that every glyph with a unicode equivalent survives a round trip.
+    fn test_unicode_roundtrip() {
+        for glyph in 1 .. 255u8 {
+            assert_eq!(to_cp437(to_unicode(glyph)), glyph);
+        }
+        assert_eq!(to_unicode(0), ' ');
+    }
+}
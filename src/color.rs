@@ -31,6 +31,32 @@ pub struct HSV {
     pub v : f32
 }
 
+#[derive(PartialEq, Copy, Clone)]
+/// Represents an H/S/L triplet, in the range 0..1 (32-bit float)
+pub struct HSL {
+    pub h : f32,
+    pub s : f32,
+    pub l : f32
+}
+
+#[derive(PartialEq, Copy, Clone)]
+/// Represents a color in the CIE L*a*b* space (D65 white point). `l` runs from 0 to 100; `a` and
+/// `b` are roughly -128..128. Equal steps in Lab look like roughly equal changes in color.
+pub struct Lab {
+    pub l : f32,
+    pub a : f32,
+    pub b : f32
+}
+
+#[derive(PartialEq, Copy, Clone)]
+/// Represents a color in the OKLab space. `l` runs from 0 to 1; `a` and `b` are roughly -0.4..0.4.
+/// OKLab is more perceptually even than Lab, and is the best choice for blending colors.
+pub struct OkLab {
+    pub l : f32,
+    pub a : f32,
+    pub b : f32
+}
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 /// Error message type when failing to convert a hex code to RGB.
 pub enum HtmlColorConversionError { InvalidStringLength, MissingHash }
@@ -109,6 +135,19 @@ impl RGB {
         Ok(RGB::from_f32(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0))
     }
 
+    /// Looks up one of the named color constants by name, for colors named in data files. Case,
+    /// spaces, dashes and underscores are ignored, so "dark_olive_green", "Dark Olive Green" and
+    /// "DARKOLIVEGREEN" are all the same color.
+    pub fn from_name(name : &str) -> Option<RGB> {
+        color_by_name(name).map(RGB::named)
+    }
+
+    /// Converts to an HTML color code (e.g. "#eeffee"), the reverse of `from_hex`.
+    pub fn to_hex(&self) -> String {
+        let byte = |c : f32| (c * 255.0).round() as u8;
+        format!("#{:02x}{:02x}{:02x}", byte(self.r), byte(self.g), byte(self.b))
+    }
+
     /// Converts an xp file color component to an RGB
     pub fn from_xp(col : XpColor) -> RGB {
         RGB::from_u8(col.r, col.g, col.b)
@@ -189,6 +228,86 @@ impl RGB {
         result.b += range.2 * percent;
         result
     }
+
+    /// Converts an RGB triple to an HSL triple.
+    pub fn to_hsl(&self) -> HSL {
+        let max = f32::max(f32::max(self.r, self.g), self.b);
+        let min = f32::min(f32::min(self.r, self.g), self.b);
+        let l = (max + min) / 2.0;
+        if max == min {
+            return HSL::from_f32(0.0, 0.0, l); // Achromatic
+        }
+
+        let d = max - min;
+        let s = if l > 0.5 { d / (2.0 - max - min) } else { d / (max + min) };
+        // HSL shares its hue with HSV
+        HSL::from_f32(self.to_hsv().h, s, l)
+    }
+
+    /// Converts to CIE L*a*b*.
+    pub fn to_lab(&self) -> Lab {
+        let (r, g, b) = (srgb_to_linear(self.r), srgb_to_linear(self.g), srgb_to_linear(self.b));
+        // Linear sRGB to XYZ, divided by the D65 white point
+        let x = (0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b) / 0.950_47;
+        let y = 0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b;
+        let z = (0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b) / 1.088_83;
+
+        let f = |t : f32| if t > 0.008_856 { t.cbrt() } else { 7.787 * t + 16.0 / 116.0 };
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+        Lab{ l : 116.0 * fy - 16.0, a : 500.0 * (fx - fy), b : 200.0 * (fy - fz) }
+    }
+
+    /// Converts to OKLab.
+    pub fn to_oklab(&self) -> OkLab {
+        let (r, g, b) = (srgb_to_linear(self.r), srgb_to_linear(self.g), srgb_to_linear(self.b));
+        let l = (0.412_221_47 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+        OkLab{
+            l : 0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            a : 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            b : 0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s
+        }
+    }
+
+    /// Lerps between this color and another through OKLab, which keeps the brightness of the
+    /// blend even and avoids the muddy middle of a plain RGB lerp.
+    pub fn lerp_oklab(&self, color : RGB, percent : f32) -> RGB {
+        let (from, to) = (self.to_oklab(), color.to_oklab());
+        OkLab{
+            l : from.l + (to.l - from.l) * percent,
+            a : from.a + (to.a - from.a) * percent,
+            b : from.b + (to.b - from.b) * percent
+        }.to_rgb()
+    }
+
+    /// Lerps between this color and another through HSV, going the short way around the hue
+    /// circle. Good for rainbow effects.
+    pub fn lerp_hsv(&self, color : RGB, percent : f32) -> RGB {
+        let (from, to) = (self.to_hsv(), color.to_hsv());
+        let mut dh = to.h - from.h;
+        if dh > 0.5 { dh -= 1.0; } else if dh < -0.5 { dh += 1.0; }
+        let h = (from.h + dh * percent).rem_euclid(1.0);
+        HSV::from_f32(h, from.s + (to.s - from.s) * percent, from.v + (to.v - from.v) * percent).to_rgb()
+    }
+
+    /// How different two colors look: the distance between them in OKLab. 0 means identical;
+    /// black and white are 1.0 apart.
+    pub fn perceptual_distance(&self, color : RGB) -> f32 {
+        let (a, b) = (self.to_oklab(), color.to_oklab());
+        ((a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)).sqrt()
+    }
+}
+
+/// Converts an sRGB channel (0..1) to linear light.
+pub(crate) fn srgb_to_linear(c : f32) -> f32 {
+    if c <= 0.040_45 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+/// Converts a linear light channel back to sRGB (0..1).
+pub(crate) fn linear_to_srgb(c : f32) -> f32 {
+    let c = f32::max(0.0, c);
+    if c <= 0.003_130_8 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 }
 }
 
 impl HSV {
@@ -228,784 +347,869 @@ impl HSV {
     }
 }
 
+impl HSL {
+    /// Constructs a new HSL color, from 3 32-bit floats
+    pub fn from_f32(h:f32, s:f32, l:f32) -> HSL { HSL{h, s, l} }
+
+    /// Converts an HSL triple to an RGB triple
+    pub fn to_rgb(&self) -> RGB {
+        // HSL and HSV share a hue; convert the lightness and saturation across
+        let v = self.l + self.s * f32::min(self.l, 1.0 - self.l);
+        let s = if v == 0.0 { 0.0 } else { 2.0 * (1.0 - self.l / v) };
+        HSV::from_f32(self.h, s, v).to_rgb()
+    }
+}
+
+impl Lab {
+    /// Constructs a new Lab color
+    pub fn new(l:f32, a:f32, b:f32) -> Lab { Lab{l, a, b} }
+
+    /// Converts back to RGB. Colors outside the RGB gamut are clamped.
+    pub fn to_rgb(&self) -> RGB {
+        let fy = (self.l + 16.0) / 116.0;
+        let fx = fy + self.a / 500.0;
+        let fz = fy - self.b / 200.0;
+        let f_inv = |t : f32| if t.powi(3) > 0.008_856 { t.powi(3) } else { (t - 16.0 / 116.0) / 7.787 };
+        let (x, y, z) = (f_inv(fx) * 0.950_47, f_inv(fy), f_inv(fz) * 1.088_83);
+
+        RGB::from_f32(
+            linear_to_srgb(3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z),
+            linear_to_srgb(-0.969_266 * x + 1.876_010_8 * y + 0.041_556 * z),
+            linear_to_srgb(0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z)
+        )
+    }
+}
+
+impl OkLab {
+    /// Constructs a new OKLab color
+    pub fn new(l:f32, a:f32, b:f32) -> OkLab { OkLab{l, a, b} }
+
+    /// Converts back to RGB. Colors outside the RGB gamut are clamped.
+    pub fn to_rgb(&self) -> RGB {
+        let l = (self.l + 0.396_337_78 * self.a + 0.215_803_76 * self.b).powi(3);
+        let m = (self.l - 0.105_561_346 * self.a - 0.063_854_17 * self.b).powi(3);
+        let s = (self.l - 0.089_484_18 * self.a - 1.291_485_5 * self.b).powi(3);
+
+        RGB::from_f32(
+            linear_to_srgb(4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s),
+            linear_to_srgb(-1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s),
+            linear_to_srgb(-0.004_196_086 * l - 0.703_418_6 * m + 1.707_614_7 * s)
+        )
+    }
+}
+
+/// Declares the named color constants, along with `NAMED_COLORS`, which lists them all by name
+/// so that colors can be looked up from text.
+macro_rules! named_colors {
+    ($($name:ident = ($r:expr, $g:expr, $b:expr),)*) => {
+        $(pub const $name : (u8,u8,u8) = ($r, $g, $b);)*
+
+        /// Every named color constant, by the name of the constant (`"DARK_RED"` for `DARK_RED`).
+        pub(crate) static NAMED_COLORS : &[(&str, (u8,u8,u8))] = &[$((stringify!($name), $name)),*];
+    };
+}
+
 // Named Colors (derived from X11 rgb.txt, which is also the source of HTML/W3C/SVG names)
-pub const SNOW : (u8,u8,u8) = (255, 250, 250);
-pub const GHOST_WHITE : (u8,u8,u8) = (248, 248, 255);
-pub const GHOSTWHITE : (u8,u8,u8) = (248, 248, 255);
-pub const WHITE_SMOKE : (u8,u8,u8) = (245, 245, 245);
-pub const WHITESMOKE : (u8,u8,u8) = (245, 245, 245);
-pub const GAINSBORO : (u8,u8,u8) = (220, 220, 220);
-pub const FLORAL_WHITE : (u8,u8,u8) = (255, 250, 240);
-pub const FLORALWHITE : (u8,u8,u8) = (255, 250, 240);
-pub const OLD_LACE : (u8,u8,u8) = (253, 245, 230);
-pub const OLDLACE : (u8,u8,u8) = (253, 245, 230);
-pub const LINEN : (u8,u8,u8) = (250, 240, 230);
-pub const ANTIQUE_WHITE : (u8,u8,u8) = (250, 235, 215);
-pub const ANTIQUEWHITE : (u8,u8,u8) = (250, 235, 215);
-pub const PAPAYA_WHIP : (u8,u8,u8) = (255, 239, 213);
-pub const PAPAYAWHIP : (u8,u8,u8) = (255, 239, 213);
-pub const BLANCHED_ALMOND : (u8,u8,u8) = (255, 235, 205);
-pub const BLANCHEDALMOND : (u8,u8,u8) = (255, 235, 205);
-pub const BISQUE : (u8,u8,u8) = (255, 228, 196);
-pub const PEACH_PUFF : (u8,u8,u8) = (255, 218, 185);
-pub const PEACHPUFF : (u8,u8,u8) = (255, 218, 185);
-pub const NAVAJO_WHITE : (u8,u8,u8) = (255, 222, 173);
-pub const NAVAJOWHITE : (u8,u8,u8) = (255, 222, 173);
-pub const MOCCASIN : (u8,u8,u8) = (255, 228, 181);
-pub const CORNSILK : (u8,u8,u8) = (255, 248, 220);
-pub const IVORY : (u8,u8,u8) = (255, 255, 240);
-pub const LEMON_CHIFFON : (u8,u8,u8) = (255, 250, 205);
-pub const LEMONCHIFFON : (u8,u8,u8) = (255, 250, 205);
-pub const SEASHELL : (u8,u8,u8) = (255, 245, 238);
-pub const HONEYDEW : (u8,u8,u8) = (240, 255, 240);
-pub const MINT_CREAM : (u8,u8,u8) = (245, 255, 250);
-pub const MINTCREAM : (u8,u8,u8) = (245, 255, 250);
-pub const AZURE : (u8,u8,u8) = (240, 255, 255);
-pub const ALICE_BLUE : (u8,u8,u8) = (240, 248, 255);
-pub const ALICEBLUE : (u8,u8,u8) = (240, 248, 255);
-pub const LAVENDER : (u8,u8,u8) = (230, 230, 250);
-pub const LAVENDER_BLUSH : (u8,u8,u8) = (255, 240, 245);
-pub const LAVENDERBLUSH : (u8,u8,u8) = (255, 240, 245);
-pub const MISTY_ROSE : (u8,u8,u8) = (255, 228, 225);
-pub const MISTYROSE : (u8,u8,u8) = (255, 228, 225);
-pub const WHITE : (u8,u8,u8) = (255, 255, 255);
-pub const BLACK : (u8,u8,u8) = (0, 0, 0);
-pub const DARK_SLATE : (u8,u8,u8) = (47, 79, 79);
-pub const DARKSLATEGRAY : (u8,u8,u8) = (47, 79, 79);
-pub const DARKSLATEGREY : (u8,u8,u8) = (47, 79, 79);
-pub const DIM_GRAY : (u8,u8,u8) = (105, 105, 105);
-pub const DIMGRAY : (u8,u8,u8) = (105, 105, 105);
-pub const DIM_GREY : (u8,u8,u8) = (105, 105, 105);
-pub const DIMGREY : (u8,u8,u8) = (105, 105, 105);
-pub const SLATE_GRAY : (u8,u8,u8) = (112, 128, 144);
-pub const SLATEGRAY : (u8,u8,u8) = (112, 128, 144);
-pub const SLATE_GREY : (u8,u8,u8) = (112, 128, 144);
-pub const SLATEGREY : (u8,u8,u8) = (112, 128, 144);
-pub const LIGHT_SLATE : (u8,u8,u8) = (119, 136, 153);
-pub const LIGHTSLATEGRAY : (u8,u8,u8) = (119, 136, 153);
-pub const LIGHTSLATEGREY : (u8,u8,u8) = (119, 136, 153);
-pub const GRAY : (u8,u8,u8) = (190, 190, 190);
-pub const GREY : (u8,u8,u8) = (190, 190, 190);
-pub const X11_GRAY : (u8,u8,u8) = (190, 190, 190);
-pub const X11GRAY : (u8,u8,u8) = (190, 190, 190);
-pub const X11_GREY : (u8,u8,u8) = (190, 190, 190);
-pub const X11GREY : (u8,u8,u8) = (190, 190, 190);
-pub const WEB_GRAY : (u8,u8,u8) = (128, 128, 128);
-pub const WEBGRAY : (u8,u8,u8) = (128, 128, 128);
-pub const WEB_GREY : (u8,u8,u8) = (128, 128, 128);
-pub const WEBGREY : (u8,u8,u8) = (128, 128, 128);
-pub const LIGHT_GREY : (u8,u8,u8) = (211, 211, 211);
-pub const LIGHTGREY : (u8,u8,u8) = (211, 211, 211);
-pub const LIGHT_GRAY : (u8,u8,u8) = (211, 211, 211);
-pub const LIGHTGRAY : (u8,u8,u8) = (211, 211, 211);
-pub const MIDNIGHT_BLUE : (u8,u8,u8) = (25, 25, 112);
-pub const MIDNIGHTBLUE : (u8,u8,u8) = (25, 25, 112);
-pub const NAVY : (u8,u8,u8) = (0, 0, 128);
-pub const NAVY_BLUE : (u8,u8,u8) = (0, 0, 128);
-pub const NAVYBLUE : (u8,u8,u8) = (0, 0, 128);
-pub const CORNFLOWER_BLUE : (u8,u8,u8) = (100, 149, 237);
-pub const CORNFLOWERBLUE : (u8,u8,u8) = (100, 149, 237);
-pub const DARKSLATEBLUE : (u8,u8,u8) = (72, 61, 139);
-pub const SLATE_BLUE : (u8,u8,u8) = (106, 90, 205);
-pub const SLATEBLUE : (u8,u8,u8) = (106, 90, 205);
-pub const MEDIUM_SLATE : (u8,u8,u8) = (123, 104, 238);
-pub const MEDIUMSLATEBLUE : (u8,u8,u8) = (123, 104, 238);
-pub const LIGHTSLATEBLUE : (u8,u8,u8) = (132, 112, 255);
-pub const MEDIUM_BLUE : (u8,u8,u8) = (0, 0, 205);
-pub const MEDIUMBLUE : (u8,u8,u8) = (0, 0, 205);
-pub const ROYAL_BLUE : (u8,u8,u8) = (65, 105, 225);
-pub const ROYALBLUE : (u8,u8,u8) = (65, 105, 225);
-pub const BLUE : (u8,u8,u8) = (0, 0, 255);
-pub const DODGER_BLUE : (u8,u8,u8) = (30, 144, 255);
-pub const DODGERBLUE : (u8,u8,u8) = (30, 144, 255);
-pub const DEEP_SKY : (u8,u8,u8) = (0, 191, 255);
-pub const DEEPSKYBLUE : (u8,u8,u8) = (0, 191, 255);
-pub const SKY_BLUE : (u8,u8,u8) = (135, 206, 235);
-pub const SKYBLUE : (u8,u8,u8) = (135, 206, 235);
-pub const LIGHT_SKY : (u8,u8,u8) = (135, 206, 250);
-pub const LIGHTSKYBLUE : (u8,u8,u8) = (135, 206, 250);
-pub const STEEL_BLUE : (u8,u8,u8) = (70, 130, 180);
-pub const STEELBLUE : (u8,u8,u8) = (70, 130, 180);
-pub const LIGHT_STEEL : (u8,u8,u8) = (176, 196, 222);
-pub const LIGHTSTEELBLUE : (u8,u8,u8) = (176, 196, 222);
-pub const LIGHT_BLUE : (u8,u8,u8) = (173, 216, 230);
-pub const LIGHTBLUE : (u8,u8,u8) = (173, 216, 230);
-pub const POWDER_BLUE : (u8,u8,u8) = (176, 224, 230);
-pub const POWDERBLUE : (u8,u8,u8) = (176, 224, 230);
-pub const PALE_TURQUOISE : (u8,u8,u8) = (175, 238, 238);
-pub const PALETURQUOISE : (u8,u8,u8) = (175, 238, 238);
-pub const DARK_TURQUOISE : (u8,u8,u8) = (0, 206, 209);
-pub const DARKTURQUOISE : (u8,u8,u8) = (0, 206, 209);
-pub const MEDIUM_TURQUOISE : (u8,u8,u8) = (72, 209, 204);
-pub const MEDIUMTURQUOISE : (u8,u8,u8) = (72, 209, 204);
-pub const TURQUOISE : (u8,u8,u8) = (64, 224, 208);
-pub const CYAN : (u8,u8,u8) = (0, 255, 255);
-pub const AQUA : (u8,u8,u8) = (0, 255, 255);
-pub const LIGHT_CYAN : (u8,u8,u8) = (224, 255, 255);
-pub const LIGHTCYAN : (u8,u8,u8) = (224, 255, 255);
-pub const CADET_BLUE : (u8,u8,u8) = (95, 158, 160);
-pub const CADETBLUE : (u8,u8,u8) = (95, 158, 160);
-pub const MEDIUM_AQUAMARINE : (u8,u8,u8) = (102, 205, 170);
-pub const MEDIUMAQUAMARINE : (u8,u8,u8) = (102, 205, 170);
-pub const AQUAMARINE : (u8,u8,u8) = (127, 255, 212);
-pub const DARK_GREEN : (u8,u8,u8) = (0, 100, 0);
-pub const DARKGREEN : (u8,u8,u8) = (0, 100, 0);
-pub const DARK_OLIVE : (u8,u8,u8) = (85, 107, 47);
-pub const DARKOLIVEGREEN : (u8,u8,u8) = (85, 107, 47);
-pub const DARK_SEA : (u8,u8,u8) = (143, 188, 143);
-pub const DARKSEAGREEN : (u8,u8,u8) = (143, 188, 143);
-pub const SEA_GREEN : (u8,u8,u8) = (46, 139, 87);
-pub const SEAGREEN : (u8,u8,u8) = (46, 139, 87);
-pub const MEDIUM_SEA : (u8,u8,u8) = (60, 179, 113);
-pub const MEDIUMSEAGREEN : (u8,u8,u8) = (60, 179, 113);
-pub const LIGHT_SEA : (u8,u8,u8) = (32, 178, 170);
-pub const LIGHTSEAGREEN : (u8,u8,u8) = (32, 178, 170);
-pub const PALE_GREEN : (u8,u8,u8) = (152, 251, 152);
-pub const PALEGREEN : (u8,u8,u8) = (152, 251, 152);
-pub const SPRING_GREEN : (u8,u8,u8) = (0, 255, 127);
-pub const SPRINGGREEN : (u8,u8,u8) = (0, 255, 127);
-pub const LAWN_GREEN : (u8,u8,u8) = (124, 252, 0);
-pub const LAWNGREEN : (u8,u8,u8) = (124, 252, 0);
-pub const GREEN : (u8,u8,u8) = (0, 255, 0);
-pub const LIME : (u8,u8,u8) = (0, 255, 0);
-pub const X11_GREEN : (u8,u8,u8) = (0, 255, 0);
-pub const X11GREEN : (u8,u8,u8) = (0, 255, 0);
-pub const WEB_GREEN : (u8,u8,u8) = (0, 128, 0);
-pub const WEBGREEN : (u8,u8,u8) = (0, 128, 0);
-pub const CHARTREUSE : (u8,u8,u8) = (127, 255, 0);
-pub const MEDIUM_SPRING : (u8,u8,u8) = (0, 250, 154);
-pub const MEDIUMSPRINGGREEN : (u8,u8,u8) = (0, 250, 154);
-pub const GREEN_YELLOW : (u8,u8,u8) = (173, 255, 47);
-pub const GREENYELLOW : (u8,u8,u8) = (173, 255, 47);
-pub const LIME_GREEN : (u8,u8,u8) = (50, 205, 50);
-pub const LIMEGREEN : (u8,u8,u8) = (50, 205, 50);
-pub const YELLOW_GREEN : (u8,u8,u8) = (154, 205, 50);
-pub const YELLOWGREEN : (u8,u8,u8) = (154, 205, 50);
-pub const FOREST_GREEN : (u8,u8,u8) = (34, 139, 34);
-pub const FORESTGREEN : (u8,u8,u8) = (34, 139, 34);
-pub const OLIVE_DRAB : (u8,u8,u8) = (107, 142, 35);
-pub const OLIVEDRAB : (u8,u8,u8) = (107, 142, 35);
-pub const DARK_KHAKI : (u8,u8,u8) = (189, 183, 107);
-pub const DARKKHAKI : (u8,u8,u8) = (189, 183, 107);
-pub const KHAKI : (u8,u8,u8) = (240, 230, 140);
-pub const PALE_GOLDENROD : (u8,u8,u8) = (238, 232, 170);
-pub const PALEGOLDENROD : (u8,u8,u8) = (238, 232, 170);
-pub const LIGHT_GOLDENROD : (u8,u8,u8) = (250, 250, 210);
-pub const LIGHTGOLDENRODYELLOW : (u8,u8,u8) = (250, 250, 210);
-pub const LIGHT_YELLOW : (u8,u8,u8) = (255, 255, 224);
-pub const LIGHTYELLOW : (u8,u8,u8) = (255, 255, 224);
-pub const YELLOW : (u8,u8,u8) = (255, 255, 0);
-pub const GOLD : (u8,u8,u8) = (255, 215, 0);
-pub const LIGHTGOLDENROD : (u8,u8,u8) = (238, 221, 130);
-pub const GOLDENROD : (u8,u8,u8) = (218, 165, 32);
-pub const DARK_GOLDENROD : (u8,u8,u8) = (184, 134, 11);
-pub const DARKGOLDENROD : (u8,u8,u8) = (184, 134, 11);
-pub const ROSY_BROWN : (u8,u8,u8) = (188, 143, 143);
-pub const ROSYBROWN : (u8,u8,u8) = (188, 143, 143);
-pub const INDIAN_RED : (u8,u8,u8) = (205, 92, 92);
-pub const INDIANRED : (u8,u8,u8) = (205, 92, 92);
-pub const SADDLE_BROWN : (u8,u8,u8) = (139, 69, 19);
-pub const SADDLEBROWN : (u8,u8,u8) = (139, 69, 19);
-pub const SIENNA : (u8,u8,u8) = (160, 82, 45);
-pub const PERU : (u8,u8,u8) = (205, 133, 63);
-pub const BURLYWOOD : (u8,u8,u8) = (222, 184, 135);
-pub const BEIGE : (u8,u8,u8) = (245, 245, 220);
-pub const WHEAT : (u8,u8,u8) = (245, 222, 179);
-pub const SANDY_BROWN : (u8,u8,u8) = (244, 164, 96);
-pub const SANDYBROWN : (u8,u8,u8) = (244, 164, 96);
-pub const TAN : (u8,u8,u8) = (210, 180, 140);
-pub const CHOCOLATE : (u8,u8,u8) = (210, 105, 30);
-pub const FIREBRICK_34 : (u8,u8,u8) = (178, 34, 34);
-pub const BROWN_42 : (u8,u8,u8) = (165, 42, 42);
-pub const DARK_SALMON : (u8,u8,u8) = (233, 150, 122);
-pub const DARKSALMON : (u8,u8,u8) = (233, 150, 122);
-pub const SALMON : (u8,u8,u8) = (250, 128, 114);
-pub const LIGHT_SALMON : (u8,u8,u8) = (255, 160, 122);
-pub const LIGHTSALMON : (u8,u8,u8) = (255, 160, 122);
-pub const ORANGE : (u8,u8,u8) = (255, 165, 0);
-pub const DARK_ORANGE : (u8,u8,u8) = (255, 140, 0);
-pub const DARKORANGE : (u8,u8,u8) = (255, 140, 0);
-pub const CORAL : (u8,u8,u8) = (255, 127, 80);
-pub const LIGHT_CORAL : (u8,u8,u8) = (240, 128, 128);
-pub const LIGHTCORAL : (u8,u8,u8) = (240, 128, 128);
-pub const TOMATO : (u8,u8,u8) = (255, 99, 71);
-pub const ORANGE_RED : (u8,u8,u8) = (255, 69, 0);
-pub const ORANGERED : (u8,u8,u8) = (255, 69, 0);
-pub const RED : (u8,u8,u8) = (255, 0, 0);
-pub const HOT_PINK : (u8,u8,u8) = (255, 105, 180);
-pub const HOTPINK : (u8,u8,u8) = (255, 105, 180);
-pub const DEEP_PINK : (u8,u8,u8) = (255, 20, 147);
-pub const DEEPPINK : (u8,u8,u8) = (255, 20, 147);
-pub const PINK : (u8,u8,u8) = (255, 192, 203);
-pub const LIGHT_PINK : (u8,u8,u8) = (255, 182, 193);
-pub const LIGHTPINK : (u8,u8,u8) = (255, 182, 193);
-pub const PALE_VIOLET : (u8,u8,u8) = (219, 112, 147);
-pub const PALEVIOLETRED : (u8,u8,u8) = (219, 112, 147);
-pub const MAROON : (u8,u8,u8) = (176, 48, 96);
-pub const X11_MAROON : (u8,u8,u8) = (176, 48, 96);
-pub const X11MAROON : (u8,u8,u8) = (176, 48, 96);
-pub const WEB_MAROON : (u8,u8,u8) = (128, 0, 0);
-pub const WEBMAROON : (u8,u8,u8) = (128, 0, 0);
-pub const MEDIUM_VIOLET : (u8,u8,u8) = (199, 21, 133);
-pub const MEDIUMVIOLETRED : (u8,u8,u8) = (199, 21, 133);
-pub const VIOLET_RED : (u8,u8,u8) = (208, 32, 144);
-pub const VIOLETRED : (u8,u8,u8) = (208, 32, 144);
-pub const MAGENTA : (u8,u8,u8) = (255, 0, 255);
-pub const FUCHSIA : (u8,u8,u8) = (255, 0, 255);
-pub const VIOLET : (u8,u8,u8) = (238, 130, 238);
-pub const PLUM : (u8,u8,u8) = (221, 160, 221);
-pub const ORCHID : (u8,u8,u8) = (218, 112, 214);
-pub const MEDIUM_ORCHID : (u8,u8,u8) = (186, 85, 211);
-pub const MEDIUMORCHID : (u8,u8,u8) = (186, 85, 211);
-pub const DARK_ORCHID : (u8,u8,u8) = (153, 50, 204);
-pub const DARKORCHID : (u8,u8,u8) = (153, 50, 204);
-pub const DARK_VIOLET : (u8,u8,u8) = (148, 0, 211);
-pub const DARKVIOLET : (u8,u8,u8) = (148, 0, 211);
-pub const BLUE_VIOLET : (u8,u8,u8) = (138, 43, 226);
-pub const BLUEVIOLET : (u8,u8,u8) = (138, 43, 226);
-pub const PURPLE : (u8,u8,u8) = (160, 32, 240);
-pub const X11_PURPLE : (u8,u8,u8) = (160, 32, 240);
-pub const X11PURPLE : (u8,u8,u8) = (160, 32, 240);
-pub const WEB_PURPLE : (u8,u8,u8) = (128, 0, 128);
-pub const WEBPURPLE : (u8,u8,u8) = (128, 0, 128);
-pub const MEDIUM_PURPLE : (u8,u8,u8) = (147, 112, 219);
-pub const MEDIUMPURPLE : (u8,u8,u8) = (147, 112, 219);
-pub const THISTLE : (u8,u8,u8) = (216, 191, 216);
-pub const SNOW1 : (u8,u8,u8) = (255, 250, 250);
-pub const SNOW2 : (u8,u8,u8) = (238, 233, 233);
-pub const SNOW3 : (u8,u8,u8) = (205, 201, 201);
-pub const SNOW4 : (u8,u8,u8) = (139, 137, 137);
-pub const SEASHELL1 : (u8,u8,u8) = (255, 245, 238);
-pub const SEASHELL2 : (u8,u8,u8) = (238, 229, 222);
-pub const SEASHELL3 : (u8,u8,u8) = (205, 197, 191);
-pub const SEASHELL4 : (u8,u8,u8) = (139, 134, 130);
-pub const ANTIQUEWHITE1 : (u8,u8,u8) = (255, 239, 219);
-pub const ANTIQUEWHITE2 : (u8,u8,u8) = (238, 223, 204);
-pub const ANTIQUEWHITE3 : (u8,u8,u8) = (205, 192, 176);
-pub const ANTIQUEWHITE4 : (u8,u8,u8) = (139, 131, 120);
-pub const BISQUE1 : (u8,u8,u8) = (255, 228, 196);
-pub const BISQUE2 : (u8,u8,u8) = (238, 213, 183);
-pub const BISQUE3 : (u8,u8,u8) = (205, 183, 158);
-pub const BISQUE4 : (u8,u8,u8) = (139, 125, 107);
-pub const PEACHPUFF1 : (u8,u8,u8) = (255, 218, 185);
-pub const PEACHPUFF2 : (u8,u8,u8) = (238, 203, 173);
-pub const PEACHPUFF3 : (u8,u8,u8) = (205, 175, 149);
-pub const PEACHPUFF4 : (u8,u8,u8) = (139, 119, 101);
-pub const NAVAJOWHITE1 : (u8,u8,u8) = (255, 222, 173);
-pub const NAVAJOWHITE2 : (u8,u8,u8) = (238, 207, 161);
-pub const NAVAJOWHITE3 : (u8,u8,u8) = (205, 179, 139);
-pub const NAVAJOWHITE4 : (u8,u8,u8) = (139, 121, 94);
-pub const LEMONCHIFFON1 : (u8,u8,u8) = (255, 250, 205);
-pub const LEMONCHIFFON2 : (u8,u8,u8) = (238, 233, 191);
-pub const LEMONCHIFFON3 : (u8,u8,u8) = (205, 201, 165);
-pub const LEMONCHIFFON4 : (u8,u8,u8) = (139, 137, 112);
-pub const CORNSILK1 : (u8,u8,u8) = (255, 248, 220);
-pub const CORNSILK2 : (u8,u8,u8) = (238, 232, 205);
-pub const CORNSILK3 : (u8,u8,u8) = (205, 200, 177);
-pub const CORNSILK4 : (u8,u8,u8) = (139, 136, 120);
-pub const IVORY1 : (u8,u8,u8) = (255, 255, 240);
-pub const IVORY2 : (u8,u8,u8) = (238, 238, 224);
-pub const IVORY3 : (u8,u8,u8) = (205, 205, 193);
-pub const IVORY4 : (u8,u8,u8) = (139, 139, 131);
-pub const HONEYDEW1 : (u8,u8,u8) = (240, 255, 240);
-pub const HONEYDEW2 : (u8,u8,u8) = (224, 238, 224);
-pub const HONEYDEW3 : (u8,u8,u8) = (193, 205, 193);
-pub const HONEYDEW4 : (u8,u8,u8) = (131, 139, 131);
-pub const LAVENDERBLUSH1 : (u8,u8,u8) = (255, 240, 245);
-pub const LAVENDERBLUSH2 : (u8,u8,u8) = (238, 224, 229);
-pub const LAVENDERBLUSH3 : (u8,u8,u8) = (205, 193, 197);
-pub const LAVENDERBLUSH4 : (u8,u8,u8) = (139, 131, 134);
-pub const MISTYROSE1 : (u8,u8,u8) = (255, 228, 225);
-pub const MISTYROSE2 : (u8,u8,u8) = (238, 213, 210);
-pub const MISTYROSE3 : (u8,u8,u8) = (205, 183, 181);
-pub const MISTYROSE4 : (u8,u8,u8) = (139, 125, 123);
-pub const AZURE1 : (u8,u8,u8) = (240, 255, 255);
-pub const AZURE2 : (u8,u8,u8) = (224, 238, 238);
-pub const AZURE3 : (u8,u8,u8) = (193, 205, 205);
-pub const AZURE4 : (u8,u8,u8) = (131, 139, 139);
-pub const SLATEBLUE1 : (u8,u8,u8) = (131, 111, 255);
-pub const SLATEBLUE2 : (u8,u8,u8) = (122, 103, 238);
-pub const SLATEBLUE3 : (u8,u8,u8) = (105, 89, 205);
-pub const SLATEBLUE4 : (u8,u8,u8) = (71, 60, 139);
-pub const ROYALBLUE1 : (u8,u8,u8) = (72, 118, 255);
-pub const ROYALBLUE2 : (u8,u8,u8) = (67, 110, 238);
-pub const ROYALBLUE3 : (u8,u8,u8) = (58, 95, 205);
-pub const ROYALBLUE4 : (u8,u8,u8) = (39, 64, 139);
-pub const BLUE1 : (u8,u8,u8) = (0, 0, 255);
-pub const BLUE2 : (u8,u8,u8) = (0, 0, 238);
-pub const BLUE3 : (u8,u8,u8) = (0, 0, 205);
-pub const BLUE4 : (u8,u8,u8) = (0, 0, 139);
-pub const DODGERBLUE1 : (u8,u8,u8) = (30, 144, 255);
-pub const DODGERBLUE2 : (u8,u8,u8) = (28, 134, 238);
-pub const DODGERBLUE3 : (u8,u8,u8) = (24, 116, 205);
-pub const DODGERBLUE4 : (u8,u8,u8) = (16, 78, 139);
-pub const STEELBLUE1 : (u8,u8,u8) = (99, 184, 255);
-pub const STEELBLUE2 : (u8,u8,u8) = (92, 172, 238);
-pub const STEELBLUE3 : (u8,u8,u8) = (79, 148, 205);
-pub const STEELBLUE4 : (u8,u8,u8) = (54, 100, 139);
-pub const DEEPSKYBLUE1 : (u8,u8,u8) = (0, 191, 255);
-pub const DEEPSKYBLUE2 : (u8,u8,u8) = (0, 178, 238);
-pub const DEEPSKYBLUE3 : (u8,u8,u8) = (0, 154, 205);
-pub const DEEPSKYBLUE4 : (u8,u8,u8) = (0, 104, 139);
-pub const SKYBLUE1 : (u8,u8,u8) = (135, 206, 255);
-pub const SKYBLUE2 : (u8,u8,u8) = (126, 192, 238);
-pub const SKYBLUE3 : (u8,u8,u8) = (108, 166, 205);
-pub const SKYBLUE4 : (u8,u8,u8) = (74, 112, 139);
-pub const LIGHTSKYBLUE1 : (u8,u8,u8) = (176, 226, 255);
-pub const LIGHTSKYBLUE2 : (u8,u8,u8) = (164, 211, 238);
-pub const LIGHTSKYBLUE3 : (u8,u8,u8) = (141, 182, 205);
-pub const LIGHTSKYBLUE4 : (u8,u8,u8) = (96, 123, 139);
-pub const SLATEGRAY1 : (u8,u8,u8) = (198, 226, 255);
-pub const SLATEGRAY2 : (u8,u8,u8) = (185, 211, 238);
-pub const SLATEGRAY3 : (u8,u8,u8) = (159, 182, 205);
-pub const SLATEGRAY4 : (u8,u8,u8) = (108, 123, 139);
-pub const LIGHTSTEELBLUE1 : (u8,u8,u8) = (202, 225, 255);
-pub const LIGHTSTEELBLUE2 : (u8,u8,u8) = (188, 210, 238);
-pub const LIGHTSTEELBLUE3 : (u8,u8,u8) = (162, 181, 205);
-pub const LIGHTSTEELBLUE4 : (u8,u8,u8) = (110, 123, 139);
-pub const LIGHTBLUE1 : (u8,u8,u8) = (191, 239, 255);
-pub const LIGHTBLUE2 : (u8,u8,u8) = (178, 223, 238);
-pub const LIGHTBLUE3 : (u8,u8,u8) = (154, 192, 205);
-pub const LIGHTBLUE4 : (u8,u8,u8) = (104, 131, 139);
-pub const LIGHTCYAN1 : (u8,u8,u8) = (224, 255, 255);
-pub const LIGHTCYAN2 : (u8,u8,u8) = (209, 238, 238);
-pub const LIGHTCYAN3 : (u8,u8,u8) = (180, 205, 205);
-pub const LIGHTCYAN4 : (u8,u8,u8) = (122, 139, 139);
-pub const PALETURQUOISE1 : (u8,u8,u8) = (187, 255, 255);
-pub const PALETURQUOISE2 : (u8,u8,u8) = (174, 238, 238);
-pub const PALETURQUOISE3 : (u8,u8,u8) = (150, 205, 205);
-pub const PALETURQUOISE4 : (u8,u8,u8) = (102, 139, 139);
-pub const CADETBLUE1 : (u8,u8,u8) = (152, 245, 255);
-pub const CADETBLUE2 : (u8,u8,u8) = (142, 229, 238);
-pub const CADETBLUE3 : (u8,u8,u8) = (122, 197, 205);
-pub const CADETBLUE4 : (u8,u8,u8) = (83, 134, 139);
-pub const TURQUOISE1 : (u8,u8,u8) = (0, 245, 255);
-pub const TURQUOISE2 : (u8,u8,u8) = (0, 229, 238);
-pub const TURQUOISE3 : (u8,u8,u8) = (0, 197, 205);
-pub const TURQUOISE4 : (u8,u8,u8) = (0, 134, 139);
-pub const CYAN1 : (u8,u8,u8) = (0, 255, 255);
-pub const CYAN2 : (u8,u8,u8) = (0, 238, 238);
-pub const CYAN3 : (u8,u8,u8) = (0, 205, 205);
-pub const CYAN4 : (u8,u8,u8) = (0, 139, 139);
-pub const DARKSLATEGRAY1 : (u8,u8,u8) = (151, 255, 255);
-pub const DARKSLATEGRAY2 : (u8,u8,u8) = (141, 238, 238);
-pub const DARKSLATEGRAY3 : (u8,u8,u8) = (121, 205, 205);
-pub const DARKSLATEGRAY4 : (u8,u8,u8) = (82, 139, 139);
-pub const AQUAMARINE1 : (u8,u8,u8) = (127, 255, 212);
-pub const AQUAMARINE2 : (u8,u8,u8) = (118, 238, 198);
-pub const AQUAMARINE3 : (u8,u8,u8) = (102, 205, 170);
-pub const AQUAMARINE4 : (u8,u8,u8) = (69, 139, 116);
-pub const DARKSEAGREEN1 : (u8,u8,u8) = (193, 255, 193);
-pub const DARKSEAGREEN2 : (u8,u8,u8) = (180, 238, 180);
-pub const DARKSEAGREEN3 : (u8,u8,u8) = (155, 205, 155);
-pub const DARKSEAGREEN4 : (u8,u8,u8) = (105, 139, 105);
-pub const SEAGREEN1 : (u8,u8,u8) = (84, 255, 159);
-pub const SEAGREEN2 : (u8,u8,u8) = (78, 238, 148);
-pub const SEAGREEN3 : (u8,u8,u8) = (67, 205, 128);
-pub const SEAGREEN4 : (u8,u8,u8) = (46, 139, 87);
-pub const PALEGREEN1 : (u8,u8,u8) = (154, 255, 154);
-pub const PALEGREEN2 : (u8,u8,u8) = (144, 238, 144);
-pub const PALEGREEN3 : (u8,u8,u8) = (124, 205, 124);
-pub const PALEGREEN4 : (u8,u8,u8) = (84, 139, 84);
-pub const SPRINGGREEN1 : (u8,u8,u8) = (0, 255, 127);
-pub const SPRINGGREEN2 : (u8,u8,u8) = (0, 238, 118);
-pub const SPRINGGREEN3 : (u8,u8,u8) = (0, 205, 102);
-pub const SPRINGGREEN4 : (u8,u8,u8) = (0, 139, 69);
-pub const GREEN1 : (u8,u8,u8) = (0, 255, 0);
-pub const GREEN2 : (u8,u8,u8) = (0, 238, 0);
-pub const GREEN3 : (u8,u8,u8) = (0, 205, 0);
-pub const GREEN4 : (u8,u8,u8) = (0, 139, 0);
-pub const CHARTREUSE1 : (u8,u8,u8) = (127, 255, 0);
-pub const CHARTREUSE2 : (u8,u8,u8) = (118, 238, 0);
-pub const CHARTREUSE3 : (u8,u8,u8) = (102, 205, 0);
-pub const CHARTREUSE4 : (u8,u8,u8) = (69, 139, 0);
-pub const OLIVEDRAB1 : (u8,u8,u8) = (192, 255, 62);
-pub const OLIVEDRAB2 : (u8,u8,u8) = (179, 238, 58);
-pub const OLIVEDRAB3 : (u8,u8,u8) = (154, 205, 50);
-pub const OLIVEDRAB4 : (u8,u8,u8) = (105, 139, 34);
-pub const DARKOLIVEGREEN1 : (u8,u8,u8) = (202, 255, 112);
-pub const DARKOLIVEGREEN2 : (u8,u8,u8) = (188, 238, 104);
-pub const DARKOLIVEGREEN3 : (u8,u8,u8) = (162, 205, 90);
-pub const DARKOLIVEGREEN4 : (u8,u8,u8) = (110, 139, 61);
-pub const KHAKI1 : (u8,u8,u8) = (255, 246, 143);
-pub const KHAKI2 : (u8,u8,u8) = (238, 230, 133);
-pub const KHAKI3 : (u8,u8,u8) = (205, 198, 115);
-pub const KHAKI4 : (u8,u8,u8) = (139, 134, 78);
-pub const LIGHTGOLDENROD1 : (u8,u8,u8) = (255, 236, 139);
-pub const LIGHTGOLDENROD2 : (u8,u8,u8) = (238, 220, 130);
-pub const LIGHTGOLDENROD3 : (u8,u8,u8) = (205, 190, 112);
-pub const LIGHTGOLDENROD4 : (u8,u8,u8) = (139, 129, 76);
-pub const LIGHTYELLOW1 : (u8,u8,u8) = (255, 255, 224);
-pub const LIGHTYELLOW2 : (u8,u8,u8) = (238, 238, 209);
-pub const LIGHTYELLOW3 : (u8,u8,u8) = (205, 205, 180);
-pub const LIGHTYELLOW4 : (u8,u8,u8) = (139, 139, 122);
-pub const YELLOW1 : (u8,u8,u8) = (255, 255, 0);
-pub const YELLOW2 : (u8,u8,u8) = (238, 238, 0);
-pub const YELLOW3 : (u8,u8,u8) = (205, 205, 0);
-pub const YELLOW4 : (u8,u8,u8) = (139, 139, 0);
-pub const GOLD1 : (u8,u8,u8) = (255, 215, 0);
-pub const GOLD2 : (u8,u8,u8) = (238, 201, 0);
-pub const GOLD3 : (u8,u8,u8) = (205, 173, 0);
-pub const GOLD4 : (u8,u8,u8) = (139, 117, 0);
-pub const GOLDENROD1 : (u8,u8,u8) = (255, 193, 37);
-pub const GOLDENROD2 : (u8,u8,u8) = (238, 180, 34);
-pub const GOLDENROD3 : (u8,u8,u8) = (205, 155, 29);
-pub const GOLDENROD4 : (u8,u8,u8) = (139, 105, 20);
-pub const DARKGOLDENROD1 : (u8,u8,u8) = (255, 185, 15);
-pub const DARKGOLDENROD2 : (u8,u8,u8) = (238, 173, 14);
-pub const DARKGOLDENROD3 : (u8,u8,u8) = (205, 149, 12);
-pub const DARKGOLDENROD4 : (u8,u8,u8) = (139, 101, 8);
-pub const ROSYBROWN1 : (u8,u8,u8) = (255, 193, 193);
-pub const ROSYBROWN2 : (u8,u8,u8) = (238, 180, 180);
-pub const ROSYBROWN3 : (u8,u8,u8) = (205, 155, 155);
-pub const ROSYBROWN4 : (u8,u8,u8) = (139, 105, 105);
-pub const INDIANRED1 : (u8,u8,u8) = (255, 106, 106);
-pub const INDIANRED2 : (u8,u8,u8) = (238, 99, 99);
-pub const INDIANRED3 : (u8,u8,u8) = (205, 85, 85);
-pub const INDIANRED4 : (u8,u8,u8) = (139, 58, 58);
-pub const SIENNA1 : (u8,u8,u8) = (255, 130, 71);
-pub const SIENNA2 : (u8,u8,u8) = (238, 121, 66);
-pub const SIENNA3 : (u8,u8,u8) = (205, 104, 57);
-pub const SIENNA4 : (u8,u8,u8) = (139, 71, 38);
-pub const BURLYWOOD1 : (u8,u8,u8) = (255, 211, 155);
-pub const BURLYWOOD2 : (u8,u8,u8) = (238, 197, 145);
-pub const BURLYWOOD3 : (u8,u8,u8) = (205, 170, 125);
-pub const BURLYWOOD4 : (u8,u8,u8) = (139, 115, 85);
-pub const WHEAT1 : (u8,u8,u8) = (255, 231, 186);
-pub const WHEAT2 : (u8,u8,u8) = (238, 216, 174);
-pub const WHEAT3 : (u8,u8,u8) = (205, 186, 150);
-pub const WHEAT4 : (u8,u8,u8) = (139, 126, 102);
-pub const TAN1 : (u8,u8,u8) = (255, 165, 79);
-pub const TAN2 : (u8,u8,u8) = (238, 154, 73);
-pub const TAN3 : (u8,u8,u8) = (205, 133, 63);
-pub const TAN4 : (u8,u8,u8) = (139, 90, 43);
-pub const CHOCOLATE1 : (u8,u8,u8) = (255, 127, 36);
-pub const CHOCOLATE2 : (u8,u8,u8) = (238, 118, 33);
-pub const CHOCOLATE3 : (u8,u8,u8) = (205, 102, 29);
-pub const CHOCOLATE4 : (u8,u8,u8) = (139, 69, 19);
-pub const FIREBRICK1 : (u8,u8,u8) = (255, 48, 48);
-pub const FIREBRICK2 : (u8,u8,u8) = (238, 44, 44);
-pub const FIREBRICK3 : (u8,u8,u8) = (205, 38, 38);
-pub const FIREBRICK4 : (u8,u8,u8) = (139, 26, 26);
-pub const BROWN1 : (u8,u8,u8) = (255, 64, 64);
-pub const BROWN2 : (u8,u8,u8) = (238, 59, 59);
-pub const BROWN3 : (u8,u8,u8) = (205, 51, 51);
-pub const BROWN4 : (u8,u8,u8) = (139, 35, 35);
-pub const SALMON1 : (u8,u8,u8) = (255, 140, 105);
-pub const SALMON2 : (u8,u8,u8) = (238, 130, 98);
-pub const SALMON3 : (u8,u8,u8) = (205, 112, 84);
-pub const SALMON4 : (u8,u8,u8) = (139, 76, 57);
-pub const LIGHTSALMON1 : (u8,u8,u8) = (255, 160, 122);
-pub const LIGHTSALMON2 : (u8,u8,u8) = (238, 149, 114);
-pub const LIGHTSALMON3 : (u8,u8,u8) = (205, 129, 98);
-pub const LIGHTSALMON4 : (u8,u8,u8) = (139, 87, 66);
-pub const ORANGE1 : (u8,u8,u8) = (255, 165, 0);
-pub const ORANGE2 : (u8,u8,u8) = (238, 154, 0);
-pub const ORANGE3 : (u8,u8,u8) = (205, 133, 0);
-pub const ORANGE4 : (u8,u8,u8) = (139, 90, 0);
-pub const DARKORANGE1 : (u8,u8,u8) = (255, 127, 0);
-pub const DARKORANGE2 : (u8,u8,u8) = (238, 118, 0);
-pub const DARKORANGE3 : (u8,u8,u8) = (205, 102, 0);
-pub const DARKORANGE4 : (u8,u8,u8) = (139, 69, 0);
-pub const CORAL1 : (u8,u8,u8) = (255, 114, 86);
-pub const CORAL2 : (u8,u8,u8) = (238, 106, 80);
-pub const CORAL3 : (u8,u8,u8) = (205, 91, 69);
-pub const CORAL4 : (u8,u8,u8) = (139, 62, 47);
-pub const TOMATO1 : (u8,u8,u8) = (255, 99, 71);
-pub const TOMATO2 : (u8,u8,u8) = (238, 92, 66);
-pub const TOMATO3 : (u8,u8,u8) = (205, 79, 57);
-pub const TOMATO4 : (u8,u8,u8) = (139, 54, 38);
-pub const ORANGERED1 : (u8,u8,u8) = (255, 69, 0);
-pub const ORANGERED2 : (u8,u8,u8) = (238, 64, 0);
-pub const ORANGERED3 : (u8,u8,u8) = (205, 55, 0);
-pub const ORANGERED4 : (u8,u8,u8) = (139, 37, 0);
-pub const RED1 : (u8,u8,u8) = (255, 0, 0);
-pub const RED2 : (u8,u8,u8) = (238, 0, 0);
-pub const RED3 : (u8,u8,u8) = (205, 0, 0);
-pub const RED4 : (u8,u8,u8) = (139, 0, 0);
-pub const DEEPPINK1 : (u8,u8,u8) = (255, 20, 147);
-pub const DEEPPINK2 : (u8,u8,u8) = (238, 18, 137);
-pub const DEEPPINK3 : (u8,u8,u8) = (205, 16, 118);
-pub const DEEPPINK4 : (u8,u8,u8) = (139, 10, 80);
-pub const HOTPINK1 : (u8,u8,u8) = (255, 110, 180);
-pub const HOTPINK2 : (u8,u8,u8) = (238, 106, 167);
-pub const HOTPINK3 : (u8,u8,u8) = (205, 96, 144);
-pub const HOTPINK4 : (u8,u8,u8) = (139, 58, 98);
-pub const PINK1 : (u8,u8,u8) = (255, 181, 197);
-pub const PINK2 : (u8,u8,u8) = (238, 169, 184);
-pub const PINK3 : (u8,u8,u8) = (205, 145, 158);
-pub const PINK4 : (u8,u8,u8) = (139, 99, 108);
-pub const LIGHTPINK1 : (u8,u8,u8) = (255, 174, 185);
-pub const LIGHTPINK2 : (u8,u8,u8) = (238, 162, 173);
-pub const LIGHTPINK3 : (u8,u8,u8) = (205, 140, 149);
-pub const LIGHTPINK4 : (u8,u8,u8) = (139, 95, 101);
-pub const PALEVIOLETRED1 : (u8,u8,u8) = (255, 130, 171);
-pub const PALEVIOLETRED2 : (u8,u8,u8) = (238, 121, 159);
-pub const PALEVIOLETRED3 : (u8,u8,u8) = (205, 104, 137);
-pub const PALEVIOLETRED4 : (u8,u8,u8) = (139, 71, 93);
-pub const MAROON1 : (u8,u8,u8) = (255, 52, 179);
-pub const MAROON2 : (u8,u8,u8) = (238, 48, 167);
-pub const MAROON3 : (u8,u8,u8) = (205, 41, 144);
-pub const MAROON4 : (u8,u8,u8) = (139, 28, 98);
-pub const VIOLETRED1 : (u8,u8,u8) = (255, 62, 150);
-pub const VIOLETRED2 : (u8,u8,u8) = (238, 58, 140);
-pub const VIOLETRED3 : (u8,u8,u8) = (205, 50, 120);
-pub const VIOLETRED4 : (u8,u8,u8) = (139, 34, 82);
-pub const MAGENTA1 : (u8,u8,u8) = (255, 0, 255);
-pub const MAGENTA2 : (u8,u8,u8) = (238, 0, 238);
-pub const MAGENTA3 : (u8,u8,u8) = (205, 0, 205);
-pub const MAGENTA4 : (u8,u8,u8) = (139, 0, 139);
-pub const ORCHID1 : (u8,u8,u8) = (255, 131, 250);
-pub const ORCHID2 : (u8,u8,u8) = (238, 122, 233);
-pub const ORCHID3 : (u8,u8,u8) = (205, 105, 201);
-pub const ORCHID4 : (u8,u8,u8) = (139, 71, 137);
-pub const PLUM1 : (u8,u8,u8) = (255, 187, 255);
-pub const PLUM2 : (u8,u8,u8) = (238, 174, 238);
-pub const PLUM3 : (u8,u8,u8) = (205, 150, 205);
-pub const PLUM4 : (u8,u8,u8) = (139, 102, 139);
-pub const MEDIUMORCHID1 : (u8,u8,u8) = (224, 102, 255);
-pub const MEDIUMORCHID2 : (u8,u8,u8) = (209, 95, 238);
-pub const MEDIUMORCHID3 : (u8,u8,u8) = (180, 82, 205);
-pub const MEDIUMORCHID4 : (u8,u8,u8) = (122, 55, 139);
-pub const DARKORCHID1 : (u8,u8,u8) = (191, 62, 255);
-pub const DARKORCHID2 : (u8,u8,u8) = (178, 58, 238);
-pub const DARKORCHID3 : (u8,u8,u8) = (154, 50, 205);
-pub const DARKORCHID4 : (u8,u8,u8) = (104, 34, 139);
-pub const PURPLE1 : (u8,u8,u8) = (155, 48, 255);
-pub const PURPLE2 : (u8,u8,u8) = (145, 44, 238);
-pub const PURPLE3 : (u8,u8,u8) = (125, 38, 205);
-pub const PURPLE4 : (u8,u8,u8) = (85, 26, 139);
-pub const MEDIUMPURPLE1 : (u8,u8,u8) = (171, 130, 255);
-pub const MEDIUMPURPLE2 : (u8,u8,u8) = (159, 121, 238);
-pub const MEDIUMPURPLE3 : (u8,u8,u8) = (137, 104, 205);
-pub const MEDIUMPURPLE4 : (u8,u8,u8) = (93, 71, 139);
-pub const THISTLE1 : (u8,u8,u8) = (255, 225, 255);
-pub const THISTLE2 : (u8,u8,u8) = (238, 210, 238);
-pub const THISTLE3 : (u8,u8,u8) = (205, 181, 205);
-pub const THISTLE4 : (u8,u8,u8) = (139, 123, 139);
-pub const GRAY0 : (u8,u8,u8) = (0, 0, 0);
-pub const GREY0 : (u8,u8,u8) = (0, 0, 0);
-pub const GRAY1 : (u8,u8,u8) = (3, 3, 3);
-pub const GREY1 : (u8,u8,u8) = (3, 3, 3);
-pub const GRAY2 : (u8,u8,u8) = (5, 5, 5);
-pub const GREY2 : (u8,u8,u8) = (5, 5, 5);
-pub const GRAY3 : (u8,u8,u8) = (8, 8, 8);
-pub const GREY3 : (u8,u8,u8) = (8, 8, 8);
-pub const GRAY4 : (u8,u8,u8) = (10, 10, 10);
-pub const GREY4 : (u8,u8,u8) = (10, 10, 10);
-pub const GRAY5 : (u8,u8,u8) = (13, 13, 13);
-pub const GREY5 : (u8,u8,u8) = (13, 13, 13);
-pub const GRAY6 : (u8,u8,u8) = (15, 15, 15);
-pub const GREY6 : (u8,u8,u8) = (15, 15, 15);
-pub const GRAY7 : (u8,u8,u8) = (18, 18, 18);
-pub const GREY7 : (u8,u8,u8) = (18, 18, 18);
-pub const GRAY8 : (u8,u8,u8) = (20, 20, 20);
-pub const GREY8 : (u8,u8,u8) = (20, 20, 20);
-pub const GRAY9 : (u8,u8,u8) = (23, 23, 23);
-pub const GREY9 : (u8,u8,u8) = (23, 23, 23);
-pub const GRAY10 : (u8,u8,u8) = (26, 26, 26);
-pub const GREY10 : (u8,u8,u8) = (26, 26, 26);
-pub const GRAY11 : (u8,u8,u8) = (28, 28, 28);
-pub const GREY11 : (u8,u8,u8) = (28, 28, 28);
-pub const GRAY12 : (u8,u8,u8) = (31, 31, 31);
-pub const GREY12 : (u8,u8,u8) = (31, 31, 31);
-pub const GRAY13 : (u8,u8,u8) = (33, 33, 33);
-pub const GREY13 : (u8,u8,u8) = (33, 33, 33);
-pub const GRAY14 : (u8,u8,u8) = (36, 36, 36);
-pub const GREY14 : (u8,u8,u8) = (36, 36, 36);
-pub const GRAY15 : (u8,u8,u8) = (38, 38, 38);
-pub const GREY15 : (u8,u8,u8) = (38, 38, 38);
-pub const GRAY16 : (u8,u8,u8) = (41, 41, 41);
-pub const GREY16 : (u8,u8,u8) = (41, 41, 41);
-pub const GRAY17 : (u8,u8,u8) = (43, 43, 43);
-pub const GREY17 : (u8,u8,u8) = (43, 43, 43);
-pub const GRAY18 : (u8,u8,u8) = (46, 46, 46);
-pub const GREY18 : (u8,u8,u8) = (46, 46, 46);
-pub const GRAY19 : (u8,u8,u8) = (48, 48, 48);
-pub const GREY19 : (u8,u8,u8) = (48, 48, 48);
-pub const GRAY20 : (u8,u8,u8) = (51, 51, 51);
-pub const GREY20 : (u8,u8,u8) = (51, 51, 51);
-pub const GRAY21 : (u8,u8,u8) = (54, 54, 54);
-pub const GREY21 : (u8,u8,u8) = (54, 54, 54);
-pub const GRAY22 : (u8,u8,u8) = (56, 56, 56);
-pub const GREY22 : (u8,u8,u8) = (56, 56, 56);
-pub const GRAY23 : (u8,u8,u8) = (59, 59, 59);
-pub const GREY23 : (u8,u8,u8) = (59, 59, 59);
-pub const GRAY24 : (u8,u8,u8) = (61, 61, 61);
-pub const GREY24 : (u8,u8,u8) = (61, 61, 61);
-pub const GRAY25 : (u8,u8,u8) = (64, 64, 64);
-pub const GREY25 : (u8,u8,u8) = (64, 64, 64);
-pub const GRAY26 : (u8,u8,u8) = (66, 66, 66);
-pub const GREY26 : (u8,u8,u8) = (66, 66, 66);
-pub const GRAY27 : (u8,u8,u8) = (69, 69, 69);
-pub const GREY27 : (u8,u8,u8) = (69, 69, 69);
-pub const GRAY28 : (u8,u8,u8) = (71, 71, 71);
-pub const GREY28 : (u8,u8,u8) = (71, 71, 71);
-pub const GRAY29 : (u8,u8,u8) = (74, 74, 74);
-pub const GREY29 : (u8,u8,u8) = (74, 74, 74);
-pub const GRAY30 : (u8,u8,u8) = (77, 77, 77);
-pub const GREY30 : (u8,u8,u8) = (77, 77, 77);
-pub const GRAY31 : (u8,u8,u8) = (79, 79, 79);
-pub const GREY31 : (u8,u8,u8) = (79, 79, 79);
-pub const GRAY32 : (u8,u8,u8) = (82, 82, 82);
-pub const GREY32 : (u8,u8,u8) = (82, 82, 82);
-pub const GRAY33 : (u8,u8,u8) = (84, 84, 84);
-pub const GREY33 : (u8,u8,u8) = (84, 84, 84);
-pub const GRAY34 : (u8,u8,u8) = (87, 87, 87);
-pub const GREY34 : (u8,u8,u8) = (87, 87, 87);
-pub const GRAY35 : (u8,u8,u8) = (89, 89, 89);
-pub const GREY35 : (u8,u8,u8) = (89, 89, 89);
-pub const GRAY36 : (u8,u8,u8) = (92, 92, 92);
-pub const GREY36 : (u8,u8,u8) = (92, 92, 92);
-pub const GRAY37 : (u8,u8,u8) = (94, 94, 94);
-pub const GREY37 : (u8,u8,u8) = (94, 94, 94);
-pub const GRAY38 : (u8,u8,u8) = (97, 97, 97);
-pub const GREY38 : (u8,u8,u8) = (97, 97, 97);
-pub const GRAY39 : (u8,u8,u8) = (99, 99, 99);
-pub const GREY39 : (u8,u8,u8) = (99, 99, 99);
-pub const GRAY40 : (u8,u8,u8) = (102, 102, 102);
-pub const GREY40 : (u8,u8,u8) = (102, 102, 102);
-pub const GRAY41 : (u8,u8,u8) = (105, 105, 105);
-pub const GREY41 : (u8,u8,u8) = (105, 105, 105);
-pub const GRAY42 : (u8,u8,u8) = (107, 107, 107);
-pub const GREY42 : (u8,u8,u8) = (107, 107, 107);
-pub const GRAY43 : (u8,u8,u8) = (110, 110, 110);
-pub const GREY43 : (u8,u8,u8) = (110, 110, 110);
-pub const GRAY44 : (u8,u8,u8) = (112, 112, 112);
-pub const GREY44 : (u8,u8,u8) = (112, 112, 112);
-pub const GRAY45 : (u8,u8,u8) = (115, 115, 115);
-pub const GREY45 : (u8,u8,u8) = (115, 115, 115);
-pub const GRAY46 : (u8,u8,u8) = (117, 117, 117);
-pub const GREY46 : (u8,u8,u8) = (117, 117, 117);
-pub const GRAY47 : (u8,u8,u8) = (120, 120, 120);
-pub const GREY47 : (u8,u8,u8) = (120, 120, 120);
-pub const GRAY48 : (u8,u8,u8) = (122, 122, 122);
-pub const GREY48 : (u8,u8,u8) = (122, 122, 122);
-pub const GRAY49 : (u8,u8,u8) = (125, 125, 125);
-pub const GREY49 : (u8,u8,u8) = (125, 125, 125);
-pub const GRAY50 : (u8,u8,u8) = (127, 127, 127);
-pub const GREY50 : (u8,u8,u8) = (127, 127, 127);
-pub const GRAY51 : (u8,u8,u8) = (130, 130, 130);
-pub const GREY51 : (u8,u8,u8) = (130, 130, 130);
-pub const GRAY52 : (u8,u8,u8) = (133, 133, 133);
-pub const GREY52 : (u8,u8,u8) = (133, 133, 133);
-pub const GRAY53 : (u8,u8,u8) = (135, 135, 135);
-pub const GREY53 : (u8,u8,u8) = (135, 135, 135);
-pub const GRAY54 : (u8,u8,u8) = (138, 138, 138);
-pub const GREY54 : (u8,u8,u8) = (138, 138, 138);
-pub const GRAY55 : (u8,u8,u8) = (140, 140, 140);
-pub const GREY55 : (u8,u8,u8) = (140, 140, 140);
-pub const GRAY56 : (u8,u8,u8) = (143, 143, 143);
-pub const GREY56 : (u8,u8,u8) = (143, 143, 143);
-pub const GRAY57 : (u8,u8,u8) = (145, 145, 145);
-pub const GREY57 : (u8,u8,u8) = (145, 145, 145);
-pub const GRAY58 : (u8,u8,u8) = (148, 148, 148);
-pub const GREY58 : (u8,u8,u8) = (148, 148, 148);
-pub const GRAY59 : (u8,u8,u8) = (150, 150, 150);
-pub const GREY59 : (u8,u8,u8) = (150, 150, 150);
-pub const GRAY60 : (u8,u8,u8) = (153, 153, 153);
-pub const GREY60 : (u8,u8,u8) = (153, 153, 153);
-pub const GRAY61 : (u8,u8,u8) = (156, 156, 156);
-pub const GREY61 : (u8,u8,u8) = (156, 156, 156);
-pub const GRAY62 : (u8,u8,u8) = (158, 158, 158);
-pub const GREY62 : (u8,u8,u8) = (158, 158, 158);
-pub const GRAY63 : (u8,u8,u8) = (161, 161, 161);
-pub const GREY63 : (u8,u8,u8) = (161, 161, 161);
-pub const GRAY64 : (u8,u8,u8) = (163, 163, 163);
-pub const GREY64 : (u8,u8,u8) = (163, 163, 163);
-pub const GRAY65 : (u8,u8,u8) = (166, 166, 166);
-pub const GREY65 : (u8,u8,u8) = (166, 166, 166);
-pub const GRAY66 : (u8,u8,u8) = (168, 168, 168);
-pub const GREY66 : (u8,u8,u8) = (168, 168, 168);
-pub const GRAY67 : (u8,u8,u8) = (171, 171, 171);
-pub const GREY67 : (u8,u8,u8) = (171, 171, 171);
-pub const GRAY68 : (u8,u8,u8) = (173, 173, 173);
-pub const GREY68 : (u8,u8,u8) = (173, 173, 173);
-pub const GRAY69 : (u8,u8,u8) = (176, 176, 176);
-pub const GREY69 : (u8,u8,u8) = (176, 176, 176);
-pub const GRAY70 : (u8,u8,u8) = (179, 179, 179);
-pub const GREY70 : (u8,u8,u8) = (179, 179, 179);
-pub const GRAY71 : (u8,u8,u8) = (181, 181, 181);
-pub const GREY71 : (u8,u8,u8) = (181, 181, 181);
-pub const GRAY72 : (u8,u8,u8) = (184, 184, 184);
-pub const GREY72 : (u8,u8,u8) = (184, 184, 184);
-pub const GRAY73 : (u8,u8,u8) = (186, 186, 186);
-pub const GREY73 : (u8,u8,u8) = (186, 186, 186);
-pub const GRAY74 : (u8,u8,u8) = (189, 189, 189);
-pub const GREY74 : (u8,u8,u8) = (189, 189, 189);
-pub const GRAY75 : (u8,u8,u8) = (191, 191, 191);
-pub const GREY75 : (u8,u8,u8) = (191, 191, 191);
-pub const GRAY76 : (u8,u8,u8) = (194, 194, 194);
-pub const GREY76 : (u8,u8,u8) = (194, 194, 194);
-pub const GRAY77 : (u8,u8,u8) = (196, 196, 196);
-pub const GREY77 : (u8,u8,u8) = (196, 196, 196);
-pub const GRAY78 : (u8,u8,u8) = (199, 199, 199);
-pub const GREY78 : (u8,u8,u8) = (199, 199, 199);
-pub const GRAY79 : (u8,u8,u8) = (201, 201, 201);
-pub const GREY79 : (u8,u8,u8) = (201, 201, 201);
-pub const GRAY80 : (u8,u8,u8) = (204, 204, 204);
-pub const GREY80 : (u8,u8,u8) = (204, 204, 204);
-pub const GRAY81 : (u8,u8,u8) = (207, 207, 207);
-pub const GREY81 : (u8,u8,u8) = (207, 207, 207);
-pub const GRAY82 : (u8,u8,u8) = (209, 209, 209);
-pub const GREY82 : (u8,u8,u8) = (209, 209, 209);
-pub const GRAY83 : (u8,u8,u8) = (212, 212, 212);
-pub const GREY83 : (u8,u8,u8) = (212, 212, 212);
-pub const GRAY84 : (u8,u8,u8) = (214, 214, 214);
-pub const GREY84 : (u8,u8,u8) = (214, 214, 214);
-pub const GRAY85 : (u8,u8,u8) = (217, 217, 217);
-pub const GREY85 : (u8,u8,u8) = (217, 217, 217);
-pub const GRAY86 : (u8,u8,u8) = (219, 219, 219);
-pub const GREY86 : (u8,u8,u8) = (219, 219, 219);
-pub const GRAY87 : (u8,u8,u8) = (222, 222, 222);
-pub const GREY87 : (u8,u8,u8) = (222, 222, 222);
-pub const GRAY88 : (u8,u8,u8) = (224, 224, 224);
-pub const GREY88 : (u8,u8,u8) = (224, 224, 224);
-pub const GRAY89 : (u8,u8,u8) = (227, 227, 227);
-pub const GREY89 : (u8,u8,u8) = (227, 227, 227);
-pub const GRAY90 : (u8,u8,u8) = (229, 229, 229);
-pub const GREY90 : (u8,u8,u8) = (229, 229, 229);
-pub const GRAY91 : (u8,u8,u8) = (232, 232, 232);
-pub const GREY91 : (u8,u8,u8) = (232, 232, 232);
-pub const GRAY92 : (u8,u8,u8) = (235, 235, 235);
-pub const GREY92 : (u8,u8,u8) = (235, 235, 235);
-pub const GRAY93 : (u8,u8,u8) = (237, 237, 237);
-pub const GREY93 : (u8,u8,u8) = (237, 237, 237);
-pub const GRAY94 : (u8,u8,u8) = (240, 240, 240);
-pub const GREY94 : (u8,u8,u8) = (240, 240, 240);
-pub const GRAY95 : (u8,u8,u8) = (242, 242, 242);
-pub const GREY95 : (u8,u8,u8) = (242, 242, 242);
-pub const GRAY96 : (u8,u8,u8) = (245, 245, 245);
-pub const GREY96 : (u8,u8,u8) = (245, 245, 245);
-pub const GRAY97 : (u8,u8,u8) = (247, 247, 247);
-pub const GREY97 : (u8,u8,u8) = (247, 247, 247);
-pub const GRAY98 : (u8,u8,u8) = (250, 250, 250);
-pub const GREY98 : (u8,u8,u8) = (250, 250, 250);
-pub const GRAY99 : (u8,u8,u8) = (252, 252, 252);
-pub const GREY99 : (u8,u8,u8) = (252, 252, 252);
-pub const GRAY100 : (u8,u8,u8) = (255, 255, 255);
-pub const GREY100 : (u8,u8,u8) = (255, 255, 255);
-pub const DARK_GREY : (u8,u8,u8) = (169, 169, 169);
-pub const DARKGREY : (u8,u8,u8) = (169, 169, 169);
-pub const DARK_GRAY : (u8,u8,u8) = (169, 169, 169);
-pub const DARKGRAY : (u8,u8,u8) = (169, 169, 169);
-pub const DARK_BLUE : (u8,u8,u8) = (0, 0, 139);
-pub const DARKBLUE : (u8,u8,u8) = (0, 0, 139);
-pub const DARK_CYAN : (u8,u8,u8) = (0, 139, 139);
-pub const DARKCYAN : (u8,u8,u8) = (0, 139, 139);
-pub const DARK_MAGENTA : (u8,u8,u8) = (139, 0, 139);
-pub const DARKMAGENTA : (u8,u8,u8) = (139, 0, 139);
-pub const DARK_RED : (u8,u8,u8) = (139, 0, 0);
-pub const DARKRED : (u8,u8,u8) = (139, 0, 0);
-pub const LIGHT_GREEN : (u8,u8,u8) = (144, 238, 144);
-pub const LIGHTGREEN : (u8,u8,u8) = (144, 238, 144);
-pub const CRIMSON : (u8,u8,u8) = (220, 20, 60);
-pub const INDIGO : (u8,u8,u8) = (75, 0, 130);
-pub const OLIVE : (u8,u8,u8) = (128, 128, 0);
-pub const REBECCA_PURPLE : (u8,u8,u8) = (102, 51, 153);
-pub const REBECCAPURPLE : (u8,u8,u8) = (102, 51, 153);
-pub const SILVER : (u8,u8,u8) = (192, 192, 192);
-pub const TEAL : (u8,u8,u8) = (0, 128, 128);
+named_colors! {
+    SNOW = (255, 250, 250),
+    GHOST_WHITE = (248, 248, 255),
+    GHOSTWHITE = (248, 248, 255),
+    WHITE_SMOKE = (245, 245, 245),
+    WHITESMOKE = (245, 245, 245),
+    GAINSBORO = (220, 220, 220),
+    FLORAL_WHITE = (255, 250, 240),
+    FLORALWHITE = (255, 250, 240),
+    OLD_LACE = (253, 245, 230),
+    OLDLACE = (253, 245, 230),
+    LINEN = (250, 240, 230),
+    ANTIQUE_WHITE = (250, 235, 215),
+    ANTIQUEWHITE = (250, 235, 215),
+    PAPAYA_WHIP = (255, 239, 213),
+    PAPAYAWHIP = (255, 239, 213),
+    BLANCHED_ALMOND = (255, 235, 205),
+    BLANCHEDALMOND = (255, 235, 205),
+    BISQUE = (255, 228, 196),
+    PEACH_PUFF = (255, 218, 185),
+    PEACHPUFF = (255, 218, 185),
+    NAVAJO_WHITE = (255, 222, 173),
+    NAVAJOWHITE = (255, 222, 173),
+    MOCCASIN = (255, 228, 181),
+    CORNSILK = (255, 248, 220),
+    IVORY = (255, 255, 240),
+    LEMON_CHIFFON = (255, 250, 205),
+    LEMONCHIFFON = (255, 250, 205),
+    SEASHELL = (255, 245, 238),
+    HONEYDEW = (240, 255, 240),
+    MINT_CREAM = (245, 255, 250),
+    MINTCREAM = (245, 255, 250),
+    AZURE = (240, 255, 255),
+    ALICE_BLUE = (240, 248, 255),
+    ALICEBLUE = (240, 248, 255),
+    LAVENDER = (230, 230, 250),
+    LAVENDER_BLUSH = (255, 240, 245),
+    LAVENDERBLUSH = (255, 240, 245),
+    MISTY_ROSE = (255, 228, 225),
+    MISTYROSE = (255, 228, 225),
+    WHITE = (255, 255, 255),
+    BLACK = (0, 0, 0),
+    DARK_SLATE = (47, 79, 79),
+    DARKSLATEGRAY = (47, 79, 79),
+    DARKSLATEGREY = (47, 79, 79),
+    DIM_GRAY = (105, 105, 105),
+    DIMGRAY = (105, 105, 105),
+    DIM_GREY = (105, 105, 105),
+    DIMGREY = (105, 105, 105),
+    SLATE_GRAY = (112, 128, 144),
+    SLATEGRAY = (112, 128, 144),
+    SLATE_GREY = (112, 128, 144),
+    SLATEGREY = (112, 128, 144),
+    LIGHT_SLATE = (119, 136, 153),
+    LIGHTSLATEGRAY = (119, 136, 153),
+    LIGHTSLATEGREY = (119, 136, 153),
+    GRAY = (190, 190, 190),
+    GREY = (190, 190, 190),
+    X11_GRAY = (190, 190, 190),
+    X11GRAY = (190, 190, 190),
+    X11_GREY = (190, 190, 190),
+    X11GREY = (190, 190, 190),
+    WEB_GRAY = (128, 128, 128),
+    WEBGRAY = (128, 128, 128),
+    WEB_GREY = (128, 128, 128),
+    WEBGREY = (128, 128, 128),
+    LIGHT_GREY = (211, 211, 211),
+    LIGHTGREY = (211, 211, 211),
+    LIGHT_GRAY = (211, 211, 211),
+    LIGHTGRAY = (211, 211, 211),
+    MIDNIGHT_BLUE = (25, 25, 112),
+    MIDNIGHTBLUE = (25, 25, 112),
+    NAVY = (0, 0, 128),
+    NAVY_BLUE = (0, 0, 128),
+    NAVYBLUE = (0, 0, 128),
+    CORNFLOWER_BLUE = (100, 149, 237),
+    CORNFLOWERBLUE = (100, 149, 237),
+    DARKSLATEBLUE = (72, 61, 139),
+    SLATE_BLUE = (106, 90, 205),
+    SLATEBLUE = (106, 90, 205),
+    MEDIUM_SLATE = (123, 104, 238),
+    MEDIUMSLATEBLUE = (123, 104, 238),
+    LIGHTSLATEBLUE = (132, 112, 255),
+    MEDIUM_BLUE = (0, 0, 205),
+    MEDIUMBLUE = (0, 0, 205),
+    ROYAL_BLUE = (65, 105, 225),
+    ROYALBLUE = (65, 105, 225),
+    BLUE = (0, 0, 255),
+    DODGER_BLUE = (30, 144, 255),
+    DODGERBLUE = (30, 144, 255),
+    DEEP_SKY = (0, 191, 255),
+    DEEPSKYBLUE = (0, 191, 255),
+    SKY_BLUE = (135, 206, 235),
+    SKYBLUE = (135, 206, 235),
+    LIGHT_SKY = (135, 206, 250),
+    LIGHTSKYBLUE = (135, 206, 250),
+    STEEL_BLUE = (70, 130, 180),
+    STEELBLUE = (70, 130, 180),
+    LIGHT_STEEL = (176, 196, 222),
+    LIGHTSTEELBLUE = (176, 196, 222),
+    LIGHT_BLUE = (173, 216, 230),
+    LIGHTBLUE = (173, 216, 230),
+    POWDER_BLUE = (176, 224, 230),
+    POWDERBLUE = (176, 224, 230),
+    PALE_TURQUOISE = (175, 238, 238),
+    PALETURQUOISE = (175, 238, 238),
+    DARK_TURQUOISE = (0, 206, 209),
+    DARKTURQUOISE = (0, 206, 209),
+    MEDIUM_TURQUOISE = (72, 209, 204),
+    MEDIUMTURQUOISE = (72, 209, 204),
+    TURQUOISE = (64, 224, 208),
+    CYAN = (0, 255, 255),
+    AQUA = (0, 255, 255),
+    LIGHT_CYAN = (224, 255, 255),
+    LIGHTCYAN = (224, 255, 255),
+    CADET_BLUE = (95, 158, 160),
+    CADETBLUE = (95, 158, 160),
+    MEDIUM_AQUAMARINE = (102, 205, 170),
+    MEDIUMAQUAMARINE = (102, 205, 170),
+    AQUAMARINE = (127, 255, 212),
+    DARK_GREEN = (0, 100, 0),
+    DARKGREEN = (0, 100, 0),
+    DARK_OLIVE = (85, 107, 47),
+    DARKOLIVEGREEN = (85, 107, 47),
+    DARK_SEA = (143, 188, 143),
+    DARKSEAGREEN = (143, 188, 143),
+    SEA_GREEN = (46, 139, 87),
+    SEAGREEN = (46, 139, 87),
+    MEDIUM_SEA = (60, 179, 113),
+    MEDIUMSEAGREEN = (60, 179, 113),
+    LIGHT_SEA = (32, 178, 170),
+    LIGHTSEAGREEN = (32, 178, 170),
+    PALE_GREEN = (152, 251, 152),
+    PALEGREEN = (152, 251, 152),
+    SPRING_GREEN = (0, 255, 127),
+    SPRINGGREEN = (0, 255, 127),
+    LAWN_GREEN = (124, 252, 0),
+    LAWNGREEN = (124, 252, 0),
+    GREEN = (0, 255, 0),
+    LIME = (0, 255, 0),
+    X11_GREEN = (0, 255, 0),
+    X11GREEN = (0, 255, 0),
+    WEB_GREEN = (0, 128, 0),
+    WEBGREEN = (0, 128, 0),
+    CHARTREUSE = (127, 255, 0),
+    MEDIUM_SPRING = (0, 250, 154),
+    MEDIUMSPRINGGREEN = (0, 250, 154),
+    GREEN_YELLOW = (173, 255, 47),
+    GREENYELLOW = (173, 255, 47),
+    LIME_GREEN = (50, 205, 50),
+    LIMEGREEN = (50, 205, 50),
+    YELLOW_GREEN = (154, 205, 50),
+    YELLOWGREEN = (154, 205, 50),
+    FOREST_GREEN = (34, 139, 34),
+    FORESTGREEN = (34, 139, 34),
+    OLIVE_DRAB = (107, 142, 35),
+    OLIVEDRAB = (107, 142, 35),
+    DARK_KHAKI = (189, 183, 107),
+    DARKKHAKI = (189, 183, 107),
+    KHAKI = (240, 230, 140),
+    PALE_GOLDENROD = (238, 232, 170),
+    PALEGOLDENROD = (238, 232, 170),
+    LIGHT_GOLDENROD = (250, 250, 210),
+    LIGHTGOLDENRODYELLOW = (250, 250, 210),
+    LIGHT_YELLOW = (255, 255, 224),
+    LIGHTYELLOW = (255, 255, 224),
+    YELLOW = (255, 255, 0),
+    GOLD = (255, 215, 0),
+    LIGHTGOLDENROD = (238, 221, 130),
+    GOLDENROD = (218, 165, 32),
+    DARK_GOLDENROD = (184, 134, 11),
+    DARKGOLDENROD = (184, 134, 11),
+    ROSY_BROWN = (188, 143, 143),
+    ROSYBROWN = (188, 143, 143),
+    INDIAN_RED = (205, 92, 92),
+    INDIANRED = (205, 92, 92),
+    SADDLE_BROWN = (139, 69, 19),
+    SADDLEBROWN = (139, 69, 19),
+    SIENNA = (160, 82, 45),
+    PERU = (205, 133, 63),
+    BURLYWOOD = (222, 184, 135),
+    BEIGE = (245, 245, 220),
+    WHEAT = (245, 222, 179),
+    SANDY_BROWN = (244, 164, 96),
+    SANDYBROWN = (244, 164, 96),
+    TAN = (210, 180, 140),
+    CHOCOLATE = (210, 105, 30),
+    FIREBRICK_34 = (178, 34, 34),
+    BROWN_42 = (165, 42, 42),
+    DARK_SALMON = (233, 150, 122),
+    DARKSALMON = (233, 150, 122),
+    SALMON = (250, 128, 114),
+    LIGHT_SALMON = (255, 160, 122),
+    LIGHTSALMON = (255, 160, 122),
+    ORANGE = (255, 165, 0),
+    DARK_ORANGE = (255, 140, 0),
+    DARKORANGE = (255, 140, 0),
+    CORAL = (255, 127, 80),
+    LIGHT_CORAL = (240, 128, 128),
+    LIGHTCORAL = (240, 128, 128),
+    TOMATO = (255, 99, 71),
+    ORANGE_RED = (255, 69, 0),
+    ORANGERED = (255, 69, 0),
+    RED = (255, 0, 0),
+    HOT_PINK = (255, 105, 180),
+    HOTPINK = (255, 105, 180),
+    DEEP_PINK = (255, 20, 147),
+    DEEPPINK = (255, 20, 147),
+    PINK = (255, 192, 203),
+    LIGHT_PINK = (255, 182, 193),
+    LIGHTPINK = (255, 182, 193),
+    PALE_VIOLET = (219, 112, 147),
+    PALEVIOLETRED = (219, 112, 147),
+    MAROON = (176, 48, 96),
+    X11_MAROON = (176, 48, 96),
+    X11MAROON = (176, 48, 96),
+    WEB_MAROON = (128, 0, 0),
+    WEBMAROON = (128, 0, 0),
+    MEDIUM_VIOLET = (199, 21, 133),
+    MEDIUMVIOLETRED = (199, 21, 133),
+    VIOLET_RED = (208, 32, 144),
+    VIOLETRED = (208, 32, 144),
+    MAGENTA = (255, 0, 255),
+    FUCHSIA = (255, 0, 255),
+    VIOLET = (238, 130, 238),
+    PLUM = (221, 160, 221),
+    ORCHID = (218, 112, 214),
+    MEDIUM_ORCHID = (186, 85, 211),
+    MEDIUMORCHID = (186, 85, 211),
+    DARK_ORCHID = (153, 50, 204),
+    DARKORCHID = (153, 50, 204),
+    DARK_VIOLET = (148, 0, 211),
+    DARKVIOLET = (148, 0, 211),
+    BLUE_VIOLET = (138, 43, 226),
+    BLUEVIOLET = (138, 43, 226),
+    PURPLE = (160, 32, 240),
+    X11_PURPLE = (160, 32, 240),
+    X11PURPLE = (160, 32, 240),
+    WEB_PURPLE = (128, 0, 128),
+    WEBPURPLE = (128, 0, 128),
+    MEDIUM_PURPLE = (147, 112, 219),
+    MEDIUMPURPLE = (147, 112, 219),
+    THISTLE = (216, 191, 216),
+    SNOW1 = (255, 250, 250),
+    SNOW2 = (238, 233, 233),
+    SNOW3 = (205, 201, 201),
+    SNOW4 = (139, 137, 137),
+    SEASHELL1 = (255, 245, 238),
+    SEASHELL2 = (238, 229, 222),
+    SEASHELL3 = (205, 197, 191),
+    SEASHELL4 = (139, 134, 130),
+    ANTIQUEWHITE1 = (255, 239, 219),
+    ANTIQUEWHITE2 = (238, 223, 204),
+    ANTIQUEWHITE3 = (205, 192, 176),
+    ANTIQUEWHITE4 = (139, 131, 120),
+    BISQUE1 = (255, 228, 196),
+    BISQUE2 = (238, 213, 183),
+    BISQUE3 = (205, 183, 158),
+    BISQUE4 = (139, 125, 107),
+    PEACHPUFF1 = (255, 218, 185),
+    PEACHPUFF2 = (238, 203, 173),
+    PEACHPUFF3 = (205, 175, 149),
+    PEACHPUFF4 = (139, 119, 101),
+    NAVAJOWHITE1 = (255, 222, 173),
+    NAVAJOWHITE2 = (238, 207, 161),
+    NAVAJOWHITE3 = (205, 179, 139),
+    NAVAJOWHITE4 = (139, 121, 94),
+    LEMONCHIFFON1 = (255, 250, 205),
+    LEMONCHIFFON2 = (238, 233, 191),
+    LEMONCHIFFON3 = (205, 201, 165),
+    LEMONCHIFFON4 = (139, 137, 112),
+    CORNSILK1 = (255, 248, 220),
+    CORNSILK2 = (238, 232, 205),
+    CORNSILK3 = (205, 200, 177),
+    CORNSILK4 = (139, 136, 120),
+    IVORY1 = (255, 255, 240),
+    IVORY2 = (238, 238, 224),
+    IVORY3 = (205, 205, 193),
+    IVORY4 = (139, 139, 131),
+    HONEYDEW1 = (240, 255, 240),
+    HONEYDEW2 = (224, 238, 224),
+    HONEYDEW3 = (193, 205, 193),
+    HONEYDEW4 = (131, 139, 131),
+    LAVENDERBLUSH1 = (255, 240, 245),
+    LAVENDERBLUSH2 = (238, 224, 229),
+    LAVENDERBLUSH3 = (205, 193, 197),
+    LAVENDERBLUSH4 = (139, 131, 134),
+    MISTYROSE1 = (255, 228, 225),
+    MISTYROSE2 = (238, 213, 210),
+    MISTYROSE3 = (205, 183, 181),
+    MISTYROSE4 = (139, 125, 123),
+    AZURE1 = (240, 255, 255),
+    AZURE2 = (224, 238, 238),
+    AZURE3 = (193, 205, 205),
+    AZURE4 = (131, 139, 139),
+    SLATEBLUE1 = (131, 111, 255),
+    SLATEBLUE2 = (122, 103, 238),
+    SLATEBLUE3 = (105, 89, 205),
+    SLATEBLUE4 = (71, 60, 139),
+    ROYALBLUE1 = (72, 118, 255),
+    ROYALBLUE2 = (67, 110, 238),
+    ROYALBLUE3 = (58, 95, 205),
+    ROYALBLUE4 = (39, 64, 139),
+    BLUE1 = (0, 0, 255),
+    BLUE2 = (0, 0, 238),
+    BLUE3 = (0, 0, 205),
+    BLUE4 = (0, 0, 139),
+    DODGERBLUE1 = (30, 144, 255),
+    DODGERBLUE2 = (28, 134, 238),
+    DODGERBLUE3 = (24, 116, 205),
+    DODGERBLUE4 = (16, 78, 139),
+    STEELBLUE1 = (99, 184, 255),
+    STEELBLUE2 = (92, 172, 238),
+    STEELBLUE3 = (79, 148, 205),
+    STEELBLUE4 = (54, 100, 139),
+    DEEPSKYBLUE1 = (0, 191, 255),
+    DEEPSKYBLUE2 = (0, 178, 238),
+    DEEPSKYBLUE3 = (0, 154, 205),
+    DEEPSKYBLUE4 = (0, 104, 139),
+    SKYBLUE1 = (135, 206, 255),
+    SKYBLUE2 = (126, 192, 238),
+    SKYBLUE3 = (108, 166, 205),
+    SKYBLUE4 = (74, 112, 139),
+    LIGHTSKYBLUE1 = (176, 226, 255),
+    LIGHTSKYBLUE2 = (164, 211, 238),
+    LIGHTSKYBLUE3 = (141, 182, 205),
+    LIGHTSKYBLUE4 = (96, 123, 139),
+    SLATEGRAY1 = (198, 226, 255),
+    SLATEGRAY2 = (185, 211, 238),
+    SLATEGRAY3 = (159, 182, 205),
+    SLATEGRAY4 = (108, 123, 139),
+    LIGHTSTEELBLUE1 = (202, 225, 255),
+    LIGHTSTEELBLUE2 = (188, 210, 238),
+    LIGHTSTEELBLUE3 = (162, 181, 205),
+    LIGHTSTEELBLUE4 = (110, 123, 139),
+    LIGHTBLUE1 = (191, 239, 255),
+    LIGHTBLUE2 = (178, 223, 238),
+    LIGHTBLUE3 = (154, 192, 205),
+    LIGHTBLUE4 = (104, 131, 139),
+    LIGHTCYAN1 = (224, 255, 255),
+    LIGHTCYAN2 = (209, 238, 238),
+    LIGHTCYAN3 = (180, 205, 205),
+    LIGHTCYAN4 = (122, 139, 139),
+    PALETURQUOISE1 = (187, 255, 255),
+    PALETURQUOISE2 = (174, 238, 238),
+    PALETURQUOISE3 = (150, 205, 205),
+    PALETURQUOISE4 = (102, 139, 139),
+    CADETBLUE1 = (152, 245, 255),
+    CADETBLUE2 = (142, 229, 238),
+    CADETBLUE3 = (122, 197, 205),
+    CADETBLUE4 = (83, 134, 139),
+    TURQUOISE1 = (0, 245, 255),
+    TURQUOISE2 = (0, 229, 238),
+    TURQUOISE3 = (0, 197, 205),
+    TURQUOISE4 = (0, 134, 139),
+    CYAN1 = (0, 255, 255),
+    CYAN2 = (0, 238, 238),
+    CYAN3 = (0, 205, 205),
+    CYAN4 = (0, 139, 139),
+    DARKSLATEGRAY1 = (151, 255, 255),
+    DARKSLATEGRAY2 = (141, 238, 238),
+    DARKSLATEGRAY3 = (121, 205, 205),
+    DARKSLATEGRAY4 = (82, 139, 139),
+    AQUAMARINE1 = (127, 255, 212),
+    AQUAMARINE2 = (118, 238, 198),
+    AQUAMARINE3 = (102, 205, 170),
+    AQUAMARINE4 = (69, 139, 116),
+    DARKSEAGREEN1 = (193, 255, 193),
+    DARKSEAGREEN2 = (180, 238, 180),
+    DARKSEAGREEN3 = (155, 205, 155),
+    DARKSEAGREEN4 = (105, 139, 105),
+    SEAGREEN1 = (84, 255, 159),
+    SEAGREEN2 = (78, 238, 148),
+    SEAGREEN3 = (67, 205, 128),
+    SEAGREEN4 = (46, 139, 87),
+    PALEGREEN1 = (154, 255, 154),
+    PALEGREEN2 = (144, 238, 144),
+    PALEGREEN3 = (124, 205, 124),
+    PALEGREEN4 = (84, 139, 84),
+    SPRINGGREEN1 = (0, 255, 127),
+    SPRINGGREEN2 = (0, 238, 118),
+    SPRINGGREEN3 = (0, 205, 102),
+    SPRINGGREEN4 = (0, 139, 69),
+    GREEN1 = (0, 255, 0),
+    GREEN2 = (0, 238, 0),
+    GREEN3 = (0, 205, 0),
+    GREEN4 = (0, 139, 0),
+    CHARTREUSE1 = (127, 255, 0),
+    CHARTREUSE2 = (118, 238, 0),
+    CHARTREUSE3 = (102, 205, 0),
+    CHARTREUSE4 = (69, 139, 0),
+    OLIVEDRAB1 = (192, 255, 62),
+    OLIVEDRAB2 = (179, 238, 58),
+    OLIVEDRAB3 = (154, 205, 50),
+    OLIVEDRAB4 = (105, 139, 34),
+    DARKOLIVEGREEN1 = (202, 255, 112),
+    DARKOLIVEGREEN2 = (188, 238, 104),
+    DARKOLIVEGREEN3 = (162, 205, 90),
+    DARKOLIVEGREEN4 = (110, 139, 61),
+    KHAKI1 = (255, 246, 143),
+    KHAKI2 = (238, 230, 133),
+    KHAKI3 = (205, 198, 115),
+    KHAKI4 = (139, 134, 78),
+    LIGHTGOLDENROD1 = (255, 236, 139),
+    LIGHTGOLDENROD2 = (238, 220, 130),
+    LIGHTGOLDENROD3 = (205, 190, 112),
+    LIGHTGOLDENROD4 = (139, 129, 76),
+    LIGHTYELLOW1 = (255, 255, 224),
+    LIGHTYELLOW2 = (238, 238, 209),
+    LIGHTYELLOW3 = (205, 205, 180),
+    LIGHTYELLOW4 = (139, 139, 122),
+    YELLOW1 = (255, 255, 0),
+    YELLOW2 = (238, 238, 0),
+    YELLOW3 = (205, 205, 0),
+    YELLOW4 = (139, 139, 0),
+    GOLD1 = (255, 215, 0),
+    GOLD2 = (238, 201, 0),
+    GOLD3 = (205, 173, 0),
+    GOLD4 = (139, 117, 0),
+    GOLDENROD1 = (255, 193, 37),
+    GOLDENROD2 = (238, 180, 34),
+    GOLDENROD3 = (205, 155, 29),
+    GOLDENROD4 = (139, 105, 20),
+    DARKGOLDENROD1 = (255, 185, 15),
+    DARKGOLDENROD2 = (238, 173, 14),
+    DARKGOLDENROD3 = (205, 149, 12),
+    DARKGOLDENROD4 = (139, 101, 8),
+    ROSYBROWN1 = (255, 193, 193),
+    ROSYBROWN2 = (238, 180, 180),
+    ROSYBROWN3 = (205, 155, 155),
+    ROSYBROWN4 = (139, 105, 105),
+    INDIANRED1 = (255, 106, 106),
+    INDIANRED2 = (238, 99, 99),
+    INDIANRED3 = (205, 85, 85),
+    INDIANRED4 = (139, 58, 58),
+    SIENNA1 = (255, 130, 71),
+    SIENNA2 = (238, 121, 66),
+    SIENNA3 = (205, 104, 57),
+    SIENNA4 = (139, 71, 38),
+    BURLYWOOD1 = (255, 211, 155),
+    BURLYWOOD2 = (238, 197, 145),
+    BURLYWOOD3 = (205, 170, 125),
+    BURLYWOOD4 = (139, 115, 85),
+    WHEAT1 = (255, 231, 186),
+    WHEAT2 = (238, 216, 174),
+    WHEAT3 = (205, 186, 150),
+    WHEAT4 = (139, 126, 102),
+    TAN1 = (255, 165, 79),
+    TAN2 = (238, 154, 73),
+    TAN3 = (205, 133, 63),
+    TAN4 = (139, 90, 43),
+    CHOCOLATE1 = (255, 127, 36),
+    CHOCOLATE2 = (238, 118, 33),
+    CHOCOLATE3 = (205, 102, 29),
+    CHOCOLATE4 = (139, 69, 19),
+    FIREBRICK1 = (255, 48, 48),
+    FIREBRICK2 = (238, 44, 44),
+    FIREBRICK3 = (205, 38, 38),
+    FIREBRICK4 = (139, 26, 26),
+    BROWN1 = (255, 64, 64),
+    BROWN2 = (238, 59, 59),
+    BROWN3 = (205, 51, 51),
+    BROWN4 = (139, 35, 35),
+    SALMON1 = (255, 140, 105),
+    SALMON2 = (238, 130, 98),
+    SALMON3 = (205, 112, 84),
+    SALMON4 = (139, 76, 57),
+    LIGHTSALMON1 = (255, 160, 122),
+    LIGHTSALMON2 = (238, 149, 114),
+    LIGHTSALMON3 = (205, 129, 98),
+    LIGHTSALMON4 = (139, 87, 66),
+    ORANGE1 = (255, 165, 0),
+    ORANGE2 = (238, 154, 0),
+    ORANGE3 = (205, 133, 0),
+    ORANGE4 = (139, 90, 0),
+    DARKORANGE1 = (255, 127, 0),
+    DARKORANGE2 = (238, 118, 0),
+    DARKORANGE3 = (205, 102, 0),
+    DARKORANGE4 = (139, 69, 0),
+    CORAL1 = (255, 114, 86),
+    CORAL2 = (238, 106, 80),
+    CORAL3 = (205, 91, 69),
+    CORAL4 = (139, 62, 47),
+    TOMATO1 = (255, 99, 71),
+    TOMATO2 = (238, 92, 66),
+    TOMATO3 = (205, 79, 57),
+    TOMATO4 = (139, 54, 38),
+    ORANGERED1 = (255, 69, 0),
+    ORANGERED2 = (238, 64, 0),
+    ORANGERED3 = (205, 55, 0),
+    ORANGERED4 = (139, 37, 0),
+    RED1 = (255, 0, 0),
+    RED2 = (238, 0, 0),
+    RED3 = (205, 0, 0),
+    RED4 = (139, 0, 0),
+    DEEPPINK1 = (255, 20, 147),
+    DEEPPINK2 = (238, 18, 137),
+    DEEPPINK3 = (205, 16, 118),
+    DEEPPINK4 = (139, 10, 80),
+    HOTPINK1 = (255, 110, 180),
+    HOTPINK2 = (238, 106, 167),
+    HOTPINK3 = (205, 96, 144),
+    HOTPINK4 = (139, 58, 98),
+    PINK1 = (255, 181, 197),
+    PINK2 = (238, 169, 184),
+    PINK3 = (205, 145, 158),
+    PINK4 = (139, 99, 108),
+    LIGHTPINK1 = (255, 174, 185),
+    LIGHTPINK2 = (238, 162, 173),
+    LIGHTPINK3 = (205, 140, 149),
+    LIGHTPINK4 = (139, 95, 101),
+    PALEVIOLETRED1 = (255, 130, 171),
+    PALEVIOLETRED2 = (238, 121, 159),
+    PALEVIOLETRED3 = (205, 104, 137),
+    PALEVIOLETRED4 = (139, 71, 93),
+    MAROON1 = (255, 52, 179),
+    MAROON2 = (238, 48, 167),
+    MAROON3 = (205, 41, 144),
+    MAROON4 = (139, 28, 98),
+    VIOLETRED1 = (255, 62, 150),
+    VIOLETRED2 = (238, 58, 140),
+    VIOLETRED3 = (205, 50, 120),
+    VIOLETRED4 = (139, 34, 82),
+    MAGENTA1 = (255, 0, 255),
+    MAGENTA2 = (238, 0, 238),
+    MAGENTA3 = (205, 0, 205),
+    MAGENTA4 = (139, 0, 139),
+    ORCHID1 = (255, 131, 250),
+    ORCHID2 = (238, 122, 233),
+    ORCHID3 = (205, 105, 201),
+    ORCHID4 = (139, 71, 137),
+    PLUM1 = (255, 187, 255),
+    PLUM2 = (238, 174, 238),
+    PLUM3 = (205, 150, 205),
+    PLUM4 = (139, 102, 139),
+    MEDIUMORCHID1 = (224, 102, 255),
+    MEDIUMORCHID2 = (209, 95, 238),
+    MEDIUMORCHID3 = (180, 82, 205),
+    MEDIUMORCHID4 = (122, 55, 139),
+    DARKORCHID1 = (191, 62, 255),
+    DARKORCHID2 = (178, 58, 238),
+    DARKORCHID3 = (154, 50, 205),
+    DARKORCHID4 = (104, 34, 139),
+    PURPLE1 = (155, 48, 255),
+    PURPLE2 = (145, 44, 238),
+    PURPLE3 = (125, 38, 205),
+    PURPLE4 = (85, 26, 139),
+    MEDIUMPURPLE1 = (171, 130, 255),
+    MEDIUMPURPLE2 = (159, 121, 238),
+    MEDIUMPURPLE3 = (137, 104, 205),
+    MEDIUMPURPLE4 = (93, 71, 139),
+    THISTLE1 = (255, 225, 255),
+    THISTLE2 = (238, 210, 238),
+    THISTLE3 = (205, 181, 205),
+    THISTLE4 = (139, 123, 139),
+    GRAY0 = (0, 0, 0),
+    GREY0 = (0, 0, 0),
+    GRAY1 = (3, 3, 3),
+    GREY1 = (3, 3, 3),
+    GRAY2 = (5, 5, 5),
+    GREY2 = (5, 5, 5),
+    GRAY3 = (8, 8, 8),
+    GREY3 = (8, 8, 8),
+    GRAY4 = (10, 10, 10),
+    GREY4 = (10, 10, 10),
+    GRAY5 = (13, 13, 13),
+    GREY5 = (13, 13, 13),
+    GRAY6 = (15, 15, 15),
+    GREY6 = (15, 15, 15),
+    GRAY7 = (18, 18, 18),
+    GREY7 = (18, 18, 18),
+    GRAY8 = (20, 20, 20),
+    GREY8 = (20, 20, 20),
+    GRAY9 = (23, 23, 23),
+    GREY9 = (23, 23, 23),
+    GRAY10 = (26, 26, 26),
+    GREY10 = (26, 26, 26),
+    GRAY11 = (28, 28, 28),
+    GREY11 = (28, 28, 28),
+    GRAY12 = (31, 31, 31),
+    GREY12 = (31, 31, 31),
+    GRAY13 = (33, 33, 33),
+    GREY13 = (33, 33, 33),
+    GRAY14 = (36, 36, 36),
+    GREY14 = (36, 36, 36),
+    GRAY15 = (38, 38, 38),
+    GREY15 = (38, 38, 38),
+    GRAY16 = (41, 41, 41),
+    GREY16 = (41, 41, 41),
+    GRAY17 = (43, 43, 43),
+    GREY17 = (43, 43, 43),
+    GRAY18 = (46, 46, 46),
+    GREY18 = (46, 46, 46),
+    GRAY19 = (48, 48, 48),
+    GREY19 = (48, 48, 48),
+    GRAY20 = (51, 51, 51),
+    GREY20 = (51, 51, 51),
+    GRAY21 = (54, 54, 54),
+    GREY21 = (54, 54, 54),
+    GRAY22 = (56, 56, 56),
+    GREY22 = (56, 56, 56),
+    GRAY23 = (59, 59, 59),
+    GREY23 = (59, 59, 59),
+    GRAY24 = (61, 61, 61),
+    GREY24 = (61, 61, 61),
+    GRAY25 = (64, 64, 64),
+    GREY25 = (64, 64, 64),
+    GRAY26 = (66, 66, 66),
+    GREY26 = (66, 66, 66),
+    GRAY27 = (69, 69, 69),
+    GREY27 = (69, 69, 69),
+    GRAY28 = (71, 71, 71),
+    GREY28 = (71, 71, 71),
+    GRAY29 = (74, 74, 74),
+    GREY29 = (74, 74, 74),
+    GRAY30 = (77, 77, 77),
+    GREY30 = (77, 77, 77),
+    GRAY31 = (79, 79, 79),
+    GREY31 = (79, 79, 79),
+    GRAY32 = (82, 82, 82),
+    GREY32 = (82, 82, 82),
+    GRAY33 = (84, 84, 84),
+    GREY33 = (84, 84, 84),
+    GRAY34 = (87, 87, 87),
+    GREY34 = (87, 87, 87),
+    GRAY35 = (89, 89, 89),
+    GREY35 = (89, 89, 89),
+    GRAY36 = (92, 92, 92),
+    GREY36 = (92, 92, 92),
+    GRAY37 = (94, 94, 94),
+    GREY37 = (94, 94, 94),
+    GRAY38 = (97, 97, 97),
+    GREY38 = (97, 97, 97),
+    GRAY39 = (99, 99, 99),
+    GREY39 = (99, 99, 99),
+    GRAY40 = (102, 102, 102),
+    GREY40 = (102, 102, 102),
+    GRAY41 = (105, 105, 105),
+    GREY41 = (105, 105, 105),
+    GRAY42 = (107, 107, 107),
+    GREY42 = (107, 107, 107),
+    GRAY43 = (110, 110, 110),
+    GREY43 = (110, 110, 110),
+    GRAY44 = (112, 112, 112),
+    GREY44 = (112, 112, 112),
+    GRAY45 = (115, 115, 115),
+    GREY45 = (115, 115, 115),
+    GRAY46 = (117, 117, 117),
+    GREY46 = (117, 117, 117),
+    GRAY47 = (120, 120, 120),
+    GREY47 = (120, 120, 120),
+    GRAY48 = (122, 122, 122),
+    GREY48 = (122, 122, 122),
+    GRAY49 = (125, 125, 125),
+    GREY49 = (125, 125, 125),
+    GRAY50 = (127, 127, 127),
+    GREY50 = (127, 127, 127),
+    GRAY51 = (130, 130, 130),
+    GREY51 = (130, 130, 130),
+    GRAY52 = (133, 133, 133),
+    GREY52 = (133, 133, 133),
+    GRAY53 = (135, 135, 135),
+    GREY53 = (135, 135, 135),
+    GRAY54 = (138, 138, 138),
+    GREY54 = (138, 138, 138),
+    GRAY55 = (140, 140, 140),
+    GREY55 = (140, 140, 140),
+    GRAY56 = (143, 143, 143),
+    GREY56 = (143, 143, 143),
+    GRAY57 = (145, 145, 145),
+    GREY57 = (145, 145, 145),
+    GRAY58 = (148, 148, 148),
+    GREY58 = (148, 148, 148),
+    GRAY59 = (150, 150, 150),
+    GREY59 = (150, 150, 150),
+    GRAY60 = (153, 153, 153),
+    GREY60 = (153, 153, 153),
+    GRAY61 = (156, 156, 156),
+    GREY61 = (156, 156, 156),
+    GRAY62 = (158, 158, 158),
+    GREY62 = (158, 158, 158),
+    GRAY63 = (161, 161, 161),
+    GREY63 = (161, 161, 161),
+    GRAY64 = (163, 163, 163),
+    GREY64 = (163, 163, 163),
+    GRAY65 = (166, 166, 166),
+    GREY65 = (166, 166, 166),
+    GRAY66 = (168, 168, 168),
+    GREY66 = (168, 168, 168),
+    GRAY67 = (171, 171, 171),
+    GREY67 = (171, 171, 171),
+    GRAY68 = (173, 173, 173),
+    GREY68 = (173, 173, 173),
+    GRAY69 = (176, 176, 176),
+    GREY69 = (176, 176, 176),
+    GRAY70 = (179, 179, 179),
+    GREY70 = (179, 179, 179),
+    GRAY71 = (181, 181, 181),
+    GREY71 = (181, 181, 181),
+    GRAY72 = (184, 184, 184),
+    GREY72 = (184, 184, 184),
+    GRAY73 = (186, 186, 186),
+    GREY73 = (186, 186, 186),
+    GRAY74 = (189, 189, 189),
+    GREY74 = (189, 189, 189),
+    GRAY75 = (191, 191, 191),
+    GREY75 = (191, 191, 191),
+    GRAY76 = (194, 194, 194),
+    GREY76 = (194, 194, 194),
+    GRAY77 = (196, 196, 196),
+    GREY77 = (196, 196, 196),
+    GRAY78 = (199, 199, 199),
+    GREY78 = (199, 199, 199),
+    GRAY79 = (201, 201, 201),
+    GREY79 = (201, 201, 201),
+    GRAY80 = (204, 204, 204),
+    GREY80 = (204, 204, 204),
+    GRAY81 = (207, 207, 207),
+    GREY81 = (207, 207, 207),
+    GRAY82 = (209, 209, 209),
+    GREY82 = (209, 209, 209),
+    GRAY83 = (212, 212, 212),
+    GREY83 = (212, 212, 212),
+    GRAY84 = (214, 214, 214),
+    GREY84 = (214, 214, 214),
+    GRAY85 = (217, 217, 217),
+    GREY85 = (217, 217, 217),
+    GRAY86 = (219, 219, 219),
+    GREY86 = (219, 219, 219),
+    GRAY87 = (222, 222, 222),
+    GREY87 = (222, 222, 222),
+    GRAY88 = (224, 224, 224),
+    GREY88 = (224, 224, 224),
+    GRAY89 = (227, 227, 227),
+    GREY89 = (227, 227, 227),
+    GRAY90 = (229, 229, 229),
+    GREY90 = (229, 229, 229),
+    GRAY91 = (232, 232, 232),
+    GREY91 = (232, 232, 232),
+    GRAY92 = (235, 235, 235),
+    GREY92 = (235, 235, 235),
+    GRAY93 = (237, 237, 237),
+    GREY93 = (237, 237, 237),
+    GRAY94 = (240, 240, 240),
+    GREY94 = (240, 240, 240),
+    GRAY95 = (242, 242, 242),
+    GREY95 = (242, 242, 242),
+    GRAY96 = (245, 245, 245),
+    GREY96 = (245, 245, 245),
+    GRAY97 = (247, 247, 247),
+    GREY97 = (247, 247, 247),
+    GRAY98 = (250, 250, 250),
+    GREY98 = (250, 250, 250),
+    GRAY99 = (252, 252, 252),
+    GREY99 = (252, 252, 252),
+    GRAY100 = (255, 255, 255),
+    GREY100 = (255, 255, 255),
+    DARK_GREY = (169, 169, 169),
+    DARKGREY = (169, 169, 169),
+    DARK_GRAY = (169, 169, 169),
+    DARKGRAY = (169, 169, 169),
+    DARK_BLUE = (0, 0, 139),
+    DARKBLUE = (0, 0, 139),
+    DARK_CYAN = (0, 139, 139),
+    DARKCYAN = (0, 139, 139),
+    DARK_MAGENTA = (139, 0, 139),
+    DARKMAGENTA = (139, 0, 139),
+    DARK_RED = (139, 0, 0),
+    DARKRED = (139, 0, 0),
+    LIGHT_GREEN = (144, 238, 144),
+    LIGHTGREEN = (144, 238, 144),
+    CRIMSON = (220, 20, 60),
+    INDIGO = (75, 0, 130),
+    OLIVE = (128, 128, 0),
+    REBECCA_PURPLE = (102, 51, 153),
+    REBECCAPURPLE = (102, 51, 153),
+    SILVER = (192, 192, 192),
+    TEAL = (0, 128, 128),
+}
+
+
+/// Finds a named color, ignoring case, so "Dark Red", "dark-red" and "DARK_RED" all find
+/// `DARK_RED`. An exact match comes first, since a few names differ only by underscores
+/// (`LIGHT_GOLDENROD` and `LIGHTGOLDENROD` are different colors); otherwise underscores are
+/// ignored, so "dark_olive_green" finds `DARKOLIVEGREEN`.
+pub(crate) fn color_by_name(name : &str) -> Option<(u8,u8,u8)> {
+    let key : String = name.chars().map(|c| if c == ' ' || c == '-' { '_' } else { c }).collect();
+    let squashed = |text : &str| text.chars().filter(|c| *c != '_').flat_map(|c| c.to_lowercase()).collect::<String>();
+    let squashed_key = squashed(&key);
+    NAMED_COLORS.iter().find(|(n, _)| n.eq_ignore_ascii_case(&key))
+        .or_else(|| NAMED_COLORS.iter().find(|(n, _)| n.chars().filter(|c| *c != '_').map(|c| c.to_ascii_lowercase()).eq(squashed_key.chars())))
+        .map(|(_, col)| *col)
+}
+
+/// Iterates over every named color constant, with the name of the constant (`"DARK_RED"` for
+/// `DARK_RED`). Many colors appear twice, with and without underscores (`GHOST_WHITE` and
+/// `GHOSTWHITE`).
+pub fn named_colors() -> impl Iterator<Item = (&'static str, RGB)> {
+    NAMED_COLORS.iter().map(|(name, col)| (*name, RGB::named(*col)))
+}
 
 // Unit tests for the color system
 
@@ -1117,4 +1321,62 @@ mod tests {
         assert_eq!(rgb.g, 0.0);
         assert_eq!(rgb.b, 1.0);
     }
+
+    #[test]
+    // Tests looking colors up by name, with any spacing or case.
+    fn test_from_name() {
+        assert!(RGB::from_name("dark_olive_green") == Some(RGB::named(super::DARKOLIVEGREEN)));
+        assert!(RGB::from_name("Ghost White") == Some(RGB::named(super::GHOST_WHITE)));
+        assert!(RGB::from_name("not-a-color").is_none());
+        assert_eq!(RGB::named(super::ORANGE).to_hex(), "#ffa500");
+        assert_eq!(super::named_colors().count(), 777);
+        assert!(super::named_colors().any(|(name, col)| name == "TEAL" && col == RGB::named(super::TEAL)));
+        assert!(RGB::from_name("lightgoldenrod") == Some(RGB::named(super::LIGHTGOLDENROD)));
+        assert!(RGB::from_name("Light Goldenrod") == Some(RGB::named(super::LIGHT_GOLDENROD)));
+    }
+
+    #[test]
+    // Every name finds its own color, even where names differ only by underscores.
+    fn test_names_are_unambiguous() {
+        for (name, col) in super::NAMED_COLORS.iter() {
+            assert_eq!(super::color_by_name(name), Some(*col), "{}", name);
+        }
+    }
+
+    fn close(a : RGB, b : RGB) -> bool {
+        (a.r - b.r).abs() < 0.002 && (a.g - b.g).abs() < 0.002 && (a.b - b.b).abs() < 0.002
+    }
+
+    #[test]
+    // Tests that colors survive a round trip through HSL, Lab and OKLab.
+    fn test_color_space_round_trips() {
+        for col in [super::ORANGE, super::DARKOLIVEGREEN, super::ROYAL_BLUE, super::WHITE, super::BLACK].iter() {
+            let rgb = RGB::named(*col);
+            assert!(close(rgb.to_hsl().to_rgb(), rgb));
+            assert!(close(rgb.to_lab().to_rgb(), rgb));
+            assert!(close(rgb.to_oklab().to_rgb(), rgb));
+        }
+    }
+
+    #[test]
+    // Tests known values: white is L=100 in Lab and L=1 in OKLab, and pure red's HSL.
+    fn test_color_space_values() {
+        let white = RGB::from_f32(1.0, 1.0, 1.0);
+        assert!((white.to_lab().l - 100.0).abs() < 0.01);
+        assert!(white.to_lab().a.abs() < 0.01);
+        assert!((white.to_oklab().l - 1.0).abs() < 0.001);
+        let red = RGB::from_f32(1.0, 0.0, 0.0).to_hsl();
+        assert_eq!((red.h, red.s, red.l), (0.0, 1.0, 0.5));
+        assert!((white.perceptual_distance(RGB::new()) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    // Tests that perceptual blends keep their ends, and that HSV blends take the short way round.
+    fn test_perceptual_lerp() {
+        let (red, blue) = (RGB::from_f32(1.0, 0.0, 0.0), RGB::from_f32(0.0, 0.0, 1.0));
+        assert!(close(red.lerp_oklab(blue, 0.0), red));
+        assert!(close(red.lerp_oklab(blue, 1.0), blue));
+        let magenta = red.lerp_hsv(blue, 0.5);
+        assert!(close(magenta, RGB::from_f32(1.0, 0.0, 1.0)));
+    }
 }
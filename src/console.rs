@@ -1,4 +1,4 @@
-use super::{RGB, Font, Shader, rex::XpLayer};
+use super::{RGB, Font, Shader, rex::XpLayer, gui_helpers};
 use super::gl;
 
 /// The internal storage type for tiles in a simple console.
@@ -9,6 +9,14 @@ pub struct Tile {
     pub bg: RGB
 }
 
+/// A cell that changed between two frames, in console coordinates.
+#[derive(PartialEq, Copy, Clone)]
+pub struct TileChange {
+    pub x: i32,
+    pub y: i32,
+    pub tile: Tile
+}
+
 /// Trait that must be implemented by console types.
 pub trait Console {
     /// Check to see if the internal OpenGL representation needs to be rebuilt, and do so if required.
@@ -20,6 +28,19 @@ pub trait Console {
     /// Converts an x/y coordinate to a console index number.
     fn at(&self, x:i32, y:i32) -> usize;
 
+    /// Returns the console width, in characters. Consoles that don't report their size return
+    /// 0, which makes size-based helpers such as `fill_region` draw nothing.
+    fn width(&self) -> u32 { 0 }
+
+    /// Returns the console height, in characters, or 0 if the console doesn't report it.
+    fn height(&self) -> u32 { 0 }
+
+    /// Reads back the cell at x/y. Returns None if the coordinate is off the console, or
+    /// (for consoles that don't store every cell, and by default) if nothing has been drawn there.
+    fn get(&self, _x:i32, _y:i32) -> Option<Tile> {
+        None
+    }
+
     /// Clear the console.
     fn cls(&mut self);
 
@@ -38,6 +59,21 @@ pub trait Console {
     /// Sets a single cell's background color.
     fn set_bg(&mut self, x:i32, y:i32, bg:RGB);
 
+    /// Fills a rectangle, starting at x/y with the extents width/height, with a glyph and colors.
+    /// The rectangle is clipped to the console. By default, this calls `set` for each cell.
+    #[allow(clippy::too_many_arguments)]
+    fn fill_region(&mut self, x:i32, y:i32, width:i32, height:i32, fg:RGB, bg:RGB, glyph:u8) {
+        gui_helpers::fill_region(self, x, y, width, height, fg, bg, glyph);
+    }
+
+    /// Moves the console's contents by dx/dy cells (positive values move right and down). Cells
+    /// moved off the console are lost, and the cells left behind are cleared. By default, this
+    /// reads each cell with `get` and writes it back with `set`.
+    fn scroll(&mut self, dx:i32, dy:i32) {
+        let (width, height) = (self.width() as i32, self.height() as i32);
+        gui_helpers::scroll_region(self, 0, 0, width, height, dx, dy);
+    }
+
     /// Draws a box, starting at x/y with the extents width/height using CP437 line characters
     fn draw_box(&mut self, x:i32, y:i32, width:i32, height:i32, fg: RGB, bg: RGB);
 
@@ -62,4 +98,14 @@ pub trait Console {
     /// Specify a global offset (by character count, so 0.5 is half a character). Useful for
     /// drawing walls between tiles.
     fn set_offset(&mut self, x : f32, y : f32);
+
+    /// Called once per frame, after the game's tick. Consoles that keep a copy of the previous
+    /// frame use this to work out which cells changed; by default it does nothing.
+    fn end_frame(&mut self) {}
+
+    /// Returns the cells that changed during the last completed frame, or None if the console
+    /// doesn't track changes (the default).
+    fn frame_diff(&self) -> Option<&[TileChange]> {
+        None
+    }
 }
\ No newline at end of file
@@ -0,0 +1,266 @@
+use super::{Console, Tile, TileChange, RGB, Font, Shader, Point, Rect, rex::XpLayer, gui_helpers, string_to_cp437};
+use super::gl;
+
+/// One entry on the draw target's stack: where local 0,0 is on the console, how big the
+/// local area is, and the (console-space) region that may be drawn to.
+#[derive(Copy, Clone)]
+struct Viewport {
+    origin: Point,
+    width: i32,
+    height: i32,
+    clip: Rect
+}
+
+/// Wraps a console, restricting drawing to a stack of clipping rectangles. Pushing a region
+/// moves the origin to its top-left corner, so a window can draw its contents in local
+/// coordinates without spilling over its border. Anything outside the current clip is
+/// silently discarded.
+///
+/// A draw target is itself a `Console`, so any helper that draws to a console can draw to it.
+pub struct DrawTarget<'a> {
+    console: &'a mut dyn Console,
+    stack: Vec<Viewport>
+}
+
+impl<'a> DrawTarget<'a> {
+    /// Wraps a console. Initially the whole console is drawable, with the origin at 0,0.
+    pub fn new(console : &'a mut dyn Console) -> DrawTarget<'a> {
+        let width = console.width() as i32;
+        let height = console.height() as i32;
+        DrawTarget{
+            console,
+            stack: vec![Viewport{ origin: Point::new(0, 0), width, height, clip: Rect::with_size(0, 0, width, height) }]
+        }
+    }
+
+    fn top(&self) -> Viewport {
+        self.stack[self.stack.len() - 1]
+    }
+
+    /// Pushes a region, specified in the current local coordinates. Drawing is clipped to it
+    /// (and to every region below it on the stack), and it becomes the new origin.
+    pub fn push(&mut self, area : Rect) {
+        let top = self.top();
+        let absolute = area.translate(top.origin.x, top.origin.y);
+        self.stack.push(Viewport{
+            origin: Point::new(absolute.x1, absolute.y1),
+            width: area.width(),
+            height: area.height(),
+            clip: absolute.clip_to(&top.clip)
+        });
+    }
+
+    /// Pushes a clipping region, specified in the current local coordinates, without moving
+    /// the origin.
+    pub fn push_clip(&mut self, area : Rect) {
+        let top = self.top();
+        let absolute = area.translate(top.origin.x, top.origin.y);
+        self.stack.push(Viewport{ clip: absolute.clip_to(&top.clip), ..top });
+    }
+
+    /// Removes the most recently pushed region. The whole-console region can't be popped.
+    pub fn pop(&mut self) {
+        if self.stack.len() > 1 {
+            self.stack.pop();
+        }
+    }
+
+    /// Returns the number of regions pushed on top of the whole-console region.
+    pub fn depth(&self) -> usize {
+        self.stack.len() - 1
+    }
+
+    /// Returns the current clipping region, in local coordinates.
+    pub fn clip(&self) -> Rect {
+        let top = self.top();
+        top.clip.translate(-top.origin.x, -top.origin.y)
+    }
+
+    /// Converts a local coordinate to a coordinate on the underlying console.
+    pub fn to_console(&self, x:i32, y:i32) -> Point {
+        let top = self.top();
+        Point::new(x + top.origin.x, y + top.origin.y)
+    }
+
+    /// Converts a local coordinate to the underlying console, returning None if it is clipped.
+    fn visible(&self, x:i32, y:i32) -> Option<Point> {
+        let pt = self.to_console(x, y);
+        if self.top().clip.point_in_rect(pt) { Some(pt) } else { None }
+    }
+}
+
+impl<'a> Console for DrawTarget<'a> {
+    fn rebuild_if_dirty(&mut self, gl : &gl::Gles2) {
+        self.console.rebuild_if_dirty(gl);
+    }
+
+    fn gl_draw(&mut self, font : &Font, shader : &Shader, gl : &gl::Gles2) {
+        self.console.gl_draw(font, shader, gl);
+    }
+
+    /// Converts a local x/y coordinate to an index on the underlying console.
+    fn at(&self, x:i32, y:i32) -> usize {
+        let pt = self.to_console(x, y);
+        self.console.at(pt.x, pt.y)
+    }
+
+    /// Returns the width of the current region.
+    fn width(&self) -> u32 {
+        self.top().width as u32
+    }
+
+    /// Returns the height of the current region.
+    fn height(&self) -> u32 {
+        self.top().height as u32
+    }
+
+    /// Reads back a cell in local coordinates. Clipped cells return None.
+    fn get(&self, x:i32, y:i32) -> Option<Tile> {
+        self.visible(x, y).and_then(|pt| self.console.get(pt.x, pt.y))
+    }
+
+    /// Clears the current region.
+    fn cls(&mut self) {
+        let (w, h) = (self.width() as i32, self.height() as i32);
+        self.fill_region(0, 0, w, h, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0), 32);
+    }
+
+    /// Clears the current region to a background color.
+    fn cls_bg(&mut self, background : RGB) {
+        let (w, h) = (self.width() as i32, self.height() as i32);
+        self.fill_region(0, 0, w, h, RGB::from_f32(1.0, 1.0, 1.0), background, 32);
+    }
+
+    /// Prints a string, keeping the colors already on the console. Characters outside the clip are dropped.
+    fn print(&mut self, x:i32, y:i32, output:&str) {
+        for (i, glyph) in string_to_cp437(output).iter().enumerate() {
+            if let Some(pt) = self.visible(x + i as i32, y) {
+                let (fg, bg) = match self.console.get(pt.x, pt.y) {
+                    Some(tile) => (tile.fg, tile.bg),
+                    None => (RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0))
+                };
+                self.console.set(pt.x, pt.y, fg, bg, *glyph);
+            }
+        }
+    }
+
+    /// Prints a string in color. Characters outside the clip are dropped.
+    fn print_color(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, output:&str) {
+        for (i, glyph) in string_to_cp437(output).iter().enumerate() {
+            self.set(x + i as i32, y, fg, bg, *glyph);
+        }
+    }
+
+    /// Sets a single cell, if it is inside the clip.
+    fn set(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, glyph:u8) {
+        if let Some(pt) = self.visible(x, y) {
+            self.console.set(pt.x, pt.y, fg, bg, glyph);
+        }
+    }
+
+    /// Sets a single cell's background, if it is inside the clip.
+    fn set_bg(&mut self, x:i32, y:i32, bg:RGB) {
+        if let Some(pt) = self.visible(x, y) {
+            self.console.set_bg(pt.x, pt.y, bg);
+        }
+    }
+
+    fn fill_region(&mut self, sx:i32, sy:i32, width:i32, height:i32, fg:RGB, bg:RGB, glyph:u8) {
+        gui_helpers::fill_region(self, sx, sy, width, height, fg, bg, glyph);
+    }
+
+    /// Scrolls the contents of the current region.
+    fn scroll(&mut self, dx:i32, dy:i32) {
+        let (w, h) = (self.width() as i32, self.height() as i32);
+        gui_helpers::scroll_region(self, 0, 0, w, h, dx, dy);
+    }
+
+    fn draw_box(&mut self, sx:i32, sy:i32, width:i32, height:i32, fg: RGB, bg: RGB) {
+        gui_helpers::draw_box(self, sx, sy, width, height, fg, bg);
+    }
+
+    fn draw_box_double(&mut self, sx:i32, sy:i32, width:i32, height:i32, fg: RGB, bg: RGB) {
+        gui_helpers::draw_box_double(self, sx, sy, width, height, fg, bg);
+    }
+
+    fn draw_bar_horizontal(&mut self, sx:i32, sy:i32, width:i32, n:i32, max:i32, fg:RGB, bg: RGB) {
+        gui_helpers::draw_bar_horizontal(self, sx, sy, width, n, max, fg, bg);
+    }
+
+    fn draw_bar_vertical(&mut self, sx:i32, sy:i32, height:i32, n:i32, max:i32, fg:RGB, bg: RGB) {
+        gui_helpers::draw_bar_vertical(self, sx, sy, height, n, max, fg, bg);
+    }
+
+    /// Prints text, centered to the width of the current region.
+    fn print_centered(&mut self, y:i32, text:&str) {
+        self.print((self.width() as i32 / 2) - (text.to_string().len() as i32/2), y, text);
+    }
+
+    /// Prints text in color, centered to the width of the current region.
+    fn print_color_centered(&mut self, y:i32, fg:RGB, bg:RGB, text:&str) {
+        self.print_color((self.width() as i32 / 2) - (text.to_string().len() as i32/2), y, fg, bg, text);
+    }
+
+    /// Saves the whole underlying console.
+    fn to_xp_layer(&self) -> XpLayer {
+        self.console.to_xp_layer()
+    }
+
+    fn set_offset(&mut self, x : f32, y : f32) {
+        self.console.set_offset(x, y);
+    }
+
+    fn end_frame(&mut self) {
+        self.console.end_frame();
+    }
+
+    fn frame_diff(&self) -> Option<&[TileChange]> {
+        self.console.frame_diff()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DrawTarget;
+    use super::super::{Console, TerminalConsole, Rect, RGB};
+
+    #[test]
+    // A pushed region moves the origin, and nothing is drawn outside it.
+    fn test_push_translates_and_clips() {
+        let mut console = TerminalConsole::init(20, 10);
+        {
+            let mut target = DrawTarget::new(console.as_mut());
+            target.push(Rect::with_size(5, 2, 4, 3));
+            assert_eq!(target.width(), 4);
+            target.fill_region(-10, -10, 100, 100, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0), 35);
+            target.print(2, 1, "Hello");
+        }
+        let filled = console.tiles().iter().filter(|t| t.glyph != 32).count();
+        assert_eq!(filled, 12);
+        assert_eq!(console.get(5, 2).unwrap().glyph, 35);
+        assert_eq!(console.get(7, 3).unwrap().glyph, b'H');
+        assert_eq!(console.get(8, 3).unwrap().glyph, b'e');
+        assert_eq!(console.get(9, 3).unwrap().glyph, 32);
+    }
+
+    #[test]
+    // Nested regions are clipped by their parents, and popping restores the parent.
+    fn test_nested_regions() {
+        let mut console = TerminalConsole::init(20, 10);
+        let mut target = DrawTarget::new(console.as_mut());
+        target.push(Rect::with_size(2, 2, 5, 5));
+        target.push(Rect::with_size(3, 3, 5, 5));
+        assert_eq!(target.depth(), 2);
+        assert_eq!(target.clip(), Rect::with_size(0, 0, 2, 2));
+        target.set(0, 0, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0), 64);
+        target.set(2, 2, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0), 64);
+        target.pop();
+        assert_eq!(target.get(3, 3).unwrap().glyph, 64);
+        assert_eq!(target.get(4, 4).unwrap().glyph, 32);
+        assert!(target.get(5, 5).is_none());
+        target.pop();
+        target.pop();
+        assert_eq!(target.depth(), 0);
+        assert_eq!(target.width(), 20);
+    }
+}
@@ -0,0 +1,269 @@
+use super::{Console, Rect, Tile, RGB};
+
+/// Which colors of a cell an effect changes.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum EffectTarget { Foreground, Background, Both }
+
+/// What an effect does to the cells it covers.
+#[derive(Clone)]
+pub enum EffectKind {
+    /// Hides the glyph for the second half of every period, leaving the background.
+    Blink { period_ms : f32 },
+    /// Steps through a list of colors, showing each for `step_ms`. Good for lava and water.
+    Cycle { colors : Vec<RGB>, step_ms : f32, target : EffectTarget },
+    /// Blends from one color to another over `duration_ms`, then holds the final color. `None`
+    /// stands for whatever was drawn: `from: Some(red), to: None` is a damage flash that fades
+    /// back to normal, while `from: None, to: Some(black)` fades the cells out.
+    Fade { from : Option<RGB>, to : Option<RGB>, duration_ms : f32, target : EffectTarget },
+    /// Smoothly brightens and dims the drawn colors between `min` and `max` times their
+    /// brightness, once every period. Good for magic auras.
+    Pulse { period_ms : f32, min : f32, max : f32, target : EffectTarget }
+}
+
+/// A running effect, covering an area of one console.
+#[derive(Clone)]
+pub struct TileEffect {
+    pub kind : EffectKind,
+    pub console : usize,
+    pub area : Rect,
+    /// How long the effect lasts, or None to run until it is removed.
+    pub lifetime_ms : Option<f32>,
+    pub elapsed_ms : f32
+}
+
+/// Timed effects layered on top of console cells: blinking, color cycling, fades and pulses.
+/// RLTK keeps one set in `Rltk::effects`; each frame, after your `tick`, it advances them by
+/// `frame_time_ms` and applies them to whatever you drew. Once the frame has been shown the drawn
+/// cells are put back, so effects don't build up on cells you don't redraw.
+#[derive(Clone, Default)]
+pub struct TileEffects {
+    effects : Vec<(usize, TileEffect)>,
+    next_id : usize,
+    /// The cells as they were before `apply_to` changed them: console index, x, y and tile.
+    saved : Vec<(usize, i32, i32, Tile)>
+}
+
+impl TileEffects {
+    /// Creates an empty set of effects.
+    pub fn new() -> TileEffects {
+        TileEffects{ effects : Vec::new(), next_id : 0, saved : Vec::new() }
+    }
+
+    /// Starts an effect on an area of a console (by index, as returned by `register_console`).
+    /// Returns an id that can be passed to `remove`.
+    pub fn add(&mut self, console : usize, area : Rect, kind : EffectKind, lifetime_ms : Option<f32>) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.effects.push((id, TileEffect{ kind, console, area, lifetime_ms, elapsed_ms : 0.0 }));
+        id
+    }
+
+    /// Stops an effect.
+    pub fn remove(&mut self, id : usize) {
+        self.effects.retain(|(effect_id, _)| *effect_id != id);
+    }
+
+    /// Stops every effect.
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+
+    /// The running effect with an id, if it hasn't finished.
+    pub fn get(&self, id : usize) -> Option<&TileEffect> {
+        self.effects.iter().find(|(effect_id, _)| *effect_id == id).map(|(_, effect)| effect)
+    }
+
+    /// The number of running effects.
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    /// True if no effects are running.
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Moves every effect on by `frame_time_ms`, and drops those that have finished.
+    pub fn advance(&mut self, frame_time_ms : f32) {
+        for (_, effect) in self.effects.iter_mut() {
+            effect.elapsed_ms += frame_time_ms;
+        }
+        self.effects.retain(|(_, effect)| match effect.lifetime_ms {
+            Some(lifetime) => effect.elapsed_ms < lifetime,
+            None => true
+        });
+    }
+
+    /// Applies the effects belonging to console `index` to that console, remembering the cells
+    /// they cover so that `restore` can put them back.
+    pub fn apply_to(&mut self, index : usize, console : &mut dyn Console) {
+        for (_, effect) in self.effects.iter().filter(|(_, effect)| effect.console == index) {
+            let area = effect.clipped_area(console);
+            for y in area.y1 .. area.y2 {
+                for x in area.x1 .. area.x2 {
+                    if let Some(tile) = console.get(x, y) {
+                        self.saved.push((index, x, y, tile));
+                    }
+                }
+            }
+            effect.apply(console);
+        }
+    }
+
+    /// Puts back the cells of console `index` as they were before `apply_to` changed them.
+    pub fn restore(&mut self, index : usize, console : &mut dyn Console) {
+        // In reverse, so that where effects overlap the cell ends up as it was first drawn
+        for (_, x, y, tile) in self.saved.iter().rev().filter(|(console, ..)| *console == index) {
+            console.set(*x, *y, tile.fg, tile.bg, tile.glyph);
+        }
+        self.saved.retain(|(console, ..)| *console != index);
+    }
+}
+
+impl TileEffect {
+    /// The part of the effect's area that lies on a console.
+    fn clipped_area(&self, console : &dyn Console) -> Rect {
+        self.area.clip_to(&Rect::with_size(0, 0, console.width() as i32, console.height() as i32))
+    }
+
+    /// Changes the cells under the effect, as they should look `elapsed_ms` into it.
+    pub fn apply(&self, console : &mut dyn Console) {
+        let area = self.clipped_area(console);
+        let t = self.elapsed_ms;
+
+        for y in area.y1 .. area.y2 {
+            for x in area.x1 .. area.x2 {
+                let tile = match console.get(x, y) {
+                    Some(tile) => tile,
+                    None => continue
+                };
+                let (mut fg, mut bg, mut glyph) = (tile.fg, tile.bg, tile.glyph);
+
+                let mut recolor = |target : EffectTarget, transform : &dyn Fn(RGB) -> RGB| {
+                    if target != EffectTarget::Background { fg = transform(fg); }
+                    if target != EffectTarget::Foreground { bg = transform(bg); }
+                };
+                match &self.kind {
+                    EffectKind::Blink{ period_ms } => {
+                        if *period_ms > 0.0 && t % period_ms >= period_ms / 2.0 { glyph = 32; }
+                    }
+                    EffectKind::Cycle{ colors, step_ms, target } => {
+                        if !colors.is_empty() {
+                            let step = if *step_ms > 0.0 { (t / step_ms) as usize } else { 0 };
+                            let col = colors[step % colors.len()];
+                            recolor(*target, &|_| col);
+                        }
+                    }
+                    EffectKind::Fade{ from, to, duration_ms, target } => {
+                        let percent = if *duration_ms > 0.0 { f32::min(1.0, t / duration_ms) } else { 1.0 };
+                        recolor(*target, &|drawn| from.unwrap_or(drawn).lerp(to.unwrap_or(drawn), percent));
+                    }
+                    EffectKind::Pulse{ period_ms, min, max, target } => {
+                        let phase = if *period_ms > 0.0 { t / period_ms } else { 0.0 };
+                        let level = 0.5 - 0.5 * (phase * std::f32::consts::PI * 2.0).cos();
+                        let scale = min + (max - min) * level;
+                        recolor(*target, &|drawn| drawn * scale);
+                    }
+                }
+
+                console.set(x, y, fg, bg, glyph);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TileEffects, EffectKind, EffectTarget};
+    use super::super::{Console, VirtualConsole, Rect, RGB};
+
+    fn draw() -> VirtualConsole {
+        let mut console = VirtualConsole::new(4, 1);
+        console.print_color(0, 0, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0), "~~~~");
+        console
+    }
+
+    #[test]
+    // Effects only cover their area, and finish after their lifetime.
+    fn test_blink_and_lifetime() {
+        let mut effects = TileEffects::new();
+        effects.add(0, Rect::with_size(1, 0, 2, 1), EffectKind::Blink{ period_ms : 100.0 }, Some(200.0));
+
+        effects.advance(60.0);
+        let mut console = draw();
+        effects.apply_to(0, &mut console);
+        assert_eq!(console.get(0, 0).unwrap().glyph, b'~');
+        assert_eq!(console.get(1, 0).unwrap().glyph, 32);
+        assert_eq!(console.get(3, 0).unwrap().glyph, b'~');
+
+        effects.advance(60.0);
+        let mut console = draw();
+        effects.apply_to(0, &mut console);
+        assert_eq!(console.get(1, 0).unwrap().glyph, b'~');
+
+        effects.apply_to(1, &mut console);
+        effects.advance(100.0);
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    // A flash starts at its color and fades back to what was drawn; cycles step through colors.
+    fn test_fade_and_cycle() {
+        let red = RGB::from_f32(1.0, 0.0, 0.0);
+        let mut effects = TileEffects::new();
+        let flash = effects.add(0, Rect::with_size(0, 0, 4, 1), EffectKind::Fade{ from : Some(red), to : None, duration_ms : 100.0, target : EffectTarget::Foreground }, None);
+
+        let mut console = draw();
+        effects.apply_to(0, &mut console);
+        assert!(console.get(0, 0).unwrap().fg == red);
+        effects.advance(50.0);
+        let mut console = draw();
+        effects.apply_to(0, &mut console);
+        assert!(console.get(0, 0).unwrap().fg == RGB::from_f32(1.0, 0.5, 0.5));
+        assert!(console.get(0, 0).unwrap().bg == RGB::from_f32(0.0, 0.0, 0.0));
+        effects.remove(flash);
+
+        let colors = vec![RGB::from_f32(1.0, 0.0, 0.0), RGB::from_f32(0.0, 1.0, 0.0)];
+        effects.add(0, Rect::with_size(0, 0, 1, 1), EffectKind::Cycle{ colors : colors.clone(), step_ms : 10.0, target : EffectTarget::Background }, None);
+        effects.advance(15.0);
+        let mut console = draw();
+        effects.apply_to(0, &mut console);
+        assert!(console.get(0, 0).unwrap().bg == colors[1]);
+    }
+
+    #[test]
+    // Pulses dim to `min` at the start of each period, and reach `max` half way through.
+    fn test_pulse() {
+        let mut effects = TileEffects::new();
+        effects.add(0, Rect::with_size(0, 0, 1, 1), EffectKind::Pulse{ period_ms : 100.0, min : 0.5, max : 1.0, target : EffectTarget::Both }, None);
+        let mut console = draw();
+        effects.apply_to(0, &mut console);
+        assert!((console.get(0, 0).unwrap().fg.r - 0.5).abs() < 0.001);
+
+        effects.advance(50.0);
+        let mut console = draw();
+        effects.apply_to(0, &mut console);
+        assert!((console.get(0, 0).unwrap().fg.r - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    // Restoring after each frame stops effects building up on cells that aren't redrawn.
+    fn test_restore() {
+        let mut effects = TileEffects::new();
+        effects.add(0, Rect::with_size(0, 0, 2, 1), EffectKind::Pulse{ period_ms : 100.0, min : 0.5, max : 1.0, target : EffectTarget::Both }, None);
+        effects.add(0, Rect::with_size(1, 0, 2, 1), EffectKind::Blink{ period_ms : 100.0 }, None);
+        let mut console = draw();
+
+        effects.advance(75.0);
+        for _frame in 0 .. 2 {
+            effects.apply_to(0, &mut console);
+            assert!((console.get(0, 0).unwrap().fg.r - 0.75).abs() < 0.001);
+            assert_eq!(console.get(1, 0).unwrap().glyph, 32);
+            effects.restore(0, &mut console);
+        }
+
+        for x in 0 .. 4 {
+            assert!(console.get(x, 0).unwrap() == draw().get(x, 0).unwrap());
+        }
+    }
+}
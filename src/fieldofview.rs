@@ -1,15 +1,204 @@
-use super::Point;
-use super::geometry::{distance2d, DistanceAlg};
-use super::Algorithm2D;
+use super::{Point, Point3};
+use super::geometry::{distance2d, line3d, DistanceAlg};
+use super::{Algorithm2D, Algorithm3D};
 
 extern crate bresenham;
 use bresenham::Bresenham;
 
+/// The ways of calculating field-of-view. They differ in how they treat corners and pillars, and
+/// in whether visibility is symmetric (if A can see B, B can see A).
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum FovAlgorithm {
+    /// Bresenham rays to the edge of the range, as used by `field_of_view`. Fast, but asymmetric,
+    /// with gaps behind pillars.
+    Rays,
+    /// Recursive shadowcasting: a tile is visible if any part of it is lit. Generous around
+    /// corners, and asymmetric.
+    Shadowcasting,
+    /// Symmetric shadowcasting: floor tiles are visible if their center is lit, so if you can see
+    /// a monster it can see you. Walls are visible if any part is lit.
+    SymmetricShadowcasting,
+    /// Precise permissive: a tile is visible if any unblocked line joins any part of the start
+    /// tile to any part of it. Symmetric, and the most generous.
+    Permissive,
+    /// Shadowcasting in which tiles are diamonds rather than squares, so walls cast smaller
+    /// shadows and you can see further past pillars and around corners.
+    DiamondWalls
+}
+
+/// A reusable set of visible tiles for a map of a fixed size, stored one bit per tile. Fill it
+/// with `compute` each turn instead of allocating a new list of points.
+#[derive(Clone)]
+pub struct VisibilityMap {
+    width : i32,
+    height : i32,
+    bits : Vec<u64>
+}
+
+impl VisibilityMap {
+    /// Creates an empty set for a map of the given size.
+    pub fn new(width : i32, height : i32) -> VisibilityMap {
+        let tiles = (width.max(0) * height.max(0)) as usize;
+        VisibilityMap{ width, height, bits : vec![0; tiles.div_ceil(64)] }
+    }
+
+    /// The width of the map.
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// The height of the map.
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Marks every tile as not visible.
+    pub fn clear(&mut self) {
+        for word in self.bits.iter_mut() { *word = 0; }
+    }
+
+    /// True if a tile is visible. Tiles outside the map never are.
+    pub fn is_visible(&self, pt : Point) -> bool {
+        match self.index(pt) {
+            Some(idx) => self.bits[idx / 64] & (1 << (idx % 64)) != 0,
+            None => false
+        }
+    }
+
+    /// Marks a tile as visible, if it is on the map.
+    pub fn set_visible(&mut self, pt : Point) {
+        if let Some(idx) = self.index(pt) {
+            self.bits[idx / 64] |= 1 << (idx % 64);
+        }
+    }
+
+    /// The number of visible tiles.
+    pub fn count(&self) -> usize {
+        self.bits.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// True if no tiles are visible.
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|word| *word == 0)
+    }
+
+    /// The visible tiles, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = Point> + '_ {
+        let width = self.width;
+        (0 .. width * self.height).filter(move |idx| self.bits[*idx as usize / 64] & (1 << (*idx as usize % 64)) != 0)
+            .map(move |idx| Point::new(idx % width, idx / width))
+    }
+
+    /// Replaces the contents with the tiles visible from `start`. Tiles outside the map are
+    /// treated as opaque and never passed to `fov_check`, so maps don't need to bounds-check.
+    pub fn compute(&mut self, algorithm : FovAlgorithm, start : Point, range : i32, fov_check : &dyn Algorithm2D) {
+        self.clear();
+        let (width, height) = (self.width, self.height);
+        let opaque = |pt : Point| {
+            pt.x < 0 || pt.y < 0 || pt.x >= width || pt.y >= height || fov_check.is_opaque(fov_check.point2d_to_index(pt))
+        };
+        cast(algorithm, start, range, &opaque, &mut |pt| self.set_visible(pt));
+    }
+
+    fn index(&self, pt : Point) -> Option<usize> {
+        if pt.x < 0 || pt.y < 0 || pt.x >= self.width || pt.y >= self.height { return None; }
+        Some((pt.y * self.width + pt.x) as usize)
+    }
+}
+
 #[allow(dead_code)]
 /// Calculates field-of-view for a map that supports Algorithm2D.
 pub fn field_of_view(start : Point, range : i32, fov_check : &Algorithm2D) -> Vec<Point> {
     let mut result : Vec<Point> = Vec::new();
+    cast_rays(start, range, &|pt| fov_check.is_opaque(fov_check.point2d_to_index(pt)), &mut |pt| result.push(pt));
+    return result;
+}
+
+/// Calculates field-of-view with a choice of algorithm. Unlike `field_of_view`, each visible
+/// tile appears once.
+pub fn field_of_view_with(algorithm : FovAlgorithm, start : Point, range : i32, fov_check : &dyn Algorithm2D) -> Vec<Point> {
+    let mut result : Vec<Point> = Vec::new();
+    let side = range.max(0) * 2 + 1;
+    let mut seen = vec![false; (side * side) as usize];
+    let opaque = |pt : Point| fov_check.is_opaque(fov_check.point2d_to_index(pt));
+
+    cast(algorithm, start, range, &opaque, &mut |pt| {
+        let (x, y) = (pt.x - start.x + range, pt.y - start.y + range);
+        if x >= 0 && y >= 0 && x < side && y < side && !seen[(y * side + x) as usize] {
+            seen[(y * side + x) as usize] = true;
+            result.push(pt);
+        }
+    });
+    result
+}
+
+/// Calculates field-of-view for a 3D map that supports Algorithm3D, such as a Dwarf Fortress
+/// style world of z-levels. Each tile is a cube, and sight passes through any tile that isn't
+/// opaque: make open space and transparent floors (grates, glass) see-through, and solid ground
+/// opaque, and you can look down shafts, off cliffs and up to higher levels.
+///
+/// A tile within `range` is visible if a straight line joins it to the start without passing
+/// through an opaque tile, either way round, so visibility is symmetric. Opaque tiles themselves
+/// are visible. Every tile in range is looked up, so off-map points should report as opaque.
+pub fn field_of_view_3d(start : Point3, range : i32, fov_check : &dyn Algorithm3D) -> Vec<Point3> {
+    let range = range.max(0);
+    let range_squared = range * range;
+    let opaque = |pt : Point3| fov_check.is_opaque(fov_check.point3d_to_index(pt));
+    let clear = |from : Point3, to : Point3| {
+        let line = line3d(from, to);
+        line.len() < 3 || !line[1 .. line.len() - 1].iter().any(|pt| opaque(*pt))
+    };
+
+    let mut result : Vec<Point3> = Vec::new();
+    for z in -range ..= range {
+        for y in -range ..= range {
+            for x in -range ..= range {
+                if x * x + y * y + z * z > range_squared { continue; }
+                let target = Point3::new(start.x + x, start.y + y, start.z + z);
+                if clear(start, target) || clear(target, start) {
+                    result.push(target);
+                }
+            }
+        }
+    }
+    result
+}
 
+/// Runs an algorithm, calling `mark` for each visible tile (possibly more than once).
+fn cast(algorithm : FovAlgorithm, start : Point, range : i32, opaque : &dyn Fn(Point) -> bool, mark : &mut dyn FnMut(Point)) {
+    let range = range.max(0);
+    let range_squared = range * range;
+    let mut mark_in_range = |pt : Point| {
+        let (dx, dy) = (pt.x - start.x, pt.y - start.y);
+        if dx * dx + dy * dy <= range_squared { mark(pt); }
+    };
+
+    match algorithm {
+        FovAlgorithm::Rays => cast_rays(start, range, opaque, &mut mark_in_range),
+        FovAlgorithm::Shadowcasting | FovAlgorithm::DiamondWalls => {
+            mark_in_range(start);
+            let diamonds = algorithm == FovAlgorithm::DiamondWalls;
+            for octant in OCTANTS.iter() {
+                cast_octant(start, 1, 1.0, 0.0, range, *octant, diamonds, opaque, &mut mark_in_range);
+            }
+        }
+        FovAlgorithm::SymmetricShadowcasting => {
+            mark_in_range(start);
+            for quadrant in 0 .. 4 {
+                scan_quadrant_row(start, quadrant, 1, (-1, 1), (1, 1), range, opaque, &mut mark_in_range);
+            }
+        }
+        FovAlgorithm::Permissive => {
+            mark_in_range(start);
+            for (dx, dy) in [(1, 1), (1, -1), (-1, -1), (-1, 1)].iter() {
+                check_permissive_quadrant(start, *dx, *dy, range, opaque, &mut mark_in_range);
+            }
+        }
+    }
+}
+
+/// Casts Bresenham rays from the start to every tile on the edge of the range.
+fn cast_rays(start : Point, range : i32, opaque : &dyn Fn(Point) -> bool, mark : &mut dyn FnMut(Point)) {
     let left = start.x - range;
     let right = start.x + range;
     let top = start.y - range;
@@ -17,46 +206,444 @@ pub fn field_of_view(start : Point, range : i32, fov_check : &Algorithm2D) -> Ve
     let range_squared : f32 = (range as f32) * (range as f32);
 
     for x in left .. right+1 {
-        for pt in scan_fov_line(start, Point::new(x, top, ), range_squared, fov_check) {
-            result.push(pt);
-        }
-        for pt in scan_fov_line(start, Point::new(x, bottom), range_squared, fov_check) {
-            result.push(pt);
-        }
+        scan_fov_line(start, Point::new(x, top, ), range_squared, opaque, mark);
+        scan_fov_line(start, Point::new(x, bottom), range_squared, opaque, mark);
     }
 
     for y in top .. bottom+1 {
-        for pt in scan_fov_line(start, Point::new(left, y), range_squared, fov_check) {
-            result.push(pt);
+        scan_fov_line(start, Point::new(left, y), range_squared, opaque, mark);
+        scan_fov_line(start, Point::new(right, y), range_squared, opaque, mark);
+    }
+}
+
+/// Helper method to scan along a line.
+fn scan_fov_line(start: Point, end: Point, range_squared : f32, opaque : &dyn Fn(Point) -> bool, mark : &mut dyn FnMut(Point)) {
+    let line = Bresenham::new((start.x as isize, start.y as isize), (end.x as isize, end.y as isize));
+
+    for (x, y) in line {
+        let target = Point::new(x as i32, y as i32);
+        let dsq = distance2d(DistanceAlg::PythagorasSquared, start, target);
+        if dsq > range_squared {
+            return;
         }
-        for pt in scan_fov_line(start, Point::new(right, y), range_squared, fov_check) {
-            result.push(pt);
+        mark(target);
+        if opaque(target) {
+            return;
         }
     }
+}
 
-    return result;
+/// Multipliers (xx, xy, yx, yy) turning octant-relative offsets into map offsets.
+const OCTANTS : [(i32, i32, i32, i32); 8] = [
+    (1, 0, 0, 1), (0, 1, 1, 0), (0, -1, 1, 0), (-1, 0, 0, 1),
+    (-1, 0, 0, -1), (0, -1, -1, 0), (0, 1, -1, 0), (1, 0, 0, -1)
+];
+
+/// The widest and narrowest slopes (dx / dy) from the start to a tile, treating it as a square
+/// or a diamond.
+fn tile_slopes(dx : i32, dy : i32, diamonds : bool) -> (f32, f32) {
+    let (x, y) = (dx as f32, dy as f32);
+    let corners = if diamonds {
+        [(x - 0.5, y), (x + 0.5, y), (x, y - 0.5), (x, y + 0.5)]
+    } else {
+        [(x - 0.5, y + 0.5), (x + 0.5, y - 0.5), (x - 0.5, y - 0.5), (x + 0.5, y + 0.5)]
+    };
+    corners.iter().map(|(cx, cy)| cx / cy).fold((f32::MIN, f32::MAX), |(wide, narrow), slope| (wide.max(slope), narrow.min(slope)))
 }
 
-/// Helper method to scan along a line.
-fn scan_fov_line(start: Point, end: Point, range_squared : f32, fov_check : &Algorithm2D) -> Vec<Point> {
-    let mut result : Vec<Point> = Vec::new();
-    let line = Bresenham::new((start.x as isize, start.y as isize), (end.x as isize, end.y as isize));
+/// Recursive shadowcasting of one octant, lighting the rows from `row` outwards that fall
+/// between the `start` and `end` slopes.
+#[allow(clippy::too_many_arguments)]
+fn cast_octant(origin : Point, row : i32, start : f32, end : f32, range : i32, octant : (i32, i32, i32, i32), diamonds : bool,
+    opaque : &dyn Fn(Point) -> bool, mark : &mut dyn FnMut(Point))
+{
+    if start < end { return; }
+    let (xx, xy, yx, yy) = octant;
+    let mut start = start;
+    let mut new_start = 0.0;
 
-    let mut blocked = false;
+    for j in row ..= range {
+        let dy = -j;
+        let mut blocked = false;
+        for dx in -j ..= 0 {
+            let pt = Point::new(origin.x + dx * xx + dy * xy, origin.y + dx * yx + dy * yy);
+            let (wide_slope, narrow_slope) = tile_slopes(dx, dy, diamonds);
+            if start < narrow_slope { continue; }
+            if end > wide_slope { break; }
 
-    for (x, y) in line {
-        if !blocked {
-            let target = Point::new(x as i32, y as i32);
-            let dsq = distance2d(DistanceAlg::PythagorasSquared, start, target);
-            if dsq <= range_squared {
-                if fov_check.is_opaque(fov_check.point2d_to_index(target)) {
-                    blocked = true;
+            mark(pt);
+            let is_opaque = opaque(pt);
+            if blocked {
+                if is_opaque {
+                    new_start = narrow_slope;
+                } else {
+                    blocked = false;
+                    start = new_start;
                 }
-                result.push(target);
-            } else {
+            } else if is_opaque && j < range {
                 blocked = true;
+                cast_octant(origin, j + 1, start, wide_slope, range, octant, diamonds, opaque, mark);
+                new_start = narrow_slope;
+            }
+        }
+        if blocked { break; }
+    }
+}
+
+/// A slope (column / depth) held as an exact fraction, with a positive denominator.
+type Slope = (i64, i64);
+
+/// Symmetric shadowcasting of one row of a quadrant, recursing into the rows beyond it.
+#[allow(clippy::too_many_arguments)]
+fn scan_quadrant_row(origin : Point, quadrant : i32, depth : i32, start : Slope, end : Slope, range : i32,
+    opaque : &dyn Fn(Point) -> bool, mark : &mut dyn FnMut(Point))
+{
+    if depth > range { return; }
+    let to_map = |col : i32| match quadrant {
+        0 => Point::new(origin.x + col, origin.y - depth),
+        1 => Point::new(origin.x + depth, origin.y + col),
+        2 => Point::new(origin.x + col, origin.y + depth),
+        _ => Point::new(origin.x - depth, origin.y + col)
+    };
+    let d = depth as i64;
+    // Columns whose centers lie between the slopes, rounding ties outwards.
+    let min_col = (2 * d * start.0 + start.1).div_euclid(2 * start.1) as i32;
+    let max_col = -(end.1 - 2 * d * end.0).div_euclid(2 * end.1) as i32;
+
+    let mut start = start;
+    let mut previous_wall : Option<bool> = None;
+    for col in min_col ..= max_col {
+        let pt = to_map(col);
+        let wall = opaque(pt);
+        let c = col as i64;
+        let symmetric = c * start.1 >= d * start.0 && c * end.1 <= d * end.0;
+        if wall || symmetric {
+            mark(pt);
+        }
+        if previous_wall == Some(true) && !wall {
+            start = (2 * c - 1, 2 * d);
+        }
+        if previous_wall == Some(false) && wall {
+            scan_quadrant_row(origin, quadrant, depth + 1, start, (2 * c - 1, 2 * d), range, opaque, mark);
+        }
+        previous_wall = Some(wall);
+    }
+    if previous_wall == Some(false) {
+        scan_quadrant_row(origin, quadrant, depth + 1, start, end, range, opaque, mark);
+    }
+}
+
+/// A line between two grid corners, used by precise permissive FOV.
+#[derive(Clone, Copy)]
+struct ViewLine { xi : i32, yi : i32, xf : i32, yf : i32 }
+
+impl ViewLine {
+    /// Positive if the point is below the line, negative if above, zero if on it.
+    fn relative_slope(&self, x : i32, y : i32) -> i32 {
+        (self.yf - self.yi) * (self.xf - x) - (self.xf - self.xi) * (self.yf - y)
+    }
+    fn is_below(&self, x : i32, y : i32) -> bool { self.relative_slope(x, y) > 0 }
+    fn is_below_or_collinear(&self, x : i32, y : i32) -> bool { self.relative_slope(x, y) >= 0 }
+    fn is_above(&self, x : i32, y : i32) -> bool { self.relative_slope(x, y) < 0 }
+    fn is_above_or_collinear(&self, x : i32, y : i32) -> bool { self.relative_slope(x, y) <= 0 }
+    fn is_collinear(&self, x : i32, y : i32) -> bool { self.relative_slope(x, y) == 0 }
+    fn is_line_collinear(&self, other : &ViewLine) -> bool { self.is_collinear(other.xi, other.yi) && self.is_collinear(other.xf, other.yf) }
+}
+
+/// A corner that narrows a view, linked to the corners found before it.
+struct ViewBump { x : i32, y : i32, parent : Option<usize> }
+
+/// A wedge of a quadrant that is still visible, between a shallow and a steep line.
+#[derive(Clone)]
+struct View { shallow : ViewLine, steep : ViewLine, shallow_bump : Option<usize>, steep_bump : Option<usize> }
+
+/// Precise permissive FOV of one quadrant, walking the diagonals outwards and narrowing (or
+/// splitting) the visible views around each opaque tile.
+fn check_permissive_quadrant(origin : Point, dx : i32, dy : i32, range : i32, opaque : &dyn Fn(Point) -> bool, mark : &mut dyn FnMut(Point)) {
+    let mut views = vec![View{
+        shallow : ViewLine{ xi : 0, yi : 1, xf : range, yf : 0 }, steep : ViewLine{ xi : 1, yi : 0, xf : 0, yf : range },
+        shallow_bump : None, steep_bump : None
+    }];
+    let mut bumps : Vec<ViewBump> = Vec::new();
+
+    for i in 1 ..= range * 2 {
+        if views.is_empty() { break; }
+        for j in i32::max(0, i - range) ..= i32::min(i, range) {
+            if views.is_empty() { break; }
+            visit_permissive(origin, i - j, j, dx, dy, &mut views, &mut bumps, opaque, mark);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn visit_permissive(origin : Point, x : i32, y : i32, dx : i32, dy : i32, views : &mut Vec<View>, bumps : &mut Vec<ViewBump>,
+    opaque : &dyn Fn(Point) -> bool, mark : &mut dyn FnMut(Point))
+{
+    let (top_left, bottom_right) = ((x, y + 1), (x + 1, y));
+    let mut index = 0;
+    while index < views.len() && views[index].steep.is_below_or_collinear(bottom_right.0, bottom_right.1) {
+        index += 1;
+    }
+    if index == views.len() || views[index].shallow.is_above_or_collinear(top_left.0, top_left.1) {
+        return;
+    }
+
+    let pt = Point::new(origin.x + x * dx, origin.y + y * dy);
+    mark(pt);
+    if !opaque(pt) { return; }
+
+    let shallow_above = views[index].shallow.is_above(bottom_right.0, bottom_right.1);
+    let steep_below = views[index].steep.is_below(top_left.0, top_left.1);
+    if shallow_above && steep_below {
+        views.remove(index);
+    } else if shallow_above {
+        add_shallow_bump(top_left, &mut views[index], bumps);
+        check_view(views, index);
+    } else if steep_below {
+        add_steep_bump(bottom_right, &mut views[index], bumps);
+        check_view(views, index);
+    } else {
+        // The tile splits the view in two.
+        let copy = views[index].clone();
+        views.insert(index, copy);
+        let mut steep_index = index + 1;
+        add_steep_bump(bottom_right, &mut views[index], bumps);
+        if !check_view(views, index) {
+            steep_index -= 1;
+        }
+        add_shallow_bump(top_left, &mut views[steep_index], bumps);
+        check_view(views, steep_index);
+    }
+}
+
+fn add_shallow_bump(corner : (i32, i32), view : &mut View, bumps : &mut Vec<ViewBump>) {
+    view.shallow.xf = corner.0;
+    view.shallow.yf = corner.1;
+    bumps.push(ViewBump{ x : corner.0, y : corner.1, parent : view.shallow_bump });
+    view.shallow_bump = Some(bumps.len() - 1);
+
+    let mut current = view.steep_bump;
+    while let Some(bump) = current.map(|idx| &bumps[idx]) {
+        if view.shallow.is_above(bump.x, bump.y) {
+            view.shallow.xi = bump.x;
+            view.shallow.yi = bump.y;
+        }
+        current = bump.parent;
+    }
+}
+
+fn add_steep_bump(corner : (i32, i32), view : &mut View, bumps : &mut Vec<ViewBump>) {
+    view.steep.xf = corner.0;
+    view.steep.yf = corner.1;
+    bumps.push(ViewBump{ x : corner.0, y : corner.1, parent : view.steep_bump });
+    view.steep_bump = Some(bumps.len() - 1);
+
+    let mut current = view.shallow_bump;
+    while let Some(bump) = current.map(|idx| &bumps[idx]) {
+        if view.steep.is_below(bump.x, bump.y) {
+            view.steep.xi = bump.x;
+            view.steep.yi = bump.y;
+        }
+        current = bump.parent;
+    }
+}
+
+/// Drops a view that has narrowed to nothing, returning false if it was dropped.
+fn check_view(views : &mut Vec<View>, index : usize) -> bool {
+    let (shallow, steep) = (views[index].shallow, views[index].steep);
+    if shallow.is_line_collinear(&steep) && (shallow.is_collinear(0, 1) || shallow.is_collinear(1, 0)) {
+        views.remove(index);
+        false
+    } else {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{field_of_view, field_of_view_with, field_of_view_3d, FovAlgorithm, VisibilityMap};
+    use super::super::{Algorithm2D, Algorithm3D, BaseMap, Point, Point3};
+
+    const ALL : [FovAlgorithm; 5] = [FovAlgorithm::Rays, FovAlgorithm::Shadowcasting, FovAlgorithm::SymmetricShadowcasting,
+        FovAlgorithm::Permissive, FovAlgorithm::DiamondWalls];
+
+    // A map drawn with '#' for walls. Everything off the edge is a wall.
+    struct Map { width : i32, height : i32, walls : Vec<bool> }
+
+    impl Map {
+        fn new(rows : &[&str]) -> Map {
+            Map{ width : rows[0].len() as i32, height : rows.len() as i32, walls : rows.iter().flat_map(|row| row.chars().map(|c| c == '#')).collect() }
+        }
+    }
+
+    impl BaseMap for Map {
+        fn is_opaque(&self, idx : i32) -> bool { idx < 0 || self.walls[idx as usize] }
+        fn get_available_exits(&self, _idx : i32) -> Vec<(i32, f32)> { Vec::new() }
+        fn get_pathing_distance(&self, _idx1 : i32, _idx2 : i32) -> f32 { 0.0 }
+    }
+
+    impl Algorithm2D for Map {
+        fn point2d_to_index(&self, pt : Point) -> i32 {
+            if pt.x < 0 || pt.y < 0 || pt.x >= self.width || pt.y >= self.height { -1 } else { pt.y * self.width + pt.x }
+        }
+        fn index_to_point2d(&self, idx : i32) -> Point { Point::new(idx % self.width, idx / self.width) }
+    }
+
+    fn open(size : i32) -> Map {
+        let row = ".".repeat(size as usize);
+        Map::new(&vec![row.as_str(); size as usize])
+    }
+
+    #[test]
+    // In the open, every algorithm sees each tile in range exactly once.
+    fn test_open_ground() {
+        let map = open(21);
+        let in_range = (-6 ..= 6).flat_map(|y| (-6 ..= 6).map(move |x| (x, y))).filter(|(x, y)| x * x + y * y <= 36).count();
+        for algorithm in ALL.iter() {
+            let mut fov = field_of_view_with(*algorithm, Point::new(10, 10), 6, &map);
+            let count = fov.len();
+            fov.sort_by_key(|pt| (pt.y, pt.x));
+            fov.dedup();
+            assert_eq!(fov.len(), count, "{:?}", algorithm);
+            if *algorithm != FovAlgorithm::Rays {
+                assert_eq!(count, in_range, "{:?}", algorithm);
+            }
+        }
+        assert!(field_of_view(Point::new(10, 10), 6, &map).len() > in_range);
+    }
+
+    #[test]
+    // Walls are visible, but hide what's behind them.
+    fn test_walls_block() {
+        let map = Map::new(&[
+            ".......",
+            "...#...",
+            ".......",
+        ]);
+        for algorithm in ALL.iter() {
+            let fov = field_of_view_with(*algorithm, Point::new(3, 2), 5, &map);
+            assert!(fov.contains(&Point::new(3, 1)), "{:?}", algorithm);
+            assert!(!fov.contains(&Point::new(3, 0)), "{:?}", algorithm);
+            assert!(fov.contains(&Point::new(0, 2)), "{:?}", algorithm);
+        }
+    }
+
+    #[test]
+    // With symmetric algorithms, if one floor tile can see another, it is seen back.
+    fn test_symmetry() {
+        let map = Map::new(&[
+            "...........",
+            "..#.....#..",
+            ".....#.....",
+            ".#.......#.",
+            "....#.#....",
+            "...........",
+            "..#...#..#.",
+            "...........",
+        ]);
+        let floors : Vec<Point> = (0 .. map.width * map.height).filter(|idx| !map.walls[*idx as usize]).map(|idx| map.index_to_point2d(idx)).collect();
+        for algorithm in [FovAlgorithm::SymmetricShadowcasting, FovAlgorithm::Permissive].iter() {
+            let mut views = VisibilityMap::new(map.width, map.height);
+            let seen : Vec<VisibilityMap> = floors.iter().map(|pt| { views.compute(*algorithm, *pt, 20, &map); views.clone() }).collect();
+            for (a, seen_from_a) in floors.iter().zip(seen.iter()) {
+                for (b, seen_from_b) in floors.iter().zip(seen.iter()) {
+                    assert_eq!(seen_from_a.is_visible(*b), seen_from_b.is_visible(*a), "{:?} {:?} {:?}", algorithm, a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    // Permissive sees past a pillar that hides tiles from symmetric shadowcasting; diamond walls
+    // cast smaller shadows than square ones.
+    fn test_generosity() {
+        let map = Map::new(&[
+            "........",
+            "........",
+            "..#.....",
+            "........",
+        ]);
+        let start = Point::new(0, 3);
+        let count = |algorithm| field_of_view_with(algorithm, start, 10, &map).len();
+        assert!(count(FovAlgorithm::Permissive) >= count(FovAlgorithm::SymmetricShadowcasting));
+        assert!(count(FovAlgorithm::DiamondWalls) >= count(FovAlgorithm::Shadowcasting));
+        assert!(count(FovAlgorithm::Permissive) > count(FovAlgorithm::SymmetricShadowcasting) || count(FovAlgorithm::DiamondWalls) > count(FovAlgorithm::Shadowcasting));
+    }
+
+    #[test]
+    // A visibility map is cleared on each compute, and never sees off the edge of the map.
+    fn test_visibility_map() {
+        let map = open(5);
+        let mut views = VisibilityMap::new(5, 5);
+        assert!(views.is_empty());
+        views.compute(FovAlgorithm::Shadowcasting, Point::new(0, 0), 2, &map);
+        assert_eq!(views.count(), 6);
+        assert_eq!(views.iter().collect::<Vec<Point>>(), vec![Point::new(0, 0), Point::new(1, 0), Point::new(2, 0), Point::new(0, 1), Point::new(1, 1), Point::new(0, 2)]);
+        assert!(!views.is_visible(Point::new(-1, 0)));
+
+        views.compute(FovAlgorithm::Shadowcasting, Point::new(4, 4), 1, &map);
+        assert!(!views.is_visible(Point::new(0, 0)));
+        assert!(views.is_visible(Point::new(3, 4)));
+        views.clear();
+        assert!(views.is_empty());
+    }
+
+    // A 7x7x5 world: solid ground on levels 0 and 1 (z is up) with a shaft dug down at (3, 3),
+    // and a roof on level 3 that is glass over x = 0.
+    struct World;
+
+    impl World {
+        fn solid(pt : Point3) -> bool {
+            if pt.x < 0 || pt.y < 0 || pt.z < 0 || pt.x >= 7 || pt.y >= 7 || pt.z >= 5 { return true; }
+            pt.z <= 1 && !(pt.x == 3 && pt.y == 3) || pt.z == 3 && pt.x > 0
+        }
+    }
+
+    impl BaseMap for World {
+        fn is_opaque(&self, idx : i32) -> bool { idx < 0 || World::solid(self.index_to_point3d(idx)) }
+        fn get_available_exits(&self, _idx : i32) -> Vec<(i32, f32)> { Vec::new() }
+        fn get_pathing_distance(&self, _idx1 : i32, _idx2 : i32) -> f32 { 0.0 }
+    }
+
+    impl Algorithm3D for World {
+        fn point3d_to_index(&self, pt : Point3) -> i32 {
+            if pt.x < 0 || pt.y < 0 || pt.z < 0 || pt.x >= 7 || pt.y >= 7 || pt.z >= 5 { -1 } else { (pt.z * 7 + pt.y) * 7 + pt.x }
+        }
+        fn index_to_point3d(&self, idx : i32) -> Point3 { Point3::new(idx % 7, (idx / 7) % 7, idx / 49) }
+    }
+
+    #[test]
+    // You can see across your level and the ground beneath it, down an open shaft from above it,
+    // and through a transparent roof, but not through solid ground.
+    fn test_3d() {
+        let above_shaft = field_of_view_3d(Point3::new(3, 3, 2), 5, &World);
+        assert!(above_shaft.contains(&Point3::new(0, 3, 2)));
+        assert!(above_shaft.contains(&Point3::new(1, 3, 1)));
+        assert!(above_shaft.contains(&Point3::new(3, 3, 0)));
+        assert!(!above_shaft.contains(&Point3::new(1, 3, 0)));
+        assert!(above_shaft.contains(&Point3::new(3, 3, 3)));
+        assert!(!above_shaft.contains(&Point3::new(3, 3, 4)));
+
+        let beside_shaft = field_of_view_3d(Point3::new(0, 3, 2), 5, &World);
+        assert!(!beside_shaft.contains(&Point3::new(3, 3, 0)));
+        assert!(beside_shaft.contains(&Point3::new(0, 3, 4)));
+        assert!(!beside_shaft.contains(&Point3::new(3, 3, 4)));
+
+        let mut sorted = above_shaft.clone();
+        sorted.sort_by_key(|pt| (pt.z, pt.y, pt.x));
+        sorted.dedup();
+        assert_eq!(sorted.len(), above_shaft.len());
+    }
+
+    #[test]
+    // 3D visibility is symmetric.
+    fn test_3d_symmetry() {
+        let open : Vec<Point3> = (0 .. 7 * 7 * 5).map(|idx| World.index_to_point3d(idx)).filter(|pt| !World::solid(*pt)).collect();
+        let seen : Vec<Vec<Point3>> = open.iter().map(|pt| field_of_view_3d(*pt, 6, &World)).collect();
+        for (a, seen_from_a) in open.iter().zip(seen.iter()) {
+            for (b, seen_from_b) in open.iter().zip(seen.iter()) {
+                assert_eq!(seen_from_a.contains(b), seen_from_b.contains(a), "{:?} {:?}", a, b);
             }
         }
     }
-    return result;
 }
@@ -33,6 +33,14 @@ impl Framebuffer {
         }
     }
 
+    /// A placeholder for contexts that don't render with OpenGL (such as the terminal backend).
+    pub fn headless() -> Framebuffer {
+        Framebuffer {
+            fbo : 0,
+            texture : 0
+        }
+    }
+
     pub fn bind(&self, gl : &gl::Gles2) {
         unsafe {
             gl.BindFramebuffer(gl::FRAMEBUFFER, self.fbo);
@@ -1,6 +1,9 @@
 use super::{Point, Point3};
 use std::cmp::{max, min};
 
+mod rect;
+pub use self::rect::Rect;
+
 extern crate bresenham;
 use bresenham::Bresenham;
 
@@ -114,9 +117,35 @@ pub fn line2d(start: Point, end: Point) -> Vec<Point> {
     result
 }
 
+/// Plots a line between two 3D points, including both ends, and returns the points along it.
+pub fn line3d(start: Point3, end: Point3) -> Vec<Point3> {
+    let delta = [end.x - start.x, end.y - start.y, end.z - start.z];
+    let length = delta.iter().map(|d| d.abs()).max().unwrap_or(0);
+    let mut current = [start.x, start.y, start.z];
+    let mut error = delta.map(|d| 2 * d.abs() - length);
+
+    let mut result = vec![start];
+    for _ in 0..length {
+        for ((position, d), error) in current.iter_mut().zip(delta.iter()).zip(error.iter_mut()) {
+            if d.abs() == length {
+                *position += d.signum();
+            } else {
+                if *error >= 0 {
+                    *position += d.signum();
+                    *error -= 2 * length;
+                }
+                *error += 2 * d.abs();
+            }
+        }
+        result.push(Point3::new(current[0], current[1], current[2]));
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{distance2d, distance3d, DistanceAlg, Point, Point3, project_angle};
+    use super::{distance2d, distance3d, DistanceAlg, Point, Point3, project_angle, line3d};
 
     #[test]
     fn test_pythagoras_distance() {
@@ -253,4 +282,18 @@ mod tests {
         dest = project_angle(start, 10.0, 5.49779); // 315 degrees, north-west
         assert_eq!(dest, Point::new(-7, -7));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_line3d() {
+        let line = line3d(Point3::new(0,0,0), Point3::new(3,0,0));
+        assert_eq!(line, vec![Point3::new(0,0,0), Point3::new(1,0,0), Point3::new(2,0,0), Point3::new(3,0,0)]);
+
+        let line = line3d(Point3::new(2,2,2), Point3::new(-2,0,1));
+        assert_eq!(line.len(), 5);
+        assert_eq!(line[0], Point3::new(2,2,2));
+        assert_eq!(line[2], Point3::new(0,1,1));
+        assert_eq!(line[4], Point3::new(-2,0,1));
+
+        assert_eq!(line3d(Point3::new(1,1,1), Point3::new(1,1,1)), vec![Point3::new(1,1,1)]);
+    }
+}
@@ -0,0 +1,93 @@
+use super::super::Point;
+use std::cmp::{max, min};
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+/// An axis-aligned rectangle. x1/y1 are inclusive, x2/y2 are exclusive - so a rectangle
+/// created with `with_size(0, 0, 10, 10)` covers cells 0 to 9 on each axis.
+pub struct Rect {
+    pub x1: i32,
+    pub y1: i32,
+    pub x2: i32,
+    pub y2: i32
+}
+
+impl Rect {
+    /// Create a rectangle from a top-left corner and a width/height.
+    pub fn with_size(x:i32, y:i32, width:i32, height:i32) -> Rect {
+        Rect{ x1: x, y1: y, x2: x + width, y2: y + height }
+    }
+
+    /// Create a rectangle from exact (exclusive) extents.
+    pub fn with_exact(x1:i32, y1:i32, x2:i32, y2:i32) -> Rect {
+        Rect{ x1, y1, x2, y2 }
+    }
+
+    /// Width of the rectangle, in cells.
+    pub fn width(&self) -> i32 {
+        max(0, self.x2 - self.x1)
+    }
+
+    /// Height of the rectangle, in cells.
+    pub fn height(&self) -> i32 {
+        max(0, self.y2 - self.y1)
+    }
+
+    /// True if the rectangle covers no cells.
+    pub fn is_empty(&self) -> bool {
+        self.width() == 0 || self.height() == 0
+    }
+
+    /// True if this rectangle overlaps another one.
+    pub fn intersect(&self, other:&Rect) -> bool {
+        self.x1 < other.x2 && self.x2 > other.x1 && self.y1 < other.y2 && self.y2 > other.y1
+    }
+
+    /// Returns the part of this rectangle that is also inside another one. If they don't
+    /// overlap, the result is empty.
+    pub fn clip_to(&self, other:&Rect) -> Rect {
+        let x1 = max(self.x1, other.x1);
+        let y1 = max(self.y1, other.y1);
+        Rect{ x1, y1, x2: max(x1, min(self.x2, other.x2)), y2: max(y1, min(self.y2, other.y2)) }
+    }
+
+    /// Moves the rectangle by dx/dy.
+    pub fn translate(&self, dx:i32, dy:i32) -> Rect {
+        Rect{ x1: self.x1 + dx, y1: self.y1 + dy, x2: self.x2 + dx, y2: self.y2 + dy }
+    }
+
+    /// True if a point is inside the rectangle.
+    pub fn point_in_rect(&self, point:Point) -> bool {
+        point.x >= self.x1 && point.x < self.x2 && point.y >= self.y1 && point.y < self.y2
+    }
+
+    /// The center of the rectangle.
+    pub fn center(&self) -> Point {
+        Point::new((self.x1 + self.x2) / 2, (self.y1 + self.y2) / 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rect, Point};
+
+    #[test]
+    fn test_point_in_rect() {
+        let r = Rect::with_size(2, 2, 3, 3);
+        assert!(r.point_in_rect(Point::new(2, 2)));
+        assert!(r.point_in_rect(Point::new(4, 4)));
+        assert!(!r.point_in_rect(Point::new(5, 4)));
+        assert!(!r.point_in_rect(Point::new(1, 3)));
+    }
+
+    #[test]
+    fn test_clip_to() {
+        let a = Rect::with_size(0, 0, 10, 10);
+        let b = Rect::with_size(5, 8, 10, 10);
+        assert!(a.intersect(&b));
+        assert_eq!(a.clip_to(&b), Rect::with_exact(5, 8, 10, 10));
+
+        let c = Rect::with_size(20, 20, 5, 5);
+        assert!(!a.intersect(&c));
+        assert!(a.clip_to(&c).is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+use super::RGB;
+
+/// How a `Gradient` blends between its stops.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Interpolation {
+    /// A straight lerp of the red, green and blue channels.
+    Rgb,
+    /// Around the hue circle, the short way. Good for rainbows and fire.
+    Hsv,
+    /// Through OKLab, so the brightness changes evenly along the gradient.
+    OkLab
+}
+
+/// A smooth run of colors through any number of stops, sampled with a position from 0 to 1. Use
+/// it for health bars that fade from green to red, heat maps, or lighting ramps.
+#[derive(Clone)]
+pub struct Gradient {
+    stops: Vec<(f32, RGB)>,
+    interpolation: Interpolation
+}
+
+impl Gradient {
+    /// Creates a gradient through the given colors, spaced evenly from 0 to 1.
+    pub fn new(colors : &[RGB]) -> Gradient {
+        let last = usize::max(1, colors.len().saturating_sub(1)) as f32;
+        let stops = colors.iter().enumerate().map(|(i, col)| (i as f32 / last, *col)).collect();
+        Gradient{ stops, interpolation: Interpolation::OkLab }
+    }
+
+    /// Creates a gradient from (position, color) stops. Positions are usually 0 to 1, and need
+    /// not be in order.
+    pub fn with_stops(stops : &[(f32, RGB)]) -> Gradient {
+        let mut stops = stops.to_vec();
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        Gradient{ stops, interpolation: Interpolation::OkLab }
+    }
+
+    /// Picks how colors are blended between stops. The default is `Interpolation::OkLab`.
+    pub fn with_interpolation(mut self, interpolation : Interpolation) -> Gradient {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// The stops, ordered by position.
+    pub fn stops(&self) -> &[(f32, RGB)] {
+        &self.stops
+    }
+
+    /// The color at position `t`. Positions before the first stop or after the last take the
+    /// color of that stop; an empty gradient is black.
+    pub fn sample(&self, t : f32) -> RGB {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return RGB::new()
+        };
+        if t <= first.0 { return first.1; }
+        if t >= last.0 { return last.1; }
+
+        let next = self.stops.iter().position(|stop| stop.0 > t).unwrap_or(self.stops.len() - 1);
+        let (from, to) = (self.stops[next - 1], self.stops[next]);
+        let percent = (t - from.0) / (to.0 - from.0);
+        match self.interpolation {
+            Interpolation::Rgb => from.1.lerp(to.1, percent),
+            Interpolation::Hsv => from.1.lerp_hsv(to.1, percent),
+            Interpolation::OkLab => from.1.lerp_oklab(to.1, percent)
+        }
+    }
+
+    /// Samples `count` evenly spaced colors, from the start to the end of the gradient.
+    pub fn steps(&self, count : usize) -> Vec<RGB> {
+        let last = usize::max(1, count.saturating_sub(1)) as f32;
+        (0 .. count).map(|i| self.sample(i as f32 / last)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Gradient, Interpolation};
+    use super::super::RGB;
+
+    #[test]
+    // Stops are hit exactly, the ends are clamped, and positions in between blend.
+    fn test_sample() {
+        let (red, yellow, green) = (RGB::from_f32(1.0, 0.0, 0.0), RGB::from_f32(1.0, 1.0, 0.0), RGB::from_f32(0.0, 1.0, 0.0));
+        let gradient = Gradient::with_stops(&[(1.0, green), (0.0, red), (0.25, yellow)]).with_interpolation(Interpolation::Rgb);
+        assert!(gradient.sample(-1.0) == red);
+        assert!(gradient.sample(0.25) == yellow);
+        assert!(gradient.sample(2.0) == green);
+        assert!(gradient.sample(0.125) == RGB::from_f32(1.0, 0.5, 0.0));
+    }
+
+    #[test]
+    fn test_steps() {
+        let gradient = Gradient::new(&[RGB::from_f32(0.0, 0.0, 0.0), RGB::from_f32(1.0, 1.0, 1.0)]);
+        let steps = gradient.steps(5);
+        assert_eq!(steps.len(), 5);
+        assert!(steps[0] == RGB::from_f32(0.0, 0.0, 0.0));
+        assert!(steps[4] == RGB::from_f32(1.0, 1.0, 1.0));
+        // OKLab blends get lighter at every step
+        assert!(steps.windows(2).all(|pair| pair[1].r > pair[0].r));
+        assert!(Gradient::new(&[]).sample(0.5) == RGB::new());
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Console, RGB, codepage437::to_cp437};
+use super::{Console, Tile, RGB, codepage437::to_cp437};
 
 /// Draws a box, starting at x/y with the extents width/height using CP437 line characters
 pub fn draw_box(console : &mut dyn Console, sx:i32, sy:i32, width:i32, height:i32, fg: RGB, bg: RGB) {
@@ -68,4 +68,62 @@ pub fn draw_bar_vertical(console : &mut dyn Console, sx:i32, sy:i32, height:i32,
             console.set(sx, sy + y, fg, bg, to_cp437('░'));
         }
     }
-}
\ No newline at end of file
+}
+
+/// Fills a rectangle with a glyph and colors, clipped to the console's extents
+pub fn fill_region<C : Console + ?Sized>(console : &mut C, sx:i32, sy:i32, width:i32, height:i32, fg:RGB, bg:RGB, glyph:u8) {
+    let left = i32::max(0, sx);
+    let top = i32::max(0, sy);
+    let right = i32::min(console.width() as i32, sx + width);
+    let bottom = i32::min(console.height() as i32, sy + height);
+    for y in top .. bottom {
+        for x in left .. right {
+            console.set(x, y, fg, bg, glyph);
+        }
+    }
+}
+
+/// Moves the contents of a rectangle by dx/dy cells, clearing the cells left behind. The rectangle
+/// is clipped to the console's extents.
+pub fn scroll_region<C : Console + ?Sized>(console : &mut C, sx:i32, sy:i32, width:i32, height:i32, dx:i32, dy:i32) {
+    let left = i32::max(0, sx);
+    let top = i32::max(0, sy);
+    let right = i32::min(console.width() as i32, sx + width);
+    let bottom = i32::min(console.height() as i32, sy + height);
+    if right <= left || bottom <= top { return; }
+
+    let region_width = right - left;
+    let mut snapshot : Vec<Option<Tile>> = Vec::with_capacity((region_width * (bottom - top)) as usize);
+    for y in top .. bottom {
+        for x in left .. right {
+            snapshot.push(console.get(x, y));
+        }
+    }
+
+    for y in top .. bottom {
+        for x in left .. right {
+            let (source_x, source_y) = (x - dx, y - dy);
+            let source = if source_x >= left && source_x < right && source_y >= top && source_y < bottom {
+                snapshot[((source_y - top) * region_width + (source_x - left)) as usize]
+            } else {
+                None
+            };
+            match source {
+                Some(tile) => console.set(x, y, tile.fg, tile.bg, tile.glyph),
+                None => console.set(x, y, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0), 32)
+            }
+        }
+    }
+}
+
+/// Passes the foreground and background of every cell in a console through a function, such as
+/// a palette quantizer or a color-blindness filter.
+pub fn recolor(console : &mut dyn Console, transform : &dyn Fn(RGB) -> RGB) {
+    for y in 0 .. console.height() as i32 {
+        for x in 0 .. console.width() as i32 {
+            if let Some(tile) = console.get(x, y) {
+                console.set(x, y, transform(tile.fg), transform(tile.bg), tile.glyph);
+            }
+        }
+    }
+}
@@ -14,6 +14,8 @@ mod rltk;
 mod console;
 mod simple_console;
 mod sparse_console;
+mod virtual_console;
+mod tile_buffer;
 mod fieldofview;
 mod geometry;
 mod dijkstra;
@@ -26,6 +28,20 @@ mod gui_helpers;
 mod random;
 mod fastnoise;
 pub mod textblock;
+mod gradient;
+mod palette;
+mod effects;
+mod particles;
+mod tween;
+mod lighting;
+mod draw_target;
+mod camera;
+mod message_log;
+pub mod widgets;
+pub mod terminal;
+pub mod recording;
+#[cfg(feature = "serialization")]
+pub mod streaming;
 
 pub use self::rltk::main_loop;
 pub use self::rltk::Rltk;
@@ -36,15 +52,28 @@ pub use self::console::*;
 pub use self::shader::Shader;
 pub use self::simple_console::SimpleConsole;
 pub use self::sparse_console::SparseConsole;
-pub use self::fieldofview::field_of_view;
-pub use self::geometry::{ distance2d, distance3d, DistanceAlg, line2d, project_angle };
+pub use self::virtual_console::VirtualConsole;
+pub use self::fieldofview::{field_of_view, field_of_view_with, field_of_view_3d, FovAlgorithm, VisibilityMap};
+pub use self::geometry::{ distance2d, distance3d, DistanceAlg, line2d, line3d, project_angle, Rect };
 pub use self::dijkstra::DijkstraMap;
 pub use self::astar::{a_star_search, NavigationPath};
 pub use glutin::event::VirtualKeyCode;
-pub use self::codepage437::{string_to_cp437, to_cp437};
+pub use self::codepage437::{string_to_cp437, to_cp437, to_unicode};
 pub use self::random::RandomNumberGenerator;
 pub use self::fastnoise::*;
-pub use self::textblock::{TextBlock, TextBuilder};
+pub use self::textblock::{TextBlock, TextBuilder, Overflow, Alignment};
+pub use self::gradient::{Gradient, Interpolation};
+pub use self::palette::{Palette, ColorBlindness};
+pub use self::effects::{TileEffects, TileEffect, EffectKind, EffectTarget};
+pub use self::particles::{ParticleSystem, Emitter, Particle};
+pub use self::tween::{Tween, Tweenable, Easing};
+pub use self::lighting::{LightMap, Light, Falloff};
+pub use self::draw_target::DrawTarget;
+pub use self::camera::Camera;
+pub use self::message_log::{MessageLog, LogEntry};
+pub use self::widgets::{Ui, TextInput, TextInputResult, Menu, MenuResult};
+pub use self::terminal::TerminalConsole;
+pub use self::recording::RecordingFormat;
 
 #[cfg(feature = "serialization")]
 extern crate serde;
@@ -0,0 +1,337 @@
+use super::{field_of_view, Algorithm2D, Console, Point, RandomNumberGenerator, RGB};
+use super::geometry::{distance2d, DistanceAlg};
+
+/// How often flickering lights pick a new brightness.
+const FLICKER_STEP_MS : f32 = 80.0;
+
+/// How a light dims with distance, from full strength at its position to nothing at its radius.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Falloff {
+    /// Full strength all the way to the radius, then nothing.
+    Constant,
+    /// Dims evenly with distance.
+    Linear,
+    /// Dims quickly near the light, then tails off - a soft, natural looking pool of light.
+    Quadratic,
+    /// Stays bright near the light and fades smoothly towards the edge.
+    Smooth
+}
+
+impl Falloff {
+    /// The fraction of a light's strength (0 to 1) that reaches `distance` tiles away.
+    pub fn apply(&self, distance : f32, radius : f32) -> f32 {
+        if radius <= 0.0 { return if distance <= 0.0 { 1.0 } else { 0.0 }; }
+        let t = (distance / radius).clamp(0.0, 1.0);
+        match self {
+            Falloff::Constant => if distance <= radius { 1.0 } else { 0.0 },
+            Falloff::Linear => 1.0 - t,
+            Falloff::Quadratic => (1.0 - t).powi(2),
+            Falloff::Smooth => 1.0 - t * t * (3.0 - 2.0 * t)
+        }
+    }
+}
+
+/// A colored point light, such as a torch, a glowing fungus or a spell. Build one with `new`
+/// and the `with_` functions, then add it to a `LightMap`.
+#[derive(Clone)]
+pub struct Light {
+    pub position : Point,
+    pub color : RGB,
+    /// How far the light reaches, in tiles.
+    pub radius : i32,
+    /// Multiplies the light's color at its brightest point.
+    pub intensity : f32,
+    pub falloff : Falloff,
+    /// How much the light flickers: 0 is steady, 1 may dim all the way to nothing.
+    pub flicker : f32
+}
+
+impl Light {
+    /// A steady light with linear falloff.
+    pub fn new(position : Point, color : RGB, radius : i32) -> Light {
+        Light{ position, color, radius, intensity : 1.0, falloff : Falloff::Linear, flicker : 0.0 }
+    }
+
+    /// Sets how bright the light is at its center.
+    pub fn with_intensity(mut self, intensity : f32) -> Light {
+        self.intensity = intensity;
+        self
+    }
+
+    /// Sets how the light dims with distance.
+    pub fn with_falloff(mut self, falloff : Falloff) -> Light {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Makes the light flicker, like a torch. `amount` is from 0 (steady) to 1.
+    pub fn with_flicker(mut self, amount : f32) -> Light {
+        self.flicker = amount.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// A light in a map, with its current flicker level.
+struct PlacedLight {
+    id : usize,
+    light : Light,
+    flicker_level : f32
+}
+
+/// Works out how much light reaches each tile of a map from a set of colored lights plus an
+/// ambient level. Lights are blocked by opaque tiles, using `field_of_view` from each light.
+///
+/// Each tick, call `update` with `frame_time_ms` to animate flickering lights, `compute` with
+/// your map to recalculate the light levels, then draw the map and `apply` the lighting to the
+/// console: every drawn color is multiplied by the light on its tile. Colors from overlapping
+/// lights add together.
+pub struct LightMap {
+    width : i32,
+    height : i32,
+    /// The light every tile gets, even where no light reaches.
+    pub ambient : RGB,
+    lights : Vec<PlacedLight>,
+    next_id : usize,
+    levels : Vec<RGB>,
+    flicker_ms : f32,
+    rng : RandomNumberGenerator
+}
+
+impl LightMap {
+    /// Creates an unlit map of the given size. The seed drives flickering, so that the same seed
+    /// and frame times always flicker the same way.
+    pub fn new(width : i32, height : i32, seed : u64) -> LightMap {
+        let black = RGB::from_f32(0.0, 0.0, 0.0);
+        LightMap{
+            width, height, ambient : black, lights : Vec::new(), next_id : 0,
+            levels : vec![black; (width.max(0) * height.max(0)) as usize], flicker_ms : 0.0,
+            rng : RandomNumberGenerator::seeded(seed)
+        }
+    }
+
+    /// Sets the ambient light level.
+    pub fn with_ambient(mut self, ambient : RGB) -> LightMap {
+        self.ambient = ambient;
+        self
+    }
+
+    /// Adds a light, returning an id that can be used to move or remove it.
+    pub fn add_light(&mut self, light : Light) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.lights.push(PlacedLight{ id, light, flicker_level : 1.0 });
+        id
+    }
+
+    /// Removes a light.
+    pub fn remove_light(&mut self, id : usize) {
+        self.lights.retain(|placed| placed.id != id);
+    }
+
+    /// Removes every light.
+    pub fn clear_lights(&mut self) {
+        self.lights.clear();
+    }
+
+    /// Gives access to a light, for example to move a torch along with the player.
+    pub fn light_mut(&mut self, id : usize) -> Option<&mut Light> {
+        self.lights.iter_mut().find(|placed| placed.id == id).map(|placed| &mut placed.light)
+    }
+
+    /// The number of lights.
+    pub fn light_count(&self) -> usize {
+        self.lights.len()
+    }
+
+    /// Moves time on by `frame_time_ms`, giving flickering lights a new brightness every so often.
+    pub fn update(&mut self, frame_time_ms : f32) {
+        self.flicker_ms += frame_time_ms;
+        while self.flicker_ms >= FLICKER_STEP_MS {
+            self.flicker_ms -= FLICKER_STEP_MS;
+            for placed in self.lights.iter_mut().filter(|placed| placed.light.flicker > 0.0) {
+                placed.flicker_level = 1.0 - placed.light.flicker * self.rng.range(0.0f32, 1.0);
+            }
+        }
+    }
+
+    /// Recalculates the light on every tile of `map`. Call it after lights move or flicker, or
+    /// the map changes.
+    pub fn compute(&mut self, map : &dyn Algorithm2D) {
+        let mut totals = vec![(self.ambient.r, self.ambient.g, self.ambient.b); self.levels.len()];
+        let mut lit = vec![false; self.levels.len()];
+        let mut lit_by_light : Vec<usize> = Vec::new();
+
+        for placed in self.lights.iter() {
+            let light = &placed.light;
+            // Only the tiles the previous light reached need resetting.
+            for idx in lit_by_light.drain(..) { lit[idx] = false; }
+
+            for pt in field_of_view(light.position, light.radius, map) {
+                let idx = match self.index(pt.x, pt.y) {
+                    Some(idx) if !lit[idx] => idx,
+                    _ => continue
+                };
+                lit[idx] = true;
+                lit_by_light.push(idx);
+
+                let distance = distance2d(DistanceAlg::Pythagoras, light.position, pt);
+                let strength = light.falloff.apply(distance, light.radius as f32) * light.intensity * placed.flicker_level;
+                totals[idx].0 += light.color.r * strength;
+                totals[idx].1 += light.color.g * strength;
+                totals[idx].2 += light.color.b * strength;
+            }
+        }
+
+        for (level, total) in self.levels.iter_mut().zip(totals.iter()) {
+            *level = RGB::from_f32(total.0, total.1, total.2);
+        }
+    }
+
+    /// The light on a tile, as a color to multiply drawn colors by. Tiles outside the map get
+    /// the ambient light.
+    pub fn light_at(&self, x : i32, y : i32) -> RGB {
+        match self.index(x, y) {
+            Some(idx) => self.levels[idx],
+            None => self.ambient
+        }
+    }
+
+    /// How brightly lit a tile is, from 0 (dark) to 1 (fully lit in at least one channel).
+    /// Handy for gameplay, such as stealth.
+    pub fn level_at(&self, x : i32, y : i32) -> f32 {
+        let light = self.light_at(x, y);
+        f32::max(light.r, f32::max(light.g, light.b))
+    }
+
+    /// Multiplies every cell of a console by the light on its tile. `origin` is the map position
+    /// drawn in the console's top-left corner: `Point::new(0, 0)` unless the map scrolls.
+    pub fn apply(&self, console : &mut dyn Console, origin : Point) {
+        for y in 0 .. console.height() as i32 {
+            for x in 0 .. console.width() as i32 {
+                let idx = match self.index(x + origin.x, y + origin.y) {
+                    Some(idx) => idx,
+                    None => continue
+                };
+                if let Some(tile) = console.get(x, y) {
+                    let light = self.levels[idx];
+                    console.set(x, y, tile.fg * light, tile.bg * light, tile.glyph);
+                }
+            }
+        }
+    }
+
+    fn index(&self, x : i32, y : i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height { return None; }
+        Some((y * self.width + x) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Falloff, Light, LightMap};
+    use super::super::{Algorithm2D, BaseMap, Console, Point, SparseConsole, VirtualConsole, RGB};
+
+    // A 7x7 room with a wall running down column 4.
+    struct Room;
+
+    impl BaseMap for Room {
+        fn is_opaque(&self, idx : i32) -> bool { idx < 0 || idx % 7 == 4 }
+        fn get_available_exits(&self, _idx : i32) -> Vec<(i32, f32)> { Vec::new() }
+        fn get_pathing_distance(&self, _idx1 : i32, _idx2 : i32) -> f32 { 0.0 }
+    }
+
+    impl Algorithm2D for Room {
+        fn point2d_to_index(&self, pt : Point) -> i32 {
+            if pt.x < 0 || pt.y < 0 || pt.x >= 7 || pt.y >= 7 { -1 } else { pt.y * 7 + pt.x }
+        }
+        fn index_to_point2d(&self, idx : i32) -> Point { Point::new(idx % 7, idx / 7) }
+    }
+
+    fn close(a : f32, b : f32) -> bool { (a - b).abs() < 0.001 }
+
+    #[test]
+    // Every falloff is full strength at the light and (apart from Constant) nothing at the radius.
+    fn test_falloff() {
+        for falloff in [Falloff::Linear, Falloff::Quadratic, Falloff::Smooth].iter() {
+            assert!(close(falloff.apply(0.0, 4.0), 1.0));
+            assert!(close(falloff.apply(4.0, 4.0), 0.0));
+        }
+        assert!(close(Falloff::Constant.apply(4.0, 4.0), 1.0));
+        assert!(close(Falloff::Linear.apply(1.0, 4.0), 0.75));
+        assert!(close(Falloff::Quadratic.apply(2.0, 4.0), 0.25));
+        assert!(close(Falloff::Smooth.apply(2.0, 4.0), 0.5));
+    }
+
+    #[test]
+    // Light dims with distance, lights the wall it hits, but not the tiles behind it.
+    fn test_walls_block_light() {
+        let mut lights = LightMap::new(7, 7, 1).with_ambient(RGB::from_f32(0.1, 0.1, 0.1));
+        lights.add_light(Light::new(Point::new(1, 3), RGB::from_f32(1.0, 0.5, 0.0), 5));
+        lights.compute(&Room);
+
+        let at_light = lights.light_at(1, 3);
+        assert!(close(at_light.r, 1.0) && close(at_light.g, 0.6) && close(at_light.b, 0.1));
+        assert!(close(lights.light_at(3, 3).r, 0.1 + 0.6));
+        assert!(lights.level_at(4, 3) > 0.1);
+        assert!(lights.light_at(5, 3) == RGB::from_f32(0.1, 0.1, 0.1));
+        assert!(lights.light_at(-1, 3) == lights.ambient);
+    }
+
+    #[test]
+    // Overlapping lights add together, and the result multiplies a console's colors.
+    fn test_mixing_and_apply() {
+        let mut lights = LightMap::new(7, 7, 1);
+        lights.add_light(Light::new(Point::new(0, 0), RGB::from_f32(1.0, 0.0, 0.0), 3).with_falloff(Falloff::Constant));
+        let blue = lights.add_light(Light::new(Point::new(2, 0), RGB::from_f32(0.0, 0.0, 1.0), 3).with_intensity(0.5).with_falloff(Falloff::Constant));
+        lights.compute(&Room);
+        assert!(lights.light_at(1, 0) == RGB::from_f32(1.0, 0.0, 0.5));
+
+        let mut console = VirtualConsole::new(2, 1);
+        console.print_color(0, 0, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.5, 0.5, 0.5), "##");
+        lights.apply(&mut console, Point::new(1, 0));
+        assert!(console.get(0, 0).unwrap().fg == RGB::from_f32(1.0, 0.0, 0.5));
+        assert!(console.get(0, 0).unwrap().bg == RGB::from_f32(0.5, 0.0, 0.25));
+        assert_eq!(console.get(0, 0).unwrap().glyph, b'#');
+
+        lights.remove_light(blue);
+        lights.compute(&Room);
+        assert!(lights.light_at(1, 0) == RGB::from_f32(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    // Lighting a sparse layer every frame changes its drawn tiles in place, and skips empty cells.
+    fn test_apply_sparse() {
+        let mut lights = LightMap::new(7, 7, 1);
+        lights.add_light(Light::new(Point::new(0, 0), RGB::from_f32(0.5, 0.5, 0.5), 3).with_falloff(Falloff::Constant));
+        lights.compute(&Room);
+
+        let mut console = SparseConsole::headless(7, 7);
+        for _frame in 0 .. 3 {
+            console.print_color(1, 0, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0), "@");
+            lights.apply(console.as_mut(), Point::new(0, 0));
+            assert_eq!(console.tile_count(), 1);
+            assert!(console.get(1, 0).unwrap().fg == RGB::from_f32(0.5, 0.5, 0.5));
+            assert!(console.get(0, 0).is_none());
+        }
+    }
+
+    #[test]
+    // Flickering lights dim by up to their flicker amount, the same way for the same seed.
+    fn test_flicker() {
+        let run = |seed| {
+            let mut lights = LightMap::new(7, 7, seed);
+            lights.add_light(Light::new(Point::new(1, 1), RGB::from_f32(1.0, 1.0, 1.0), 4).with_flicker(0.5));
+            let mut levels = Vec::new();
+            for _ in 0 .. 10 {
+                lights.update(100.0);
+                lights.compute(&Room);
+                levels.push(lights.level_at(1, 1));
+            }
+            levels
+        };
+        let levels = run(7);
+        assert!(levels.iter().all(|level| *level >= 0.5 && *level <= 1.0));
+        assert!(levels.iter().any(|level| *level < 0.99));
+        assert_eq!(levels, run(7));
+    }
+}
@@ -0,0 +1,208 @@
+use super::{Console, Rltk, Rect, Point, RGB, VirtualKeyCode};
+
+/// A single message in the log. Repeats of the same message are merged into one entry.
+pub struct LogEntry {
+    pub text: String,
+    pub fg: RGB,
+    pub bg: RGB,
+    pub count: u32
+}
+
+impl LogEntry {
+    /// The text as displayed, with a repeat counter if the message was merged.
+    pub fn display_text(&self) -> String {
+        if self.count > 1 {
+            format!("{} x{}", self.text, self.count)
+        } else {
+            self.text.clone()
+        }
+    }
+}
+
+/// A scrolling message log, like the one at the bottom of most roguelike screens. Messages are
+/// word-wrapped to the width they are drawn at, the newest at the bottom. Repeated messages are
+/// merged ("You hit the orc x3"), and the player can scroll back with the mouse wheel or the
+/// PageUp/PageDown keys.
+pub struct MessageLog {
+    entries: Vec<LogEntry>,
+    capacity: usize,
+    scroll: usize
+}
+
+impl MessageLog {
+    /// Creates a log that remembers up to `capacity` messages; older ones are discarded.
+    pub fn new(capacity : usize) -> MessageLog {
+        MessageLog{ entries: Vec::new(), capacity, scroll: 0 }
+    }
+
+    /// Adds a white-on-black message.
+    pub fn log<S: ToString>(&mut self, text : S) {
+        self.log_color(text, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0));
+    }
+
+    /// Adds a message in color. If it is the same as the last message, the two are merged.
+    pub fn log_color<S: ToString>(&mut self, text : S, fg : RGB, bg : RGB) {
+        let text = text.to_string();
+        if let Some(last) = self.entries.last_mut() {
+            if last.text == text && last.fg == fg && last.bg == bg {
+                last.count += 1;
+                return;
+            }
+        }
+
+        self.entries.push(LogEntry{ text, fg, bg, count: 1 });
+        if self.entries.len() > self.capacity {
+            let excess = self.entries.len() - self.capacity;
+            self.entries.drain(0 .. excess);
+        }
+    }
+
+    /// The stored messages, oldest first.
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
+    /// Removes every message.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.scroll = 0;
+    }
+
+    /// How many lines the view is scrolled back from the newest message.
+    pub fn scroll_position(&self) -> usize {
+        self.scroll
+    }
+
+    /// Scrolls back (positive) or forward (negative) by a number of lines.
+    pub fn scroll_by(&mut self, lines : i32) {
+        self.scroll = i32::max(0, self.scroll as i32 + lines) as usize;
+    }
+
+    /// Jumps back to the newest messages.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll = 0;
+    }
+
+    /// Scrolls the log if the mouse wheel moved while the cursor was over `area`, or if
+    /// PageUp/PageDown was pressed. `area` should be the same as the one passed to `render`.
+    pub fn handle_input(&mut self, ctx : &Rltk, area : Rect) {
+        let (mx, my) = ctx.mouse_pos();
+        if ctx.mouse_wheel != 0 && area.point_in_rect(Point::new(mx, my)) {
+            self.scroll_by(ctx.mouse_wheel);
+        }
+        match ctx.key {
+            Some(VirtualKeyCode::PageUp) => self.scroll_by(area.height() - 1),
+            Some(VirtualKeyCode::PageDown) => self.scroll_by(1 - area.height()),
+            _ => {}
+        }
+
+        let max_scroll = self.wrapped_lines(area.width()).len().saturating_sub(area.height() as usize);
+        self.scroll = usize::min(self.scroll, max_scroll);
+    }
+
+    /// Wraps every message to the given width, oldest first.
+    fn wrapped_lines(&self, width : i32) -> Vec<(String, &LogEntry)> {
+        let mut lines = Vec::new();
+        for entry in self.entries.iter() {
+            for line in wrap_text(&entry.display_text(), width) {
+                lines.push((line, entry));
+            }
+        }
+        lines
+    }
+
+    /// Draws the log into `area` of a console, newest message at the bottom.
+    pub fn render(&self, console : &mut dyn Console, area : Rect) {
+        if area.is_empty() { return; }
+        console.fill_region(area.x1, area.y1, area.width(), area.height(), RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0), 32);
+
+        let lines = self.wrapped_lines(area.width());
+        let height = area.height() as usize;
+        let scroll = usize::min(self.scroll, lines.len().saturating_sub(height));
+        let end = lines.len() - scroll;
+        let start = end.saturating_sub(height);
+
+        let top = area.y2 - (end - start) as i32;
+        for (i, (line, entry)) in lines[start .. end].iter().enumerate() {
+            console.print_color(area.x1, top + i as i32, entry.fg, entry.bg, line);
+        }
+    }
+}
+
+/// Word-wraps text to a width. Words longer than a whole line are broken across lines.
+pub(crate) fn wrap_text(text : &str, width : i32) -> Vec<String> {
+    let width = i32::max(1, width) as usize;
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word : Vec<char> = word.chars().collect();
+        let current_len = current.chars().count();
+
+        if current_len > 0 && current_len + 1 + word.len() <= width {
+            current.push(' ');
+            current.extend(word.iter());
+            continue;
+        }
+        if current_len > 0 {
+            lines.push(current);
+            current = String::new();
+        }
+        while word.len() > width {
+            lines.push(word.drain(.. width).collect());
+        }
+        current.extend(word.iter());
+    }
+
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MessageLog, wrap_text};
+    use super::super::{Console, VirtualConsole, Rect, RGB};
+
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(wrap_text("You hit the orc for 5 damage", 12), vec!["You hit the", "orc for 5", "damage"]);
+        assert_eq!(wrap_text("Aaaaaaaaaargh!", 5), vec!["Aaaaa", "aaaaa", "rgh!"]);
+        assert_eq!(wrap_text("", 5), vec![""]);
+    }
+
+    #[test]
+    // Repeated messages are merged, and only the capacity is kept.
+    fn test_merge_and_capacity() {
+        let mut log = MessageLog::new(2);
+        log.log("You hit the orc");
+        log.log("You hit the orc");
+        log.log("You hit the orc");
+        assert_eq!(log.entries().len(), 1);
+        assert_eq!(log.entries()[0].display_text(), "You hit the orc x3");
+
+        log.log_color("You hit the orc", RGB::from_f32(1.0, 0.0, 0.0), RGB::from_f32(0.0, 0.0, 0.0));
+        log.log("The orc dies");
+        assert_eq!(log.entries().len(), 2);
+        assert_eq!(log.entries()[0].count, 1);
+    }
+
+    #[test]
+    // The newest line is at the bottom; scrolling back reveals older lines.
+    fn test_render_and_scroll() {
+        let mut log = MessageLog::new(10);
+        for i in 0 .. 5 {
+            log.log(format!("Message {}", i));
+        }
+        let mut console = VirtualConsole::new(12, 3);
+        log.render(&mut console, Rect::with_size(0, 0, 12, 3));
+        assert_eq!(console.get(8, 2).unwrap().glyph, b'4');
+        assert_eq!(console.get(8, 0).unwrap().glyph, b'2');
+
+        log.scroll_by(10);
+        log.render(&mut console, Rect::with_size(0, 0, 12, 3));
+        assert_eq!(console.get(8, 0).unwrap().glyph, b'0');
+        assert_eq!(console.get(8, 2).unwrap().glyph, b'2');
+    }
+}
@@ -0,0 +1,311 @@
+use super::{Console, RGB, color::{srgb_to_linear, linear_to_srgb}, gui_helpers};
+use std::io::{self, BufRead, Write};
+
+/// A fixed set of colors, such as the 16 EGA colors. Any color can be snapped to the nearest one
+/// in the palette (judged by how it looks, using OKLab), which gives a whole console a retro look.
+///
+/// Colors can also be named, so a palette loaded from a GIMP `.gpl` file (or JSON, with the
+/// `serialization` feature) can supply colors to data files by name.
+#[derive(Clone)]
+pub struct Palette {
+    colors: Vec<RGB>,
+    names: Vec<String>
+}
+
+/// A palette as stored in JSON: a list of names and colors. Colors may be HTML codes or the
+/// names of RLTK's color constants.
+#[cfg(feature = "serialization")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PaletteJson {
+    colors : Vec<PaletteJsonEntry>
+}
+
+#[cfg(feature = "serialization")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PaletteJsonEntry {
+    name : String,
+    color : String
+}
+
+/// Compares color names ignoring case, spaces, dashes and underscores.
+fn same_name(a : &str, b : &str) -> bool {
+    let simplify = |s : &str| s.chars().filter(|c| !matches!(c, ' ' | '-' | '_')).flat_map(|c| c.to_lowercase()).collect::<String>();
+    simplify(a) == simplify(b)
+}
+
+fn invalid_data(message : String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+impl Palette {
+    /// Creates a palette from a list of colors.
+    pub fn new(colors : &[RGB]) -> Palette {
+        Palette{ colors: colors.to_vec(), names: vec![String::new(); colors.len()] }
+    }
+
+    /// Creates a palette of named colors.
+    pub fn named(colors : &[(&str, RGB)]) -> Palette {
+        let mut palette = Palette::new(&[]);
+        for (name, col) in colors.iter() {
+            palette.push(name, *col);
+        }
+        palette
+    }
+
+    /// The 16-color EGA (and default VGA text mode) palette.
+    pub fn ega() -> Palette {
+        Palette::from_hex_codes(&[0x000000, 0x0000AA, 0x00AA00, 0x00AAAA, 0xAA0000, 0xAA00AA, 0xAA5500, 0xAAAAAA,
+            0x555555, 0x5555FF, 0x55FF55, 0x55FFFF, 0xFF5555, 0xFF55FF, 0xFFFF55, 0xFFFFFF])
+    }
+
+    /// The 16-color PICO-8 palette.
+    pub fn pico8() -> Palette {
+        Palette::from_hex_codes(&[0x000000, 0x1D2B53, 0x7E2553, 0x008751, 0xAB5236, 0x5F574F, 0xC2C3C7, 0xFFF1E8,
+            0xFF004D, 0xFFA300, 0xFFEC27, 0x00E436, 0x29ADFF, 0x83769C, 0xFF77A8, 0xFFCCAA])
+    }
+
+    /// `levels` shades of grey, from black to white.
+    pub fn greyscale(levels : usize) -> Palette {
+        let last = usize::max(1, levels.saturating_sub(1)) as f32;
+        let colors : Vec<RGB> = (0 .. levels).map(|i| { let v = i as f32 / last; RGB::from_f32(v, v, v) }).collect();
+        Palette::new(&colors)
+    }
+
+    fn from_hex_codes(codes : &[u32]) -> Palette {
+        let colors : Vec<RGB> = codes.iter().map(|c| RGB::from_u8((c >> 16) as u8, (c >> 8) as u8, *c as u8)).collect();
+        Palette::new(&colors)
+    }
+
+    /// Adds a color with a name (which may be empty).
+    pub fn push(&mut self, name : &str, color : RGB) {
+        self.colors.push(color);
+        self.names.push(name.to_string());
+    }
+
+    /// The colors in the palette.
+    pub fn colors(&self) -> &[RGB] {
+        &self.colors
+    }
+
+    /// The name of the color at `index`, if it has one.
+    pub fn name(&self, index : usize) -> Option<&str> {
+        self.names.get(index).map(|n| n.as_str()).filter(|n| !n.is_empty())
+    }
+
+    /// Finds a color by name, ignoring case, spaces, dashes and underscores.
+    pub fn get(&self, name : &str) -> Option<RGB> {
+        self.names.iter().position(|n| !n.is_empty() && same_name(n, name)).map(|i| self.colors[i])
+    }
+
+    /// Reads a GIMP palette (`.gpl`) file: a `GIMP Palette` header, optional `Name:` and
+    /// `Columns:` lines and `#` comments, then one `red green blue name` line per color.
+    pub fn read_gpl<R: BufRead>(reader : R) -> io::Result<Palette> {
+        let mut lines = reader.lines();
+        match lines.next() {
+            Some(Ok(ref header)) if header.trim() == "GIMP Palette" => {}
+            Some(Err(e)) => return Err(e),
+            _ => return Err(invalid_data("missing GIMP Palette header".to_string()))
+        }
+
+        let mut palette = Palette::new(&[]);
+        for line in lines {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("Name:") || trimmed.starts_with("Columns:") {
+                continue;
+            }
+
+            // Three numbers, then everything else on the line is the name
+            let mut rest = trimmed;
+            let mut channel = || {
+                let start = rest.trim_start();
+                let (number, after) = start.split_at(start.find(char::is_whitespace).unwrap_or(start.len()));
+                rest = after;
+                number.parse::<u8>().ok()
+            };
+            let (r, g, b) = match (channel(), channel(), channel()) {
+                (Some(r), Some(g), Some(b)) => (r, g, b),
+                _ => return Err(invalid_data(format!("invalid palette line: {}", trimmed)))
+            };
+            palette.push(rest.trim(), RGB::from_u8(r, g, b));
+        }
+        Ok(palette)
+    }
+
+    /// Writes the palette as a GIMP palette (`.gpl`) file, which most paint programs can load.
+    pub fn write_gpl<W: Write>(&self, writer : &mut W, name : &str) -> io::Result<()> {
+        writeln!(writer, "GIMP Palette")?;
+        writeln!(writer, "Name: {}", name)?;
+        writeln!(writer, "#")?;
+        for (col, name) in self.colors.iter().zip(self.names.iter()) {
+            let byte = |c : f32| (c * 255.0).round() as u8;
+            writeln!(writer, "{:3} {:3} {:3}\t{}", byte(col.r), byte(col.g), byte(col.b), name)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a palette from JSON: `{ "colors": [ { "name": "blood", "color": "#8a0303" } ] }`.
+    /// Colors may also be the names of RLTK's color constants, such as `"dark_red"`. Only
+    /// available with the `serialization` feature.
+    #[cfg(feature = "serialization")]
+    pub fn read_json<R: io::Read>(reader : R) -> io::Result<Palette> {
+        let file : PaletteJson = serde_json::from_reader(reader)?;
+        let mut palette = Palette::new(&[]);
+        for entry in file.colors.iter() {
+            let color = RGB::from_hex(&entry.color).ok()
+                .or_else(|| RGB::from_name(&entry.color))
+                .ok_or_else(|| invalid_data(format!("unknown color {} for {}", entry.color, entry.name)))?;
+            palette.push(&entry.name, color);
+        }
+        Ok(palette)
+    }
+
+    /// Writes the palette as JSON, in the format read by `read_json`. Only available with the
+    /// `serialization` feature.
+    #[cfg(feature = "serialization")]
+    pub fn write_json<W: Write>(&self, writer : &mut W) -> io::Result<()> {
+        let colors = self.colors.iter().zip(self.names.iter())
+            .map(|(col, name)| PaletteJsonEntry{ name : name.clone(), color : col.to_hex() })
+            .collect();
+        serde_json::to_writer_pretty(writer, &PaletteJson{ colors })?;
+        Ok(())
+    }
+
+    /// The index of the palette color that looks closest to `color`, or None if the palette is empty.
+    pub fn nearest_index(&self, color : RGB) -> Option<usize> {
+        let target = color.to_oklab();
+        let distance = |c : &RGB| {
+            let lab = c.to_oklab();
+            (lab.l - target.l).powi(2) + (lab.a - target.a).powi(2) + (lab.b - target.b).powi(2)
+        };
+        self.colors.iter().enumerate()
+            .min_by(|a, b| distance(a.1).partial_cmp(&distance(b.1)).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i)
+    }
+
+    /// The palette color that looks closest to `color`. An empty palette leaves colors unchanged.
+    pub fn quantize(&self, color : RGB) -> RGB {
+        match self.nearest_index(color) {
+            Some(i) => self.colors[i],
+            None => color
+        }
+    }
+
+    /// Snaps every foreground and background color on a console to the palette. Call it after
+    /// drawing a frame.
+    pub fn quantize_console(&self, console : &mut dyn Console) {
+        gui_helpers::recolor(console, &|c| self.quantize(c));
+    }
+}
+
+/// Simulates how colors look to players with color vision deficiencies, so you can check that
+/// your game is still readable. Filters use the Machado, Oliveira and Fernandes (2009) model at
+/// full severity.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum ColorBlindness {
+    /// No red cones (red-blind).
+    Protanopia,
+    /// No green cones (green-blind); the most common form.
+    Deuteranopia,
+    /// No blue cones (blue-blind).
+    Tritanopia,
+    /// No color vision at all.
+    Achromatopsia
+}
+
+impl ColorBlindness {
+    /// How `color` looks with this deficiency.
+    pub fn simulate(&self, color : RGB) -> RGB {
+        let matrix = match self {
+            ColorBlindness::Protanopia => [[0.152_286, 1.052_583, -0.204_868], [0.114_503, 0.786_281, 0.099_216], [-0.003_882, -0.048_116, 1.051_998]],
+            ColorBlindness::Deuteranopia => [[0.367_322, 0.860_646, -0.227_968], [0.280_085, 0.672_501, 0.047_413], [-0.011_820, 0.042_940, 0.968_881]],
+            ColorBlindness::Tritanopia => [[1.255_528, -0.076_749, -0.178_779], [-0.078_411, 0.930_809, 0.147_602], [0.004_733, 0.691_367, 0.303_900]],
+            ColorBlindness::Achromatopsia => [[0.212_672_9, 0.715_152_2, 0.072_175]; 3]
+        };
+
+        let linear = [srgb_to_linear(color.r), srgb_to_linear(color.g), srgb_to_linear(color.b)];
+        let channel = |row : [f32; 3]| linear_to_srgb(row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2]);
+        RGB::from_f32(channel(matrix[0]), channel(matrix[1]), channel(matrix[2]))
+    }
+
+    /// Applies the filter to every foreground and background color on a console. Call it after
+    /// drawing a frame.
+    pub fn apply(&self, console : &mut dyn Console) {
+        gui_helpers::recolor(console, &|c| self.simulate(c));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Palette, ColorBlindness};
+    use super::super::{Console, VirtualConsole, RGB};
+
+    #[test]
+    // Colors snap to the closest-looking palette entry, across a whole console.
+    fn test_quantize() {
+        let ega = Palette::ega();
+        assert_eq!(ega.colors().len(), 16);
+        assert!(ega.quantize(RGB::from_f32(0.9, 0.1, 0.1)) == RGB::from_u8(0xFF, 0x55, 0x55));
+        assert!(ega.quantize(RGB::from_f32(0.05, 0.05, 0.05)) == RGB::from_u8(0, 0, 0));
+        assert_eq!(Palette::new(&[]).nearest_index(RGB::new()), None);
+
+        let mut console = VirtualConsole::new(2, 1);
+        console.print_color(0, 0, RGB::from_f32(0.0, 0.6, 0.0), RGB::from_f32(0.98, 0.98, 0.98), "ab");
+        ega.quantize_console(&mut console);
+        let tile = console.get(1, 0).unwrap();
+        assert!(tile.fg == RGB::from_u8(0, 0xAA, 0));
+        assert!(tile.bg == RGB::from_u8(0xFF, 0xFF, 0xFF));
+        assert_eq!(tile.glyph, b'b');
+    }
+
+    #[test]
+    // Palettes round-trip through GIMP palette files, and colors can be found by name.
+    fn test_gpl() {
+        let palette = Palette::named(&[("Blood Red", RGB::from_u8(138, 3, 3)), ("bone", RGB::from_u8(227, 218, 201))]);
+        let mut file = Vec::new();
+        palette.write_gpl(&mut file, "Dungeon").unwrap();
+        let text = String::from_utf8(file.clone()).unwrap();
+        assert!(text.starts_with("GIMP Palette\nName: Dungeon\n"));
+        assert!(text.contains("138   3   3\tBlood Red"));
+
+        let loaded = Palette::read_gpl(&file[..]).unwrap();
+        assert_eq!(loaded.colors().len(), 2);
+        assert_eq!(loaded.name(0), Some("Blood Red"));
+        assert!(loaded.get("blood_red") == Some(RGB::from_u8(138, 3, 3)));
+        assert!(loaded.get("skull").is_none());
+        assert_eq!(Palette::ega().name(0), None);
+
+        assert!(Palette::read_gpl("Not a palette\n".as_bytes()).is_err());
+        assert!(Palette::read_gpl("GIMP Palette\n1 2\n".as_bytes()).is_err());
+    }
+
+    #[cfg(feature = "serialization")]
+    #[test]
+    // JSON palettes accept HTML codes or named color constants.
+    fn test_json() {
+        let json = r##"{ "colors": [ { "name": "blood", "color": "#8a0303" }, { "name": "moss", "color": "dark olive green" } ] }"##;
+        let palette = Palette::read_json(json.as_bytes()).unwrap();
+        assert!(palette.get("moss") == RGB::from_name("darkolivegreen"));
+
+        let mut file = Vec::new();
+        palette.write_json(&mut file).unwrap();
+        let loaded = Palette::read_json(&file[..]).unwrap();
+        assert!(loaded.get("blood") == Some(RGB::from_u8(0x8a, 0x03, 0x03)));
+        assert!(Palette::read_json(r#"{ "colors": [ { "name": "x", "color": "nope" } ] }"#.as_bytes()).is_err());
+    }
+
+    #[test]
+    // Red and green become hard to tell apart for deuteranopes; greys are unaffected.
+    fn test_color_blindness() {
+        let red = ColorBlindness::Deuteranopia.simulate(RGB::from_f32(1.0, 0.0, 0.0));
+        let green = ColorBlindness::Deuteranopia.simulate(RGB::from_f32(0.0, 1.0, 0.0));
+        assert!(red.perceptual_distance(green) < RGB::from_f32(1.0, 0.0, 0.0).perceptual_distance(RGB::from_f32(0.0, 1.0, 0.0)) / 2.0);
+
+        let grey = RGB::from_f32(0.5, 0.5, 0.5);
+        for filter in [ColorBlindness::Protanopia, ColorBlindness::Tritanopia, ColorBlindness::Achromatopsia].iter() {
+            assert!(filter.simulate(grey).perceptual_distance(grey) < 0.01);
+        }
+        let mono = ColorBlindness::Achromatopsia.simulate(RGB::from_f32(0.2, 0.8, 0.4));
+        assert!((mono.r - mono.g).abs() < 0.0001 && (mono.g - mono.b).abs() < 0.0001);
+    }
+}
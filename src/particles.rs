@@ -0,0 +1,328 @@
+use super::{Console, Gradient, RandomNumberGenerator, RGB};
+
+/// A single live particle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Particle {
+    pub x : f32,
+    pub y : f32,
+    /// Velocity, in cells per second.
+    pub vx : f32,
+    pub vy : f32,
+    pub glyph : u8,
+    pub age_ms : f32,
+    pub lifetime_ms : f32,
+    /// The emitter that spawned this particle, used to look up its colors.
+    pub emitter : usize
+}
+
+/// Describes how particles are spawned and how they look: where they appear, which way they go,
+/// how long they live and the colors they pass through over their life. Build one with the
+/// `with_` functions, then add it to a `ParticleSystem`.
+#[derive(Clone)]
+pub struct Emitter {
+    pub x : f32,
+    pub y : f32,
+    glyphs : Vec<u8>,
+    gradient : Gradient,
+    lifetime_ms : (f32, f32),
+    speed : (f32, f32),
+    direction : (f32, f32),
+    gravity : (f32, f32),
+    rate : f32,
+    duration_ms : Option<f32>,
+    fade : bool
+}
+
+impl Emitter {
+    /// An emitter at a position that sprays white `*` particles in every direction, five per
+    /// second, living for one second. Use the `with_` functions to change it.
+    pub fn new(x : f32, y : f32) -> Emitter {
+        Emitter{
+            x, y,
+            glyphs : vec![b'*'],
+            gradient : Gradient::new(&[RGB::from_f32(1.0, 1.0, 1.0)]),
+            lifetime_ms : (1000.0, 1000.0),
+            speed : (2.0, 2.0),
+            direction : (0.0, 360.0),
+            gravity : (0.0, 0.0),
+            rate : 5.0,
+            duration_ms : None,
+            fade : false
+        }
+    }
+
+    /// Particles use one of these glyphs, picked at random.
+    pub fn with_glyphs(mut self, glyphs : &[u8]) -> Emitter {
+        self.glyphs = glyphs.to_vec();
+        self
+    }
+
+    /// Particles change color along this gradient over their lifetime.
+    pub fn with_gradient(mut self, gradient : Gradient) -> Emitter {
+        self.gradient = gradient;
+        self
+    }
+
+    /// Each particle lives for a random time in this range, in milliseconds.
+    pub fn with_lifetime(mut self, min_ms : f32, max_ms : f32) -> Emitter {
+        self.lifetime_ms = (min_ms, max_ms);
+        self
+    }
+
+    /// Each particle starts with a random speed in this range, in cells per second.
+    pub fn with_speed(mut self, min : f32, max : f32) -> Emitter {
+        self.speed = (min, max);
+        self
+    }
+
+    /// Each particle heads off at a random angle in this range, in degrees clockwise from
+    /// north (so 0 is up and 90 is right).
+    pub fn with_direction(mut self, min_degrees : f32, max_degrees : f32) -> Emitter {
+        self.direction = (min_degrees, max_degrees);
+        self
+    }
+
+    /// A constant acceleration on every particle, in cells per second per second. Use a positive
+    /// `y` for falling sparks, or a negative one for rising smoke.
+    pub fn with_gravity(mut self, x : f32, y : f32) -> Emitter {
+        self.gravity = (x, y);
+        self
+    }
+
+    /// How many particles are spawned per second. Zero only spawns with `ParticleSystem::burst`.
+    pub fn with_rate(mut self, per_second : f32) -> Emitter {
+        self.rate = f32::max(0.0, per_second);
+        self
+    }
+
+    /// Stops spawning after this long; the emitter is removed once its particles have died.
+    pub fn with_duration(mut self, ms : f32) -> Emitter {
+        self.duration_ms = Some(ms);
+        self
+    }
+
+    /// If true, particles also darken towards black as they age.
+    pub fn with_fade(mut self, fade : bool) -> Emitter {
+        self.fade = fade;
+        self
+    }
+}
+
+/// An emitter in a system, with its spawning state.
+struct ActiveEmitter {
+    id : usize,
+    emitter : Emitter,
+    elapsed_ms : f32,
+    pending : f32
+}
+
+/// Spawns, moves and draws particles for spell effects, explosions, smoke and the like. Call
+/// `update` with `frame_time_ms` every tick, then `render` into a console - usually a
+/// `SparseConsole` layer above the map, so that only the particles are drawn over it.
+///
+/// All randomness comes from the system's own `RandomNumberGenerator`, so a system created with
+/// the same seed and fed the same frame times plays back identically (for replays).
+pub struct ParticleSystem {
+    emitters : Vec<ActiveEmitter>,
+    particles : Vec<Particle>,
+    rng : RandomNumberGenerator,
+    next_id : usize
+}
+
+/// A random number between `min` and `max`, which may be equal.
+fn between(rng : &mut RandomNumberGenerator, range : (f32, f32)) -> f32 {
+    if range.1 > range.0 { rng.range(range.0, range.1) } else { range.0 }
+}
+
+impl ParticleSystem {
+    /// Creates an empty particle system with a seeded random number generator.
+    pub fn new(seed : u64) -> ParticleSystem {
+        ParticleSystem::with_rng(RandomNumberGenerator::seeded(seed))
+    }
+
+    /// Creates an empty particle system that uses a random number generator you supply.
+    pub fn with_rng(rng : RandomNumberGenerator) -> ParticleSystem {
+        ParticleSystem{ emitters : Vec::new(), particles : Vec::new(), rng, next_id : 0 }
+    }
+
+    /// Adds an emitter, which starts spawning particles on the next update. Returns an id that
+    /// can be used to move or remove it.
+    pub fn add_emitter(&mut self, emitter : Emitter) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.emitters.push(ActiveEmitter{ id, emitter, elapsed_ms : 0.0, pending : 0.0 });
+        id
+    }
+
+    /// Stops an emitter. Particles it already spawned are removed too.
+    pub fn remove_emitter(&mut self, id : usize) {
+        self.emitters.retain(|e| e.id != id);
+        self.particles.retain(|p| p.emitter != id);
+    }
+
+    /// The emitter with an id, so it can be moved or changed.
+    pub fn emitter_mut(&mut self, id : usize) -> Option<&mut Emitter> {
+        self.emitters.iter_mut().find(|e| e.id == id).map(|e| &mut e.emitter)
+    }
+
+    /// Spawns `count` particles from an emitter at once, such as for an explosion.
+    pub fn burst(&mut self, id : usize, count : usize) {
+        if let Some(index) = self.emitters.iter().position(|e| e.id == id) {
+            for _ in 0 .. count {
+                self.spawn(index);
+            }
+        }
+    }
+
+    fn spawn(&mut self, index : usize) {
+        let active = &self.emitters[index];
+        let emitter = &active.emitter;
+        let glyph = if emitter.glyphs.is_empty() { b'*' } else { emitter.glyphs[self.rng.range(0, emitter.glyphs.len())] };
+        let lifetime_ms = between(&mut self.rng, emitter.lifetime_ms);
+        let speed = between(&mut self.rng, emitter.speed);
+        let angle = between(&mut self.rng, emitter.direction).to_radians();
+        self.particles.push(Particle{
+            x : emitter.x, y : emitter.y,
+            vx : angle.sin() * speed, vy : -angle.cos() * speed,
+            glyph, age_ms : 0.0, lifetime_ms, emitter : active.id
+        });
+    }
+
+    /// Moves time on by `frame_time_ms`: spawns new particles, moves and ages the live ones, and
+    /// removes the dead ones (and finished emitters with no particles left).
+    pub fn update(&mut self, frame_time_ms : f32) {
+        let seconds = frame_time_ms / 1000.0;
+
+        for particle in self.particles.iter_mut() {
+            let gravity = self.emitters.iter().find(|e| e.id == particle.emitter).map(|e| e.emitter.gravity).unwrap_or((0.0, 0.0));
+            particle.vx += gravity.0 * seconds;
+            particle.vy += gravity.1 * seconds;
+            particle.x += particle.vx * seconds;
+            particle.y += particle.vy * seconds;
+            particle.age_ms += frame_time_ms;
+        }
+        self.particles.retain(|p| p.age_ms < p.lifetime_ms);
+
+        for index in 0 .. self.emitters.len() {
+            let active = &mut self.emitters[index];
+            let remaining = match active.emitter.duration_ms {
+                Some(duration) => f32::max(0.0, f32::min(frame_time_ms, duration - active.elapsed_ms)),
+                None => frame_time_ms
+            };
+            active.elapsed_ms += frame_time_ms;
+            active.pending += active.emitter.rate * remaining / 1000.0;
+            let count = active.pending as usize;
+            active.pending -= count as f32;
+            for _ in 0 .. count {
+                self.spawn(index);
+            }
+        }
+
+        let particles = &self.particles;
+        self.emitters.retain(|e| match e.emitter.duration_ms {
+            Some(duration) => e.elapsed_ms < duration || particles.iter().any(|p| p.emitter == e.id),
+            None => true
+        });
+    }
+
+    /// The live particles.
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    /// The number of emitters still running.
+    pub fn emitter_count(&self) -> usize {
+        self.emitters.len()
+    }
+
+    /// Draws every particle into a console, keeping the background already there.
+    pub fn render(&self, console : &mut dyn Console) {
+        for particle in self.particles.iter() {
+            let emitter = match self.emitters.iter().find(|e| e.id == particle.emitter) {
+                Some(active) => &active.emitter,
+                None => continue
+            };
+            let (x, y) = (particle.x.round() as i32, particle.y.round() as i32);
+            if x < 0 || y < 0 || x >= console.width() as i32 || y >= console.height() as i32 { continue; }
+
+            let age = if particle.lifetime_ms > 0.0 { particle.age_ms / particle.lifetime_ms } else { 1.0 };
+            let mut fg = emitter.gradient.sample(age);
+            if emitter.fade {
+                fg = fg * (1.0 - age);
+            }
+            let bg = console.get(x, y).map(|t| t.bg).unwrap_or_else(RGB::new);
+            console.set(x, y, fg, bg, particle.glyph);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParticleSystem, Emitter};
+    use super::super::{Console, VirtualConsole, Gradient, RGB};
+
+    #[test]
+    // Particles spawn at the emitter's rate, move with their velocity and die of old age.
+    fn test_spawn_move_and_die() {
+        let mut system = ParticleSystem::new(1);
+        system.add_emitter(Emitter::new(5.0, 5.0).with_rate(10.0).with_speed(10.0, 10.0).with_direction(90.0, 90.0).with_lifetime(250.0, 250.0));
+
+        system.update(100.0);
+        assert_eq!(system.particles().len(), 1);
+        system.update(100.0);
+        assert_eq!(system.particles().len(), 2);
+        assert!((system.particles()[0].x - 6.0).abs() < 0.001);
+        assert!((system.particles()[0].y - 5.0).abs() < 0.001);
+        system.update(100.0);
+        system.update(100.0);
+        assert_eq!(system.particles().len(), 3);
+        assert!(system.particles().iter().all(|p| p.age_ms < 250.0));
+    }
+
+    #[test]
+    // Bursts spawn at once, and finished emitters go away when their particles have died.
+    fn test_burst_and_duration() {
+        let mut system = ParticleSystem::new(1);
+        let id = system.add_emitter(Emitter::new(0.0, 0.0).with_rate(0.0).with_duration(100.0).with_lifetime(200.0, 300.0));
+        system.burst(id, 20);
+        assert_eq!(system.particles().len(), 20);
+        system.update(150.0);
+        assert_eq!(system.emitter_count(), 1);
+        system.update(200.0);
+        assert!(system.particles().is_empty());
+        assert_eq!(system.emitter_count(), 0);
+    }
+
+    #[test]
+    // The same seed and frame times give exactly the same particles.
+    fn test_deterministic() {
+        let run = || {
+            let mut system = ParticleSystem::new(42);
+            system.add_emitter(Emitter::new(10.0, 10.0).with_rate(30.0).with_speed(1.0, 8.0).with_lifetime(100.0, 900.0).with_glyphs(&[b'*', b'+', b'.']));
+            for _ in 0 .. 20 {
+                system.update(33.0);
+            }
+            system.particles().to_vec()
+        };
+        let first = run();
+        assert!(!first.is_empty());
+        assert_eq!(first, run());
+    }
+
+    #[test]
+    // Particles take their color from the gradient, and keep the console's background.
+    fn test_render() {
+        let (red, blue) = (RGB::from_f32(1.0, 0.0, 0.0), RGB::from_f32(0.0, 0.0, 1.0));
+        let mut system = ParticleSystem::new(1);
+        let id = system.add_emitter(Emitter::new(2.0, 1.0).with_rate(0.0).with_speed(0.0, 0.0).with_gradient(Gradient::new(&[red, blue])));
+        system.burst(id, 1);
+
+        let mut console = VirtualConsole::new(4, 4);
+        console.set_bg(2, 1, RGB::from_f32(0.0, 1.0, 0.0));
+        system.render(&mut console);
+        let tile = console.get(2, 1).unwrap();
+        assert_eq!(tile.glyph, b'*');
+        assert!(tile.fg == red);
+        assert!(tile.bg == RGB::from_f32(0.0, 1.0, 0.0));
+    }
+}
@@ -1,5 +1,6 @@
 use gl::types::*;
-use super::gl;
+use super::{gl, Shader};
+use std::ffi::CString;
 use std::os::raw::c_void;
 use std::mem;
 use std::ptr;
@@ -36,4 +37,81 @@ pub fn setup_quad(gl : &gl::Gles2) -> u32 {
     }
 
     quadVAO
-}
\ No newline at end of file
+}
+
+/// Number of floats stored per console cell in an instance buffer: cell x/y, foreground r/g/b,
+/// background r/g/b and the glyph.
+pub const INSTANCE_FLOATS : usize = 9;
+
+/// Sets up a VAO for instanced console rendering. A single unit quad (and its indices) is
+/// uploaded once; each console cell is then an instance, with its position, colors and glyph
+/// read from the returned instance buffer.
+/// Returns (VAO, instance VBO).
+#[allow(non_snake_case)]
+pub fn setup_instanced_console(gl : &gl::Gles2) -> (u32, u32) {
+    let corners: [f32; 8] = [
+        1.0, 1.0,
+        1.0, 0.0,
+        0.0, 0.0,
+        0.0, 1.0
+    ];
+    let indices: [i32; 6] = [ 0, 1, 3, 1, 2, 3 ];
+
+    let (mut VAO, mut cornerVBO, mut EBO, mut instanceVBO) = (0, 0, 0, 0);
+    unsafe {
+        gl.GenVertexArrays(1, &mut VAO);
+        gl.GenBuffers(1, &mut cornerVBO);
+        gl.GenBuffers(1, &mut EBO);
+        gl.GenBuffers(1, &mut instanceVBO);
+
+        gl.BindVertexArray(VAO);
+
+        // Per-vertex quad corner
+        gl.BindBuffer(gl::ARRAY_BUFFER, cornerVBO);
+        gl.BufferData(gl::ARRAY_BUFFER,
+                    (corners.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
+                    &corners[0] as *const f32 as *const c_void,
+                    gl::STATIC_DRAW);
+        gl.VertexAttribPointer(0, 2, gl::FLOAT, gl::FALSE, 2 * mem::size_of::<GLfloat>() as GLsizei, ptr::null());
+        gl.EnableVertexAttribArray(0);
+
+        gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, EBO);
+        gl.BufferData(gl::ELEMENT_ARRAY_BUFFER,
+                    (indices.len() * mem::size_of::<GLint>()) as GLsizeiptr,
+                    &indices[0] as *const i32 as *const c_void,
+                    gl::STATIC_DRAW);
+
+        // Per-instance cell data
+        gl.BindBuffer(gl::ARRAY_BUFFER, instanceVBO);
+        let stride = (INSTANCE_FLOATS * mem::size_of::<GLfloat>()) as GLsizei;
+        // cell position attribute
+        gl.VertexAttribPointer(1, 2, gl::FLOAT, gl::FALSE, stride, ptr::null());
+        gl.EnableVertexAttribArray(1);
+        gl.VertexAttribDivisor(1, 1);
+        // color attribute
+        gl.VertexAttribPointer(2, 3, gl::FLOAT, gl::FALSE, stride, (2 * mem::size_of::<GLfloat>()) as *const c_void);
+        gl.EnableVertexAttribArray(2);
+        gl.VertexAttribDivisor(2, 1);
+        // bgcolor attribute
+        gl.VertexAttribPointer(3, 3, gl::FLOAT, gl::FALSE, stride, (5 * mem::size_of::<GLfloat>()) as *const c_void);
+        gl.EnableVertexAttribArray(3);
+        gl.VertexAttribDivisor(3, 1);
+        // glyph attribute
+        gl.VertexAttribPointer(4, 1, gl::FLOAT, gl::FALSE, stride, (8 * mem::size_of::<GLfloat>()) as *const c_void);
+        gl.EnableVertexAttribArray(4);
+        gl.VertexAttribDivisor(4, 1);
+    }
+
+    (VAO, instanceVBO)
+}
+
+/// Renders an instanced console VAO, with one instance per cell.
+pub fn draw_instanced_console(gl : &gl::Gles2, shader : &Shader, vao : u32, width : u32, height : u32, offset : (f32, f32), instances : usize) {
+    unsafe {
+        shader.useProgram(gl);
+        shader.setVec3(gl, &CString::new("consoleSize").unwrap(), width as f32, height as f32, 0.0);
+        shader.setVec3(gl, &CString::new("consoleOffset").unwrap(), offset.0, offset.1, 0.0);
+        gl.BindVertexArray(vao);
+        gl.DrawElementsInstanced(gl::TRIANGLES, 6, gl::UNSIGNED_INT, ptr::null(), instances as GLsizei);
+    }
+}
@@ -0,0 +1,295 @@
+//! Records gameplay, either as an asciinema v2 `.cast` file (cells are converted to ANSI
+//! escape codes, so it replays in a terminal or the asciinema web player) or as an
+//! animated GIF rendered from the console stack with the font bitmap.
+//!
+//! Start and stop recordings with `Rltk::start_recording` and `Rltk::stop_recording`.
+
+use super::{Tile, terminal::{AnsiScreen, ColorMode}};
+use image::{RgbImage, gif};
+use std::fmt::Write as FmtWrite;
+use std::io::{self, Write};
+use std::sync::mpsc::{channel, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// GIF frames are captured at most this often; encoding every frame would be far too slow.
+const GIF_FRAME_INTERVAL : Duration = Duration::from_millis(100);
+
+/// The file formats a recording can be saved as.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum RecordingFormat {
+    /// An asciinema v2 `.cast` file.
+    Asciicast,
+    /// An animated GIF.
+    Gif
+}
+
+/// Implemented by things that can record frames. A frame is the flattened console stack,
+/// in row-major order starting at the top-left.
+pub trait Recorder {
+    /// Records a frame.
+    fn record_frame(&mut self, frame : &[Tile]) -> io::Result<()>;
+
+    /// Writes anything still pending and closes the recording.
+    fn finish(&mut self) -> io::Result<()>;
+}
+
+/// Escapes a string for inclusion in a JSON string literal.
+fn json_escape(text : &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => { write!(result, "\\u{:04x}", c as u32).unwrap(); }
+            c => result.push(c)
+        }
+    }
+    result
+}
+
+/// Records frames as an asciinema v2 stream: a JSON header line, followed by one
+/// `[time, "o", data]` line per frame that changed.
+pub struct AsciicastRecorder<W : Write> {
+    out : W,
+    screen : AnsiScreen,
+    start : Instant,
+    first_frame : bool
+}
+
+impl<W : Write> AsciicastRecorder<W> {
+    /// Creates a recorder for a console stack of the given size (in characters), and writes
+    /// the header.
+    pub fn new(mut out : W, width : u32, height : u32) -> io::Result<AsciicastRecorder<W>> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        writeln!(out, "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}, \"env\": {{\"TERM\": \"xterm-256color\"}}}}",
+            width, height, timestamp)?;
+        Ok(AsciicastRecorder{
+            out,
+            screen : AnsiScreen::new(width, height, ColorMode::TrueColor),
+            start : Instant::now(),
+            first_frame : true
+        })
+    }
+}
+
+impl<W : Write> Recorder for AsciicastRecorder<W> {
+    fn record_frame(&mut self, frame : &[Tile]) -> io::Result<()> {
+        let mut output = self.screen.render(frame);
+        if output.is_empty() { return Ok(()); }
+        if self.first_frame {
+            // Hide the cursor and start from a clear screen
+            output.insert_str(0, "\x1b[?25l\x1b[2J");
+            self.first_frame = false;
+        }
+
+        let time = self.start.elapsed().as_micros() as f64 / 1_000_000.0;
+        writeln!(self.out, "[{:.6}, \"o\", \"{}\"]", time, json_escape(&output))
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+/// The thread that encodes GIF frames. Reducing a whole screen to a 256-color palette takes
+/// long enough to make the game visibly stutter, so it is done away from the game thread.
+struct GifWorker {
+    frames : Sender<(Vec<u8>, u16)>,
+    thread : JoinHandle<io::Result<()>>
+}
+
+/// Records frames as an animated GIF, drawing each cell with the glyph from a font bitmap.
+/// Frames are encoded on a separate thread.
+pub struct GifRecorder {
+    worker : Option<GifWorker>,
+    font : RgbImage,
+    tile_size : (u32, u32),
+    width : u32,
+    height : u32,
+    pending : Option<(Vec<u8>, Instant)>
+}
+
+impl GifRecorder {
+    /// Creates a recorder for a console stack of the given size (in characters), using a
+    /// font bitmap laid out as 16x16 glyphs of tile_size pixels. GIFs can be at most 65535
+    /// pixels across, so larger consoles are an error.
+    pub fn new<W : Write + Send + 'static>(out : W, font : RgbImage, tile_size : (u32, u32), width : u32, height : u32) -> io::Result<GifRecorder> {
+        let too_large = |chars : u32, pixels : u32| chars as u64 * pixels as u64 > u16::MAX as u64;
+        if too_large(width, tile_size.0) || too_large(height, tile_size.1) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "console is too large to record as a GIF"));
+        }
+
+        let (w, h) = ((width * tile_size.0) as u16, (height * tile_size.1) as u16);
+        let (frames, received) = channel::<(Vec<u8>, u16)>();
+        let thread = thread::spawn(move || {
+            let mut encoder = gif::Encoder::new(out);
+            for (mut pixels, delay) in received {
+                let mut frame = gif::Frame::from_rgba_speed(w, h, &mut pixels, 10);
+                frame.delay = delay;
+                encoder.encode(&frame).map_err(|e| io::Error::other(e.to_string()))?;
+            }
+            // Dropping the encoder writes the GIF trailer
+            Ok(())
+        });
+
+        Ok(GifRecorder{ worker : Some(GifWorker{ frames, thread }), font, tile_size, width, height, pending : None })
+    }
+
+    /// Renders a frame to RGBA pixels. As in the OpenGL renderer, any font pixel that isn't
+    /// (nearly) black is tinted with the foreground color; everything else is background.
+    pub fn render(&self, frame : &[Tile]) -> Vec<u8> {
+        let (tw, th) = self.tile_size;
+        let pixel_width = self.width * tw;
+        let mut pixels = vec![0u8; (pixel_width * self.height * th * 4) as usize];
+
+        for cy in 0 .. self.height {
+            for cx in 0 .. self.width {
+                let tile = frame[((cy * self.width) + cx) as usize];
+                let glyph_x = (tile.glyph as u32 % 16) * tw;
+                let glyph_y = (tile.glyph as u32 / 16) * th;
+                for py in 0 .. th {
+                    for px in 0 .. tw {
+                        let font_pixel = self.font.get_pixel(glyph_x + px, glyph_y + py);
+                        let (r, g, b) = (font_pixel[0] as f32 / 255.0, font_pixel[1] as f32 / 255.0, font_pixel[2] as f32 / 255.0);
+                        let color = if r > 0.1 || g > 0.1 || b > 0.1 {
+                            (r * tile.fg.r, g * tile.fg.g, b * tile.fg.b)
+                        } else {
+                            (tile.bg.r, tile.bg.g, tile.bg.b)
+                        };
+
+                        let idx = ((((cy * th) + py) * pixel_width + (cx * tw) + px) * 4) as usize;
+                        pixels[idx] = (color.0 * 255.0) as u8;
+                        pixels[idx + 1] = (color.1 * 255.0) as u8;
+                        pixels[idx + 2] = (color.2 * 255.0) as u8;
+                        pixels[idx + 3] = 255;
+                    }
+                }
+            }
+        }
+
+        pixels
+    }
+
+    /// Sends the pending frame to be encoded, now that we know how long it was on screen for.
+    fn flush_pending(&mut self, shown_for : Duration) -> io::Result<()> {
+        if let Some((pixels, _)) = self.pending.take() {
+            // GIF delays are in hundredths of a second
+            let delay = (shown_for.as_millis() as u64 / 10).clamp(1, u16::MAX as u64) as u16;
+            let sent = match self.worker.as_ref() {
+                Some(worker) => worker.frames.send((pixels, delay)).is_ok(),
+                None => true
+            };
+            // The worker only stops early if encoding failed; joining it reports why
+            if !sent { return self.stop_worker(); }
+        }
+        Ok(())
+    }
+
+    /// Waits for the worker to encode everything it has been sent and close the file.
+    fn stop_worker(&mut self) -> io::Result<()> {
+        match self.worker.take() {
+            Some(GifWorker{ frames, thread }) => {
+                drop(frames);
+                thread.join().unwrap_or_else(|_| Err(io::Error::other("the GIF encoder thread panicked")))
+            }
+            None => Ok(())
+        }
+    }
+}
+
+impl Recorder for GifRecorder {
+    fn record_frame(&mut self, frame : &[Tile]) -> io::Result<()> {
+        let shown_for = match self.pending {
+            Some((_, captured)) => captured.elapsed(),
+            None => GIF_FRAME_INTERVAL
+        };
+        if shown_for < GIF_FRAME_INTERVAL { return Ok(()); }
+
+        self.flush_pending(shown_for)?;
+        self.pending = Some((self.render(frame), Instant::now()));
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.flush_pending(GIF_FRAME_INTERVAL)?;
+        self.stop_worker()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{json_escape, AsciicastRecorder, GifRecorder, Recorder};
+    use super::super::{Tile, RGB};
+    use image::{RgbImage, Rgb};
+    use std::fs::File;
+
+    #[test]
+    // Escape codes and quotes must be escaped in the cast file.
+    fn test_json_escape() {
+        assert_eq!(json_escape("\x1b[1;1H\"a\\b\""), "\\u001b[1;1H\\\"a\\\\b\\\"");
+    }
+
+    #[test]
+    // A cast file starts with a header, followed by one event per changed frame.
+    fn test_asciicast() {
+        let tile = Tile{ glyph: 65, fg: RGB::from_f32(1.0, 1.0, 1.0), bg: RGB::from_f32(0.0, 0.0, 0.0) };
+        let frame = vec![tile; 2];
+
+        let mut out = Vec::new();
+        {
+            let mut recorder = AsciicastRecorder::new(&mut out, 2, 1).unwrap();
+            recorder.record_frame(&frame).unwrap();
+            recorder.record_frame(&frame).unwrap();
+            recorder.finish().unwrap();
+        }
+
+        let text = String::from_utf8(out).unwrap();
+        let lines : Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("{\"version\": 2, \"width\": 2, \"height\": 1"));
+        assert!(lines[1].starts_with("[0."));
+        assert!(lines[1].ends_with("AA\"]"));
+    }
+
+    #[test]
+    // Lit font pixels take the foreground color, dark ones the background.
+    fn test_gif_render() {
+        // A 16x16 "font" of 1x1 glyphs, where only glyph 1 is lit
+        let mut font = RgbImage::new(16, 16);
+        font.put_pixel(1, 0, Rgb([255, 255, 255]));
+
+        let recorder = GifRecorder::new(Vec::new(), font, (1, 1), 2, 1).unwrap();
+        let frame = vec![
+            Tile{ glyph: 1, fg: RGB::from_f32(1.0, 0.0, 0.0), bg: RGB::from_f32(0.0, 0.0, 1.0) },
+            Tile{ glyph: 0, fg: RGB::from_f32(1.0, 0.0, 0.0), bg: RGB::from_f32(0.0, 0.0, 1.0) }
+        ];
+        assert_eq!(recorder.render(&frame), vec![255, 0, 0, 255, 0, 0, 255, 255]);
+    }
+
+    #[test]
+    // Frames are encoded in the background, and finishing waits for the whole file.
+    fn test_gif_file() {
+        let path = std::env::temp_dir().join(format!("rltk-test-{}.gif", std::process::id()));
+        let mut recorder = GifRecorder::new(File::create(&path).unwrap(), RgbImage::new(16, 16), (1, 1), 2, 1).unwrap();
+        let frame = vec![Tile{ glyph: 0, fg: RGB::from_f32(1.0, 1.0, 1.0), bg: RGB::from_f32(0.0, 0.0, 1.0) }; 2];
+        recorder.record_frame(&frame).unwrap();
+        recorder.finish().unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(bytes.starts_with(b"GIF89a"));
+        assert_eq!(bytes.last(), Some(&0x3b));
+    }
+
+    #[test]
+    // A GIF can't be more than 65535 pixels across.
+    fn test_gif_too_large() {
+        assert!(GifRecorder::new(Vec::new(), RgbImage::new(16, 16), (8, 8), 8191, 1).is_ok());
+        assert!(GifRecorder::new(Vec::new(), RgbImage::new(16, 16), (8, 8), 8192, 1).is_err());
+        assert!(GifRecorder::new(Vec::new(), RgbImage::new(16, 16), (8, 16), 1, 4096).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use super::GameState;
 use std::time::{Instant};
-use super::{ font, Console, Shader, RGB, SimpleConsole, gl, VirtualKeyCode, rex::XpLayer, rex::XpFile, framebuffer::Framebuffer, quadrender };
+use super::{ font, Console, Tile, TileChange, Shader, RGB, SimpleConsole, TerminalConsole, gl, VirtualKeyCode, rex::XpLayer, rex::XpFile, framebuffer::Framebuffer, quadrender, TileEffects };
 use glutin::event::{Event, WindowEvent};
 use glutin::event_loop::{ControlFlow, EventLoop};
 use glutin::window::WindowBuilder;
@@ -8,6 +8,14 @@ use glutin::ContextBuilder;
 use glutin::dpi::LogicalSize;
 extern crate winit;
 use std::ffi::CString;
+use std::fs::File;
+use std::io;
+use super::recording::{Recorder, RecordingFormat, AsciicastRecorder, GifRecorder};
+use super::terminal;
+#[cfg(feature = "serialization")]
+use super::streaming::ConsoleStreamWriter;
+#[cfg(feature = "serialization")]
+use std::io::Write;
 
 /// A display console, used internally to provide console render support.
 /// Public in case you want to play with it, or access it directly.
@@ -17,6 +25,13 @@ pub struct DisplayConsole {
     pub font_index : usize
 }
 
+/// An in-progress recording, and the size (in characters) of the frames it captures.
+struct Recording {
+    recorder : Box<dyn Recorder>,
+    width : u32,
+    height : u32
+}
+
 /// A helper, to get around difficulties with moving the event loop
 /// and window context types.
 struct WrappedContext {
@@ -37,14 +52,24 @@ pub struct Rltk {
     pub frame_time_ms : f32,
     pub active_console : usize,
     pub key : Option<glutin::event::VirtualKeyCode>,
-    mouse_pos: (i32, i32),
+    pub(crate) mouse_pos: (i32, i32),
     pub left_click: bool,
+    /// True if the left mouse button was pressed this frame. Unlike `left_click`, which is set
+    /// by any button being pressed or released, this is set once per click, as widgets need.
+    pub left_pressed: bool,
+    pub mouse_wheel: i32,
+    pub chars: Vec<char>,
+    pub shift: bool,
+    pub effects: TileEffects,
     context_wrapper : Option<WrappedContext>,
-    quitting : bool,
+    pub(crate) quitting : bool,
     backing_buffer : Framebuffer,
     quad_vao : u32,
     post_scanlines : bool,
-    post_screenburn : bool
+    post_screenburn : bool,
+    recording : Option<Recording>,
+    #[cfg(feature = "serialization")]
+    stream : Option<ConsoleStreamWriter<Box<dyn Write>>>
 }
 
 #[allow(dead_code)]
@@ -87,12 +112,20 @@ impl Rltk {
             key: None,
             mouse_pos: (0,0),
             left_click: false,
+            left_pressed: false,
+            mouse_wheel: 0,
+            chars: Vec::new(),
+            shift: false,
+            effects: TileEffects::new(),
             context_wrapper: Some(WrappedContext{ el: el, wc: windowed_context }),
             quitting : false,
             backing_buffer : backing_fbo,
             quad_vao : quadVAO,
             post_scanlines : false,
-            post_screenburn : false
+            post_screenburn : false,
+            recording : None,
+            #[cfg(feature = "serialization")]
+            stream : None
         }
     }
 
@@ -114,6 +147,53 @@ impl Rltk {
         context
     }    
 
+    /// Initializes a context for the terminal backend: no window or OpenGL context is created,
+    /// and a single terminal console of the requested size is registered. Run it with
+    /// `rltk::terminal::main_loop`.
+    pub fn init_terminal(width_chars : u32, height_chars : u32) -> Rltk {
+        // Nothing is ever drawn through OpenGL, so leave every function unloaded
+        let gl = gl::Gl::load_with(|_| std::ptr::null());
+
+        let mut context = Rltk{
+            gl: gl,
+            width_pixels : width_chars,
+            height_pixels: height_chars,
+            fonts : Vec::new(),
+            consoles: Vec::new(),
+            shaders: Vec::new(),
+            fps: 0.0,
+            frame_time_ms: 0.0,
+            active_console : 0,
+            key: None,
+            mouse_pos: (0,0),
+            left_click: false,
+            left_pressed: false,
+            mouse_wheel: 0,
+            chars: Vec::new(),
+            shift: false,
+            effects: TileEffects::new(),
+            context_wrapper: None,
+            quitting : false,
+            backing_buffer : Framebuffer::headless(),
+            quad_vao : 0,
+            post_scanlines : false,
+            post_screenburn : false,
+            recording : None,
+            #[cfg(feature = "serialization")]
+            stream : None
+        };
+
+        // One "pixel" per cell, so mouse_pos maps directly onto terminal cells.
+        context.fonts.push(font::Font::new("terminal", width_chars, height_chars, (1, 1)));
+        context.register_console(TerminalConsole::init(width_chars, height_chars), 0);
+        context
+    }
+
+    /// The size of a terminal context, in characters.
+    pub(crate) fn terminal_size(&self) -> (u32, u32) {
+        (self.width_pixels, self.height_pixels)
+    }
+
     /// Registers a font, and returns its handle number. Also loads it into OpenGL.
     pub fn register_font(&mut self, mut font : font::Font) -> usize {
         font.setup_gl_texture(&self.gl);
@@ -180,6 +260,89 @@ impl Rltk {
         xp
     }
 
+    #[cfg(feature = "serialization")]
+    /// Streams every frame's console changes to a sink (a file, pipe or socket), for spectators
+    /// to replay with `streaming::ConsoleStreamReader`. Replaces any existing stream.
+    pub fn stream_to(&mut self, sink : Box<dyn Write>) {
+        self.stream = Some(ConsoleStreamWriter::new(sink));
+    }
+
+    /// Sends this frame's console changes to the spectator stream, if there is one. If the
+    /// write fails (for example, the viewer went away) the stream is closed.
+    pub(crate) fn publish_frame(&mut self) {
+        #[cfg(feature = "serialization")]
+        {
+            if let Some(stream) = self.stream.as_mut() {
+                let consoles : Vec<&dyn Console> = self.consoles.iter().map(|c| c.console.as_ref()).collect();
+                if stream.write_frame(&consoles).is_err() {
+                    self.stream = None;
+                }
+            }
+        }
+    }
+
+    /// Starts recording gameplay to a file, as an asciinema cast or an animated GIF. The
+    /// console stack is flattened to the size of the first console; GIFs are drawn with
+    /// its font. Any recording already in progress is finished first.
+    pub fn start_recording<S: ToString>(&mut self, filename : S, format : RecordingFormat) -> io::Result<()> {
+        self.stop_recording()?;
+        if self.consoles.is_empty() {
+            return Err(io::Error::other("no console has been registered to record"));
+        }
+
+        let base_layer = self.consoles[0].console.to_xp_layer();
+        let (width, height) = (base_layer.width as u32, base_layer.height as u32);
+        let file = io::BufWriter::new(File::create(filename.to_string())?);
+
+        let recorder : Box<dyn Recorder> = match format {
+            RecordingFormat::Asciicast => Box::new(AsciicastRecorder::new(file, width, height)?),
+            RecordingFormat::Gif => {
+                let font = &self.fonts[self.consoles[0].font_index];
+                let bitmap = image::open(std::path::Path::new(&font.bitmap_file))
+                    .map_err(|e| io::Error::other(e.to_string()))?;
+                Box::new(GifRecorder::new(file, bitmap.to_rgb(), font.tile_size, width, height)?)
+            }
+        };
+
+        self.recording = Some(Recording{ recorder, width, height });
+        Ok(())
+    }
+
+    /// Finishes the current recording, if there is one. This also happens automatically
+    /// when the main loop exits.
+    pub fn stop_recording(&mut self) -> io::Result<()> {
+        match self.recording.take() {
+            Some(mut recording) => recording.recorder.finish(),
+            None => Ok(())
+        }
+    }
+
+    /// Adds this frame to the recording, if there is one. If writing fails, the recording
+    /// is abandoned.
+    pub(crate) fn record_frame(&mut self) {
+        if let Some(mut recording) = self.recording.take() {
+            let frame = terminal::composite(self, recording.width, recording.height);
+            if recording.recorder.record_frame(&frame).is_ok() {
+                self.recording = Some(recording);
+            }
+        }
+    }
+
+    /// Advances the tile effects by this frame's time, and applies them to the consoles.
+    pub(crate) fn apply_effects(&mut self) {
+        self.effects.advance(self.frame_time_ms);
+        for (index, cons) in self.consoles.iter_mut().enumerate() {
+            self.effects.apply_to(index, cons.console.as_mut());
+        }
+    }
+
+    /// Puts back the cells that `apply_effects` changed, once the frame has been shown.
+    pub(crate) fn restore_effects(&mut self) {
+        for (index, cons) in self.consoles.iter_mut().enumerate() {
+            self.effects.restore(index, cons.console.as_mut());
+        }
+    }
+
     /// Enable scanlines post-processing effect.
     pub fn with_post_scanlines(&mut self, with_burn : bool) {
         self.post_scanlines = true;
@@ -195,12 +358,17 @@ impl Console for Rltk {
     // Implement pass-through to active console
 
     fn at(&self, x:i32, y:i32) -> usize { self.consoles[self.active_console].console.at(x,y) }
+    fn width(&self) -> u32 { self.consoles[self.active_console].console.width() }
+    fn height(&self) -> u32 { self.consoles[self.active_console].console.height() }
+    fn get(&self, x:i32, y:i32) -> Option<Tile> { self.consoles[self.active_console].console.get(x,y) }
     fn cls(&mut self) { self.consoles[self.active_console].console.cls(); }
     fn cls_bg(&mut self, background : RGB) { self.consoles[self.active_console].console.cls_bg(background); }
     fn print(&mut self, x:i32, y:i32, output:&str) { self.consoles[self.active_console].console.print(x, y, output); }
     fn print_color(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, output:&str) { self.consoles[self.active_console].console.print_color(x,y,fg,bg,output); }
     fn set(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, glyph:u8) { self.consoles[self.active_console].console.set(x,y,fg,bg,glyph); }
     fn set_bg(&mut self, x:i32, y:i32, bg:RGB) { self.consoles[self.active_console].console.set_bg(x,y,bg); }
+    fn fill_region(&mut self, x:i32, y:i32, width:i32, height:i32, fg:RGB, bg:RGB, glyph:u8) { self.consoles[self.active_console].console.fill_region(x,y,width,height,fg,bg,glyph); }
+    fn scroll(&mut self, dx:i32, dy:i32) { self.consoles[self.active_console].console.scroll(dx,dy); }
     fn draw_box(&mut self, x:i32, y:i32, width:i32, height:i32, fg: RGB, bg: RGB) { self.consoles[self.active_console].console.draw_box(x,y,width,height,fg,bg); }
     fn draw_box_double(&mut self, x:i32, y:i32, width:i32, height:i32, fg: RGB, bg: RGB) { self.consoles[self.active_console].console.draw_box_double(x,y,width,height,fg,bg); }
     fn draw_bar_horizontal(&mut self, x:i32, y:i32, width:i32, n:i32, max:i32, fg:RGB, bg: RGB) { self.consoles[self.active_console].console.draw_bar_horizontal(x,y,width,n,max,fg,bg); }
@@ -209,6 +377,8 @@ impl Console for Rltk {
     fn print_color_centered(&mut self, y:i32, fg:RGB, bg:RGB, text:&str) { self.consoles[self.active_console].console.print_color_centered(y, fg, bg, text); }
     fn to_xp_layer(&self) -> XpLayer { self.consoles[self.active_console].console.to_xp_layer() }
     fn set_offset(&mut self, x : f32, y : f32) { self.consoles[self.active_console].console.set_offset(x, y); }
+    fn end_frame(&mut self) { self.consoles[self.active_console].console.end_frame(); }
+    fn frame_diff(&self) -> Option<&[TileChange]> { self.consoles[self.active_console].console.frame_diff() }
 }
 
 #[allow(non_snake_case)]
@@ -238,21 +408,22 @@ pub fn main_loop(mut rltk : Rltk, mut gamestate: Box<GameState>) {
         match event {
             Event::NewEvents(_) => {
                 rltk.left_click = false;
+                rltk.left_pressed = false;
+                rltk.mouse_wheel = 0;
+                rltk.chars.clear();
                 rltk.key = None;
             }
-            Event::EventsCleared => {
+            Event::MainEventsCleared => {
                 tock(&mut rltk, &mut gamestate, &mut frames, &mut prev_seconds, &mut prev_ms, &now);
                 wc.swap_buffers().unwrap();
             }
-            Event::LoopDestroyed => return,
+            Event::LoopDestroyed => {
+                let _ = rltk.stop_recording();
+                return;
+            }
             Event::WindowEvent { ref event, .. } => match event {
                 WindowEvent::Resized(logical_size) => {
-                    let dpi_factor = wc.window().hidpi_factor();
-                    wc.resize(logical_size.to_physical(dpi_factor));
-                }
-                WindowEvent::RedrawRequested => {
-                    //tock(&mut rltk, &mut gamestate, &mut frames, &mut prev_seconds, &mut prev_ms, &now);
-                    wc.swap_buffers().unwrap();
+                    wc.resize(*logical_size);
                 }
                 WindowEvent::CloseRequested => {
                     *control_flow = ControlFlow::Exit
@@ -262,8 +433,26 @@ pub fn main_loop(mut rltk : Rltk, mut gamestate: Box<GameState>) {
                     rltk.mouse_pos = (pos.x as i32, pos.y as i32);
                 }
 
-                WindowEvent::MouseInput{ device_id : _, state: _, button: _, modifiers: _} => {
+                WindowEvent::MouseInput{ device_id : _, state, button, modifiers: _} => {
                     rltk.left_click = true;
+                    if *state == glutin::event::ElementState::Pressed && *button == glutin::event::MouseButton::Left {
+                        rltk.left_pressed = true;
+                    }
+                }
+
+                WindowEvent::MouseWheel{ delta, .. } => {
+                    // Count notches rather than pixels, so trackpads and wheels scroll alike
+                    rltk.mouse_wheel += match delta {
+                        glutin::event::MouseScrollDelta::LineDelta(_, y) => *y as i32,
+                        glutin::event::MouseScrollDelta::PixelDelta(pos) => pos.y.signum() as i32
+                    };
+                }
+
+                WindowEvent::ReceivedCharacter(c) => {
+                    // Control characters (backspace, enter, etc.) arrive as keys instead
+                    if !c.is_control() {
+                        rltk.chars.push(*c);
+                    }
                 }
 
                 WindowEvent::KeyboardInput {
@@ -276,6 +465,27 @@ pub fn main_loop(mut rltk : Rltk, mut gamestate: Box<GameState>) {
                 } => {
                     //println!("{:?}", event);
                     rltk.key = Some(*virtual_keycode);
+                    if *virtual_keycode == VirtualKeyCode::LShift || *virtual_keycode == VirtualKeyCode::RShift {
+                        rltk.shift = true;
+                    }
+                }
+
+                WindowEvent::KeyboardInput {
+                    input:
+                        glutin::event::KeyboardInput { virtual_keycode: Some(VirtualKeyCode::LShift),
+                        state : glutin::event::ElementState::Released,
+                        ..
+                    },
+                    ..
+                } | WindowEvent::KeyboardInput {
+                    input:
+                        glutin::event::KeyboardInput { virtual_keycode: Some(VirtualKeyCode::RShift),
+                        state : glutin::event::ElementState::Released,
+                        ..
+                    },
+                    ..
+                } => {
+                    rltk.shift = false;
                 }                
 
                 _ => (),
@@ -303,6 +513,14 @@ fn tock(rltk : &mut Rltk, gamestate: &mut Box<GameState>, frames: &mut i32, prev
     }
 
     gamestate.tick(rltk);
+    rltk.apply_effects();
+
+    // Record what changed this frame
+    for cons in rltk.consoles.iter_mut() {
+        cons.console.end_frame();
+    }
+    rltk.publish_frame();
+    rltk.record_frame();
 
     // Console structure - doesn't really have to be every frame...
     for cons in rltk.consoles.iter_mut() {
@@ -326,6 +544,7 @@ fn tock(rltk : &mut Rltk, gamestate: &mut Box<GameState>, frames: &mut i32, prev
         let shader = &rltk.shaders[cons.shader_index];
         cons.console.gl_draw(font, shader, &rltk.gl);
     } 
+    rltk.restore_effects();
 
     if rltk.post_scanlines {
         // Now we return to the primary screen
@@ -1,6 +1,6 @@
-use super::{Console, Tile, RGB, color, Font, Shader, rex::XpLayer, gui_helpers};
+use super::{Console, Tile, TileChange, RGB, color, Font, Shader, rex::XpLayer, gui_helpers, quadrender};
+use super::quadrender::INSTANCE_FLOATS;
 //use gl::types::*;
-use std::ptr;
 use std::mem;
 use std::os::raw::c_void;
 use super::gl;
@@ -16,19 +16,21 @@ pub struct SimpleConsole {
     // Private
     tiles: Vec<Tile>,
     is_dirty: bool,
-    vertex_counter : usize,
-    index_counter : usize,
+    dirty_rows: Vec<bool>,
+
+    // The previous frame, and what changed since then
+    previous_tiles: Vec<Tile>,
+    changed_rows: Vec<bool>,
+    changes: Vec<TileChange>,
 
     // To handle offset tiles for people who want thin walls between tiles
     offset_x : f32,
     offset_y : f32,
 
     // GL Stuff
-    vertex_buffer : Vec<f32>,
-    index_buffer : Vec<i32>,
-    VBO: u32,
+    instance_buffer : Vec<f32>,
     VAO: u32,
-    EBO: u32
+    instanceVBO: u32
 }
 
 #[allow(dead_code)]
@@ -43,149 +45,107 @@ impl SimpleConsole {
             tiles.push(Tile{glyph: 0, fg: RGB::named(color::WHITE), bg: RGB::named(color::BLACK)});
         }
 
-        let (VBO, VAO, EBO) = SimpleConsole::init_gl_for_console(gl);
-
-        let vertex_capacity : usize = (11 * width as usize * height as usize) * 4;
-        let index_capacity : usize = 6 * width as usize * height as usize;
+        let (VAO, instanceVBO) = quadrender::setup_instanced_console(gl);
 
         let mut new_console = SimpleConsole{
             width: width, 
             height: height, 
-            VBO: VBO,
             VAO: VAO,
-            EBO: EBO,
-            tiles: tiles,
+            instanceVBO: instanceVBO,
             is_dirty: true,
-            vertex_buffer : Vec::with_capacity(vertex_capacity),
-            index_buffer : Vec::with_capacity(index_capacity),
-            vertex_counter : 0,
-            index_counter: 0,
+            dirty_rows: vec![true; height as usize],
+            previous_tiles: tiles.clone(),
+            changed_rows: vec![false; height as usize],
+            changes: Vec::new(),
+            instance_buffer : vec![0.0; num_tiles * INSTANCE_FLOATS],
             offset_x : 0.0,
-            offset_y : 0.0
+            offset_y : 0.0,
+            tiles: tiles
         };
 
-        for _i in 0..vertex_capacity { new_console.vertex_buffer.push(0.0); }
-        for _i in 0..index_capacity { new_console.index_buffer.push(0); }
+        new_console.allocate_instance_buffer(gl);
 
         Box::new(new_console)
     }    
 
-    #[allow(non_snake_case)]
-    /// Sets up the OpenGL backing.
-    fn init_gl_for_console(gl : &gl::Gles2) -> (u32, u32, u32) {
-        let mut texture = 0;
-        let (mut VBO, mut VAO, mut EBO) = (0, 0, 0);
-        
+    /// Allocates the instance buffer on the GPU. Every cell is always present, so this only
+    /// happens once; after that, changed rows are uploaded in place.
+    fn allocate_instance_buffer(&mut self, gl : &gl::Gles2) {
         unsafe {
-            // Generate buffers and arrays, as well as attributes.
-            gl.GenVertexArrays(1, &mut VAO);
-            gl.GenBuffers(1, &mut VBO);
-            gl.GenBuffers(1, &mut EBO);
-
-            gl.BindVertexArray(VAO);
-
-            gl.BindBuffer(gl::ARRAY_BUFFER, VBO);
-
-            let stride = 11 * mem::size_of::<GLfloat>() as GLsizei;
-            // position attribute
-            gl.VertexAttribPointer(0, 3, gl::FLOAT, gl::FALSE, stride, ptr::null());
-            gl.EnableVertexAttribArray(0);
-            // color attribute
-            gl.VertexAttribPointer(1, 3, gl::FLOAT, gl::FALSE, stride, (3 * mem::size_of::<GLfloat>()) as *const c_void);
-            gl.EnableVertexAttribArray(1);
-             // bgcolor attribute
-            gl.VertexAttribPointer(2, 3, gl::FLOAT, gl::FALSE, stride, (6 * mem::size_of::<GLfloat>()) as *const c_void);
-            gl.EnableVertexAttribArray(2);
-            // texture coord attribute
-            gl.VertexAttribPointer(3, 2, gl::FLOAT, gl::FALSE, stride, (9 * mem::size_of::<GLfloat>()) as *const c_void);
-            gl.EnableVertexAttribArray(3);
-            
-            gl.GenTextures(1, &mut texture);
-            gl.BindTexture(gl::TEXTURE_2D, texture); // all upcoming GL_TEXTURE_2D operations now have effect on this texture object
-            // set the texture wrapping parameters
-            gl.TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::REPEAT as i32); // set texture wrapping to gl::REPEAT (default wrapping method)
-            gl.TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::REPEAT as i32);
-            // set texture filtering parameters
-            gl.TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
-            gl.TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
-        };
+            gl.BindBuffer(gl::ARRAY_BUFFER, self.instanceVBO);
+            gl.BufferData(gl::ARRAY_BUFFER,
+                        (self.instance_buffer.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
+                        &self.instance_buffer[0] as *const f32 as *const c_void,
+                        gl::DYNAMIC_DRAW);
+        }
+    }
 
-        (VBO, VAO, EBO)
+    /// Flags the row containing a tile index as needing a rebuild.
+    fn mark_dirty(&mut self, idx : usize) {
+        if idx < self.tiles.len() {
+            self.dirty_rows[idx / self.width as usize] = true;
+            self.changed_rows[idx / self.width as usize] = true;
+            self.is_dirty = true;
+        }
     }
 
-    /// Helper function to add all the elements required by the shader for a given point.
-    fn push_point(&mut self, x:f32, y:f32, fg:RGB, bg:RGB, ux:f32, uy:f32) {
-        self.vertex_buffer[self.vertex_counter] = x + self.offset_x;
-        self.vertex_buffer[self.vertex_counter+1] = y + self.offset_y;
-        self.vertex_buffer[self.vertex_counter+2] = 0.0;
-        self.vertex_buffer[self.vertex_counter+3] = fg.r;
-        self.vertex_buffer[self.vertex_counter+4] = fg.g;
-        self.vertex_buffer[self.vertex_counter+5] = fg.b;
-        self.vertex_buffer[self.vertex_counter+6] = bg.r;
-        self.vertex_buffer[self.vertex_counter+7] = bg.g;
-        self.vertex_buffer[self.vertex_counter+8] = bg.b;
-        self.vertex_buffer[self.vertex_counter+9] = ux;
-        self.vertex_buffer[self.vertex_counter+10] = uy;
-        self.vertex_counter += 11;
+    /// Flags every row as needing a rebuild.
+    fn mark_all_dirty(&mut self) {
+        for row in self.dirty_rows.iter_mut() {
+            *row = true;
+        }
+        for row in self.changed_rows.iter_mut() {
+            *row = true;
+        }
+        self.is_dirty = true;
     }
 
-    /// Rebuilds the OpenGL backing buffer.
-    fn rebuild_vertices(&mut self, gl : &gl::Gles2) {
-        self.vertex_counter = 0;
-        self.index_counter = 0;
-        let glyph_size_x : f32 = 1.0 / 16.0;
-        let glyph_size_y : f32 = 1.0 / 16.0;
+    /// Rebuilds the instance data for a single row of the console.
+    fn rebuild_row(&mut self, y : u32) {
+        for x in 0 .. self.width {
+            let idx = ((y * self.width) + x) as usize;
+            let tile = self.tiles[idx];
+            let base = idx * INSTANCE_FLOATS;
+            self.instance_buffer[base] = x as f32;
+            self.instance_buffer[base+1] = y as f32;
+            self.instance_buffer[base+2] = tile.fg.r;
+            self.instance_buffer[base+3] = tile.fg.g;
+            self.instance_buffer[base+4] = tile.fg.b;
+            self.instance_buffer[base+5] = tile.bg.r;
+            self.instance_buffer[base+6] = tile.bg.g;
+            self.instance_buffer[base+7] = tile.bg.b;
+            self.instance_buffer[base+8] = tile.glyph as f32;
+        }
+    }
 
-        let step_x : f32 = 2.0 / self.width as f32;
-        let step_y : f32 = 2.0 / self.height as f32;
+    /// Rebuilds the dirty rows of the OpenGL instance buffer, and uploads each contiguous
+    /// run of changed rows with a single BufferSubData call.
+    fn rebuild_instances(&mut self, gl : &gl::Gles2) {
+        let floats_per_row = self.width as usize * INSTANCE_FLOATS;
 
-        let mut index_count : i32 = 0;
-        let mut screen_y : f32 = -1.0;
-        for y in 0 .. self.height {
-            let mut screen_x : f32 = -1.0;
-            for x in 0 .. self.width {
-                let fg = self.tiles[((y * self.width) + x) as usize].fg;
-                let bg = self.tiles[((y * self.width) + x) as usize].bg;
-                let glyph = self.tiles[((y * self.width) + x) as usize].glyph;
-                let glyph_x = glyph % 16;
-                let glyph_y = 16 - (glyph / 16);
-
-                let glyph_left = glyph_x as f32 * glyph_size_x;
-                let glyph_right = (glyph_x+1) as f32 * glyph_size_x;
-                let glyph_top = glyph_y as f32 * glyph_size_y;
-                let glyph_bottom = (glyph_y-1) as f32 * glyph_size_y;
-
-                self.push_point(screen_x + step_x, screen_y + step_y, fg, bg, glyph_right, glyph_top);
-                self.push_point(screen_x + step_x, screen_y, fg, bg, glyph_right, glyph_bottom);
-                self.push_point(screen_x, screen_y, fg, bg, glyph_left, glyph_bottom);
-                self.push_point(screen_x, screen_y + step_y, fg, bg, glyph_left, glyph_top);
-
-                self.index_buffer[self.index_counter] = 0 + index_count;
-                self.index_buffer[self.index_counter+1] = 1 + index_count;
-                self.index_buffer[self.index_counter+2] = 3 + index_count;
-                self.index_buffer[self.index_counter+3] = 1 + index_count;
-                self.index_buffer[self.index_counter+4] = 2 + index_count;
-                self.index_buffer[self.index_counter+5] = 3 + index_count;
-                self.index_counter += 6;
-
-                index_count += 4;
-                screen_x += step_x;
+        let mut y : u32 = 0;
+        while y < self.height {
+            if !self.dirty_rows[y as usize] {
+                y += 1;
+                continue;
+            }
+
+            let first_row = y;
+            while y < self.height && self.dirty_rows[y as usize] {
+                self.rebuild_row(y);
+                self.dirty_rows[y as usize] = false;
+                y += 1;
+            }
+
+            let start = first_row as usize * floats_per_row;
+            let end = y as usize * floats_per_row;
+            unsafe {
+                gl.BindBuffer(gl::ARRAY_BUFFER, self.instanceVBO);
+                gl.BufferSubData(gl::ARRAY_BUFFER,
+                            (start * mem::size_of::<GLfloat>()) as GLintptr,
+                            ((end - start) * mem::size_of::<GLfloat>()) as GLsizeiptr,
+                            &self.instance_buffer[start] as *const f32 as *const c_void);
             }
-            screen_y += step_y;
-        }
-                
-        unsafe {
-            gl.BindBuffer(gl::ARRAY_BUFFER, self.VBO);
-            gl.BufferData(gl::ARRAY_BUFFER,
-                        (self.vertex_buffer.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
-                        &self.vertex_buffer[0] as *const f32 as *const c_void,
-                        gl::STATIC_DRAW);
-
-            gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.EBO);
-            gl.BufferData(gl::ELEMENT_ARRAY_BUFFER,
-                        (self.index_buffer.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
-                        &self.index_buffer[0] as *const i32 as *const c_void,
-                        gl::STATIC_DRAW);
         }
     }
 }
@@ -194,24 +154,18 @@ impl Console for SimpleConsole {
     /// Check if the console has changed, and if it has rebuild the backing buffer.
     fn rebuild_if_dirty(&mut self, gl : &gl::Gles2) {
          if self.is_dirty {
-            self.rebuild_vertices(gl);
+            self.rebuild_instances(gl);
             self.is_dirty = false;
         }
     }
 
     /// Sends the console to OpenGL.
     fn gl_draw(&mut self, font : &Font, shader : &Shader, gl : &gl::Gles2) {
-        unsafe {
-            // bind Texture
-            font.bind_texture(gl);
-
-            // render container
-            shader.useProgram(gl);
-            gl.BindVertexArray(self.VAO);
-            gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.EBO);
-            gl.BindBuffer(gl::ARRAY_BUFFER, self.VBO);
-            gl.DrawElements(gl::TRIANGLES, (self.width * self.height * 6) as i32, gl::UNSIGNED_INT, ptr::null());
-        }
+        // bind Texture
+        font.bind_texture(gl);
+
+        // render container
+        quadrender::draw_instanced_console(gl, shader, self.VAO, self.width, self.height, (self.offset_x, self.offset_y), self.tiles.len());
         self.is_dirty = false;
     }
 
@@ -220,9 +174,21 @@ impl Console for SimpleConsole {
         (((self.height-1 - y as u32) * self.width) + x as u32) as usize
     }
 
+    /// Returns the console width, in characters.
+    fn width(&self) -> u32 { self.width }
+
+    /// Returns the console height, in characters.
+    fn height(&self) -> u32 { self.height }
+
+    /// Reads back the cell at x/y.
+    fn get(&self, x:i32, y:i32) -> Option<Tile> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 { return None; }
+        Some(self.tiles[self.at(x, y)])
+    }
+
     /// Clears the screen.
     fn cls(&mut self) {
-        self.is_dirty = true;
+        self.mark_all_dirty();
         for tile in self.tiles.iter_mut() {
             tile.glyph = 32;
             tile.fg = RGB::named(color::WHITE);
@@ -232,7 +198,7 @@ impl Console for SimpleConsole {
 
     /// Clears the screen with a background color.
     fn cls_bg(&mut self, background : RGB) {
-        self.is_dirty = true;
+        self.mark_all_dirty();
         for tile in self.tiles.iter_mut() {
             tile.glyph = 32;
             tile.fg = RGB::named(color::WHITE);
@@ -242,13 +208,13 @@ impl Console for SimpleConsole {
 
     /// Prints a string at x/y.
     fn print(&mut self, x:i32, y:i32, output:&str) {
-        self.is_dirty = true;
         let mut idx = self.at(x, y);
 
         let bytes = super::string_to_cp437(output);
         for i in 0..bytes.len() {
             if idx < self.tiles.len() {
                 self.tiles[idx].glyph = bytes[i];
+                self.mark_dirty(idx);
                 idx += 1;
             }
         }
@@ -256,7 +222,6 @@ impl Console for SimpleConsole {
 
     /// Prints a string at x/y, with foreground and background colors.
     fn print_color(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, output:&str) {
-        self.is_dirty = true;
         let mut idx = self.at(x, y);
 
         let bytes = super::string_to_cp437(output);
@@ -265,6 +230,7 @@ impl Console for SimpleConsole {
                 self.tiles[idx].glyph = bytes[i];
                 self.tiles[idx].bg = bg;
                 self.tiles[idx].fg = fg;
+                self.mark_dirty(idx);
                 idx += 1;
             }
         }
@@ -276,12 +242,25 @@ impl Console for SimpleConsole {
         self.tiles[idx].glyph = glyph;
         self.tiles[idx].fg = fg;
         self.tiles[idx].bg = bg;
+        self.mark_dirty(idx);
     }
 
     /// Sets a single cell in the console's background
     fn set_bg(&mut self, x:i32, y:i32, bg:RGB) {
         let idx = self.at(x, y);
         self.tiles[idx].bg = bg;
+        self.mark_dirty(idx);
+    }
+
+    /// Fills a rectangle with a glyph and colors
+    fn fill_region(&mut self, sx:i32, sy:i32, width:i32, height:i32, fg:RGB, bg:RGB, glyph:u8) {
+        gui_helpers::fill_region(self, sx, sy, width, height, fg, bg, glyph);
+    }
+
+    /// Scrolls the console's contents by dx/dy cells
+    fn scroll(&mut self, dx:i32, dy:i32) {
+        let (width, height) = (self.width as i32, self.height as i32);
+        gui_helpers::scroll_region(self, 0, 0, width, height, dx, dy);
     }
 
     /// Draws a box, starting at x/y with the extents width/height using CP437 line characters
@@ -306,13 +285,11 @@ impl Console for SimpleConsole {
 
     /// Prints text, centered to the whole console width, at vertical location y.
     fn print_centered(&mut self, y:i32, text:&str) {
-        self.is_dirty = true;
         self.print((self.width as i32 / 2) - (text.to_string().len() as i32/2), y, text);
     }
 
     /// Prints text in color, centered to the whole console width, at vertical location y.
     fn print_color_centered(&mut self, y:i32, fg:RGB, bg:RGB, text:&str) {
-        self.is_dirty = true;
         self.print_color((self.width as i32 / 2) - (text.to_string().len() as i32/2), y, fg, bg, text);
     }
 
@@ -340,4 +317,31 @@ impl Console for SimpleConsole {
         self.offset_x = x * (2.0 / self.width as f32);
         self.offset_y = y * (2.0 / self.height as f32);
     }
-}
\ No newline at end of file
+
+    /// Compares the rows touched this frame with the previous frame, and stores the
+    /// changed cells for frame_diff.
+    fn end_frame(&mut self) {
+        self.changes.clear();
+        for row in 0 .. self.height as usize {
+            if !self.changed_rows[row] { continue; }
+            self.changed_rows[row] = false;
+
+            let start = row * self.width as usize;
+            for idx in start .. start + self.width as usize {
+                if self.tiles[idx] != self.previous_tiles[idx] {
+                    self.changes.push(TileChange{
+                        x: (idx - start) as i32,
+                        y: (self.height as usize - 1 - row) as i32,
+                        tile: self.tiles[idx]
+                    });
+                    self.previous_tiles[idx] = self.tiles[idx];
+                }
+            }
+        }
+    }
+
+    /// Returns the cells that changed during the last frame.
+    fn frame_diff(&self) -> Option<&[TileChange]> {
+        Some(&self.changes)
+    }
+}
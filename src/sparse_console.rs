@@ -1,6 +1,5 @@
-use super::{Console, RGB, Font, Shader, rex::XpLayer, rex::XpColor, gui_helpers};
+use super::{Console, Tile, TileChange, RGB, Font, Shader, rex::XpLayer, rex::XpColor, gui_helpers, quadrender};
 //use gl::types::*;
-use std::ptr;
 use std::mem;
 use std::os::raw::c_void;
 use gl::types::*;
@@ -24,6 +23,8 @@ pub struct SparseConsole {
 
     // Private
     tiles: Vec<SparseTile>,
+    // For each cell, the position of its tile in `tiles`, if one has been drawn
+    cell_tiles: Vec<Option<usize>>,
     is_dirty: bool,
 
     // To handle offset tiles for people who want thin walls between tiles
@@ -31,11 +32,9 @@ pub struct SparseConsole {
     offset_y : f32,
 
     // GL Stuff
-    vertex_buffer : Vec<f32>,
-    index_buffer : Vec<i32>,
-    VBO: u32,
+    instance_buffer : Vec<f32>,
     VAO: u32,
-    EBO: u32
+    instanceVBO: u32
 }
 
 #[allow(dead_code)]
@@ -45,18 +44,33 @@ impl SparseConsole {
     pub fn init(width:u32, height: u32, gl : &gl::Gles2) -> Box<SparseConsole> {
         // Console backing init
 
-        let (VBO, VAO, EBO) = SparseConsole::init_gl_for_console(gl);
+        let (VAO, instanceVBO) = quadrender::setup_instanced_console(gl);
+        SparseConsole::with_buffers(width, height, VAO, instanceVBO)
+    }
+
+    /// A console with no OpenGL buffers, for tests that only draw to it and read it back.
+    #[cfg(test)]
+    pub(crate) fn headless(width:u32, height: u32) -> Box<SparseConsole> {
+        SparseConsole::with_buffers(width, height, 0, 0)
+    }
 
+    /// How many cells have been drawn, for tests.
+    #[cfg(test)]
+    pub(crate) fn tile_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    #[allow(non_snake_case)]
+    fn with_buffers(width:u32, height: u32, VAO : u32, instanceVBO : u32) -> Box<SparseConsole> {
         let new_console = SparseConsole{
-            width: width, 
-            height: height, 
-            VBO: VBO,
-            VAO: VAO,
-            EBO: EBO,
+            width,
+            height,
+            VAO,
+            instanceVBO,
             tiles: Vec::new(),
+            cell_tiles: vec![None; (width * height) as usize],
             is_dirty: true,
-            vertex_buffer : Vec::new(),
-            index_buffer : Vec::new(),
+            instance_buffer : Vec::new(),
             offset_x : 0.0,
             offset_y : 0.0
         };
@@ -64,112 +78,61 @@ impl SparseConsole {
         Box::new(new_console)
     }
 
-    #[allow(non_snake_case)]
-    /// Initializes OpenGL for the sparse console.
-    fn init_gl_for_console(gl : &gl::Gles2) -> (u32, u32, u32) {
-        let (mut VBO, mut VAO, mut EBO) = (0, 0, 0);
-        
-        unsafe {
-            // Generate buffers and arrays, as well as attributes.
-            gl.GenVertexArrays(1, &mut VAO);
-            gl.GenBuffers(1, &mut VBO);
-            gl.GenBuffers(1, &mut EBO);
-
-            gl.BindVertexArray(VAO);
-
-            gl.BindBuffer(gl::ARRAY_BUFFER, VBO);
-
-            let stride = 11 * mem::size_of::<GLfloat>() as GLsizei;
-            // position attribute
-            gl.VertexAttribPointer(0, 3, gl::FLOAT, gl::FALSE, stride, ptr::null());
-            gl.EnableVertexAttribArray(0);
-            // color attribute
-            gl.VertexAttribPointer(1, 3, gl::FLOAT, gl::FALSE, stride, (3 * mem::size_of::<GLfloat>()) as *const c_void);
-            gl.EnableVertexAttribArray(1);
-             // bgcolor attribute
-            gl.VertexAttribPointer(2, 3, gl::FLOAT, gl::FALSE, stride, (6 * mem::size_of::<GLfloat>()) as *const c_void);
-            gl.EnableVertexAttribArray(2);
-            // texture coord attribute
-            gl.VertexAttribPointer(3, 2, gl::FLOAT, gl::FALSE, stride, (9 * mem::size_of::<GLfloat>()) as *const c_void);
-            gl.EnableVertexAttribArray(3);
+    /// Returns true if x/y lies on the console.
+    fn in_bounds(&self, x:i32, y:i32) -> bool {
+        x >= 0 && y >= 0 && x < self.width as i32 && y < self.height as i32
+    }
+
+    /// The tile drawn in cell `idx`, adding a blank one if the cell is empty.
+    fn tile_mut(&mut self, idx : usize) -> &mut SparseTile {
+        let i = match self.cell_tiles[idx] {
+            Some(i) => i,
+            None => {
+                self.cell_tiles[idx] = Some(self.tiles.len());
+                self.tiles.push(SparseTile{ idx, glyph: 32, fg: RGB::from_f32(1.0, 1.0, 1.0), bg: RGB::from_f32(0.0, 0.0, 0.0) });
+                self.tiles.len() - 1
+            }
         };
-        
-        (VBO, VAO, EBO)
-    }
-
-    /// Helper to push a point to the shader.
-    fn push_point(vertex_buffer : &mut Vec<f32>, x:f32, y:f32, fg:RGB, bg:RGB, ux:f32, uy:f32) {
-        vertex_buffer.push(x);
-        vertex_buffer.push(y);
-        vertex_buffer.push(0.0);
-        vertex_buffer.push(fg.r);
-        vertex_buffer.push(fg.g);
-        vertex_buffer.push(fg.b);
-        vertex_buffer.push(bg.r);
-        vertex_buffer.push(bg.g);
-        vertex_buffer.push(bg.b);
-        vertex_buffer.push(ux);
-        vertex_buffer.push(uy);
-    }
-
-    /// Helper to build vertices for the sparse grid.
-    fn rebuild_vertices(&mut self, gl : &gl::Gles2) {
-        if self.tiles.is_empty() { return; }
+        &mut self.tiles[i]
+    }
 
-        self.vertex_buffer.clear();
-        self.index_buffer.clear();
+    /// Rebuilds `cell_tiles` after the tile list has been replaced.
+    fn index_tiles(&mut self) {
+        for cell in self.cell_tiles.iter_mut() {
+            *cell = None;
+        }
+        for (i, t) in self.tiles.iter().enumerate() {
+            self.cell_tiles[t.idx] = Some(i);
+        }
+    }
 
-        let glyph_size_x : f32 = 1.0 / 16.0;
-        let glyph_size_y : f32 = 1.0 / 16.0;
+    /// Helper to build per-cell instance data for the sparse grid.
+    fn rebuild_instances(&mut self, gl : &gl::Gles2) {
+        if self.tiles.is_empty() { return; }
 
-        let step_x : f32 = 2.0 / self.width as f32;
-        let step_y : f32 = 2.0 / self.height as f32;
+        self.instance_buffer.clear();
 
-        let mut index_count : i32 = 0;
         for t in self.tiles.iter() {
             let x = t.idx % self.width as usize;
             let y = t.idx / self.width as usize;
 
-            let screen_x = ((step_x * x as f32) - 1.0) + self.offset_x;
-            let screen_y = ((step_y * y as f32) - 1.0) + self.offset_y;
-            let fg = t.fg;
-            let bg = t.bg;
-            let glyph = t.glyph;
-            let glyph_x = glyph % 16;
-            let glyph_y = 16 - (glyph / 16);
-
-            let glyph_left = glyph_x as f32 * glyph_size_x;
-            let glyph_right = (glyph_x+1) as f32 * glyph_size_x;
-            let glyph_top = glyph_y as f32 * glyph_size_y;
-            let glyph_bottom = (glyph_y-1) as f32 * glyph_size_y;
-
-            SparseConsole::push_point(&mut self.vertex_buffer, screen_x + step_x, screen_y + step_y, fg, bg, glyph_right, glyph_top);
-            SparseConsole::push_point(&mut self.vertex_buffer, screen_x + step_x, screen_y, fg, bg, glyph_right, glyph_bottom);
-            SparseConsole::push_point(&mut self.vertex_buffer, screen_x, screen_y, fg, bg, glyph_left, glyph_bottom);
-            SparseConsole::push_point(&mut self.vertex_buffer, screen_x, screen_y + step_y, fg, bg, glyph_left, glyph_top);
-
-            self.index_buffer.push(0 + index_count);
-            self.index_buffer.push(1 + index_count);
-            self.index_buffer.push(3 + index_count);
-            self.index_buffer.push(1 + index_count);
-            self.index_buffer.push(2 + index_count);
-            self.index_buffer.push(3 + index_count);
-
-            index_count += 4;
+            self.instance_buffer.push(x as f32);
+            self.instance_buffer.push(y as f32);
+            self.instance_buffer.push(t.fg.r);
+            self.instance_buffer.push(t.fg.g);
+            self.instance_buffer.push(t.fg.b);
+            self.instance_buffer.push(t.bg.r);
+            self.instance_buffer.push(t.bg.g);
+            self.instance_buffer.push(t.bg.b);
+            self.instance_buffer.push(t.glyph as f32);
         }
-                
+
         unsafe {
-            gl.BindBuffer(gl::ARRAY_BUFFER, self.VBO);
+            gl.BindBuffer(gl::ARRAY_BUFFER, self.instanceVBO);
             gl.BufferData(gl::ARRAY_BUFFER,
-                        (self.vertex_buffer.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
-                        &self.vertex_buffer[0] as *const f32 as *const c_void,
-                        gl::STATIC_DRAW);
-
-            gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.EBO);
-            gl.BufferData(gl::ELEMENT_ARRAY_BUFFER,
-                        (self.index_buffer.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
-                        &self.index_buffer[0] as *const i32 as *const c_void,
-                        gl::STATIC_DRAW);
+                        (self.instance_buffer.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
+                        &self.instance_buffer[0] as *const f32 as *const c_void,
+                        gl::DYNAMIC_DRAW);
         }
     }
 }
@@ -178,24 +141,18 @@ impl Console for SparseConsole {
     /// If the console has changed, rebuild the vertex buffer.
     fn rebuild_if_dirty(&mut self, gl : &gl::Gles2) {
          if self.is_dirty {
-            self.rebuild_vertices(gl);
+            self.rebuild_instances(gl);
             self.is_dirty = false;
         }
     }
 
     /// Draws the console to OpenGL.
     fn gl_draw(&mut self, font : &Font, shader : &Shader, gl : &gl::Gles2) {
-        unsafe {
-            // bind Texture
-            font.bind_texture(gl);
-
-            // render container
-            shader.useProgram(gl);
-            gl.BindVertexArray(self.VAO);
-            gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.EBO);
-            gl.BindBuffer(gl::ARRAY_BUFFER, self.VBO);
-            gl.DrawElements(gl::TRIANGLES, (self.tiles.len() * 6) as i32, gl::UNSIGNED_INT, ptr::null());
-        }
+        // bind Texture
+        font.bind_texture(gl);
+
+        // render container
+        quadrender::draw_instanced_console(gl, shader, self.VAO, self.width, self.height, (self.offset_x, self.offset_y), self.tiles.len());
         self.is_dirty = false;
     }
 
@@ -204,67 +161,81 @@ impl Console for SparseConsole {
         (((self.height-1 - y as u32) * self.width) + x as u32) as usize
     }
 
+    /// Returns the console width, in characters.
+    fn width(&self) -> u32 { self.width }
+
+    /// Returns the console height, in characters.
+    fn height(&self) -> u32 { self.height }
+
+    /// Reads back the most recent tile drawn at x/y, if there is one.
+    fn get(&self, x:i32, y:i32) -> Option<Tile> {
+        if !self.in_bounds(x, y) { return None; }
+        let idx = self.at(x, y);
+        self.cell_tiles[idx].map(|i| Tile{ glyph: self.tiles[i].glyph, fg: self.tiles[i].fg, bg: self.tiles[i].bg })
+    }
+
     /// Clear the screen.
     fn cls(&mut self) {
         self.is_dirty = true;
         self.tiles.clear();
+        self.index_tiles();
     }
 
     /// Clear the screen. Since we don't HAVE a background, it doesn't use it.
     fn cls_bg(&mut self, _background : RGB) {
         self.is_dirty = true;
         self.tiles.clear();
+        self.index_tiles();
     }
 
     /// Prints a string to an x/y position.
     fn print(&mut self, x:i32, y:i32, output:&str) {
-        self.is_dirty = true;
-        let mut idx = self.at(x, y);
-
-        let bytes = super::string_to_cp437(output);
-        for i in 0..bytes.len() {
-            self.tiles.push(SparseTile{
-                idx: idx,
-                glyph: bytes[i],
-                fg: RGB::from_f32(1.0, 1.0, 1.0),
-                bg: RGB::from_f32(0.0, 0.0, 0.0),
-            });
-            idx += 1;
-        }
+        self.print_color(x, y, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0), output);
     }
 
     /// Prints a string to an x/y position, with foreground and background colors.
     fn print_color(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, output:&str) {
-        self.is_dirty = true;
-        let mut idx = self.at(x, y);
-        
         let bytes = super::string_to_cp437(output);
-        for i in 0..bytes.len() {
-            self.tiles.push(SparseTile{
-                idx: idx,
-                glyph: bytes[i],
-                fg: fg,
-                bg: bg,
-            });
-            idx += 1;
+        for (i, glyph) in bytes.iter().enumerate() {
+            self.set(x + i as i32, y, fg, bg, *glyph);
         }
     }
 
-    /// Sets a single cell in the console
+    /// Sets a single cell in the console, replacing anything already drawn there
     fn set(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, glyph:u8) {
+        if !self.in_bounds(x, y) { return; }
+        self.is_dirty = true;
         let idx = self.at(x, y);
-        self.tiles.push(SparseTile{
-            idx: idx,
-            glyph: glyph,
-            fg: fg,
-            bg: bg,
-        });
+        *self.tile_mut(idx) = SparseTile{ idx, glyph, fg, bg };
     }
 
-    /// Sets a single cell in the console's background
+    /// Sets a single cell in the console's background. An empty cell becomes a space.
     fn set_bg(&mut self, x:i32, y:i32, bg:RGB) {
+        if !self.in_bounds(x, y) { return; }
+        self.is_dirty = true;
         let idx = self.at(x, y);
-        self.tiles[idx].bg = bg;
+        self.tile_mut(idx).bg = bg;
+    }
+
+    /// Fills a rectangle with a glyph and colors
+    fn fill_region(&mut self, sx:i32, sy:i32, width:i32, height:i32, fg:RGB, bg:RGB, glyph:u8) {
+        gui_helpers::fill_region(self, sx, sy, width, height, fg, bg, glyph);
+    }
+
+    /// Scrolls the console's contents by dx/dy cells. Tiles that move off the console are removed.
+    fn scroll(&mut self, dx:i32, dy:i32) {
+        self.is_dirty = true;
+        let (width, height) = (self.width as i32, self.height as i32);
+        let mut moved = Vec::with_capacity(self.tiles.len());
+        for t in self.tiles.drain(..) {
+            let x = (t.idx % width as usize) as i32 + dx;
+            let y = (height - 1 - (t.idx / width as usize) as i32) + dy;
+            if x >= 0 && y >= 0 && x < width && y < height {
+                moved.push(SparseTile{ idx: (((height - 1 - y) * width) + x) as usize, ..t });
+            }
+        }
+        self.tiles = moved;
+        self.index_tiles();
     }
 
     /// Draws a box, starting at x/y with the extents width/height using CP437 line characters
@@ -311,9 +282,10 @@ impl Console for SparseConsole {
             }
         }
 
+        // Tiles are stored bottom-up, while layers are top-down
         for c in self.tiles.iter() {
             let x = c.idx % self.width as usize;
-            let y = c.idx / self.width as usize;
+            let y = self.height as usize - 1 - c.idx / self.width as usize;
             let cell = layer.get_mut(x as usize, y as usize).unwrap();
             cell.ch = c.glyph as u32;
             cell.fg = c.fg.to_xp();
@@ -330,4 +302,77 @@ impl Console for SparseConsole {
         self.offset_x = x * (2.0 / self.width as f32);
         self.offset_y = y * (2.0 / self.height as f32);
     }
-}
\ No newline at end of file
+
+    /// Sparse consoles are rebuilt from scratch every frame, so there is nothing to compare.
+    fn end_frame(&mut self) {}
+
+    /// Sparse consoles don't track changes between frames.
+    fn frame_diff(&self) -> Option<&[TileChange]> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparseConsole;
+    use super::super::{Console, EffectKind, Palette, Rect, TileEffects, RGB};
+
+    #[test]
+    // Drawing over a cell replaces its tile, and any change marks the console for rebuilding.
+    fn test_set_replaces() {
+        let mut console = SparseConsole::headless(10, 5);
+        console.print(8, 1, "abc");
+        assert_eq!(console.tiles.len(), 2);
+        console.is_dirty = false;
+
+        console.set(9, 1, RGB::from_f32(1.0, 0.0, 0.0), RGB::from_f32(0.0, 0.0, 0.0), b'x');
+        console.set_bg(8, 1, RGB::from_f32(0.0, 0.0, 1.0));
+        console.set(-1, 1, RGB::from_f32(1.0, 0.0, 0.0), RGB::from_f32(0.0, 0.0, 0.0), b'x');
+        assert!(console.is_dirty);
+        assert_eq!(console.tiles.len(), 2);
+        assert_eq!(console.get(9, 1).unwrap().glyph, b'x');
+        assert_eq!(console.get(8, 1).unwrap().glyph, b'a');
+        assert!(console.get(8, 1).unwrap().bg == RGB::from_f32(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    // Effects on a sparse console change its tiles in place, and restoring puts them back.
+    fn test_effects() {
+        let mut console = SparseConsole::headless(10, 5);
+        console.print(0, 0, "ab");
+        let mut effects = TileEffects::new();
+        effects.add(0, Rect::with_size(0, 0, 3, 1), EffectKind::Blink{ period_ms : 100.0 }, None);
+        effects.advance(75.0);
+
+        for _frame in 0 .. 3 {
+            console.is_dirty = false;
+            effects.apply_to(0, console.as_mut());
+            assert!(console.is_dirty);
+            assert_eq!(console.tiles.len(), 2);
+            assert_eq!(console.get(0, 0).unwrap().glyph, 32);
+            effects.restore(0, console.as_mut());
+        }
+        assert_eq!(console.tiles.len(), 2);
+        assert_eq!(console.get(0, 0).unwrap().glyph, b'a');
+        assert!(console.get(2, 0).is_none());
+    }
+
+    #[test]
+    // Recoloring changes only the drawn tiles, in place; scrolling keeps cells findable.
+    fn test_recolor_and_scroll() {
+        let mut console = SparseConsole::headless(10, 5);
+        console.print_color(0, 0, RGB::from_f32(0.9, 0.1, 0.1), RGB::from_f32(0.1, 0.1, 0.1), "ab");
+        console.is_dirty = false;
+        Palette::new(&[RGB::from_f32(1.0, 0.0, 0.0), RGB::from_f32(0.0, 0.0, 0.0)]).quantize_console(console.as_mut());
+        assert!(console.is_dirty);
+        assert_eq!(console.tiles.len(), 2);
+        assert!(console.get(1, 0).unwrap().fg == RGB::from_f32(1.0, 0.0, 0.0));
+        assert!(console.get(1, 0).unwrap().bg == RGB::from_f32(0.0, 0.0, 0.0));
+
+        console.scroll(1, 2);
+        assert!(console.get(0, 0).is_none());
+        assert_eq!(console.get(2, 2).unwrap().glyph, b'b');
+        console.set(2, 2, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0), b'c');
+        assert_eq!(console.tiles.len(), 2);
+    }
+}
@@ -0,0 +1,206 @@
+//! Streams console contents to spectators. Each frame, the cells that changed on every
+//! console layer are serialized as one line of JSON and written to a sink (a file, a pipe
+//! or a socket). A viewer reads the lines back and applies them to its own consoles.
+//!
+//! Only available with the `serialization` feature.
+
+use super::{Console, Rltk, RGB, rex::XpColor};
+use std::io::{self, BufRead, Write};
+
+#[derive(PartialEq, Copy, Clone, serde::Serialize, serde::Deserialize, Debug)]
+/// A single cell, as sent over the wire.
+pub struct StreamCell {
+    pub x : i32,
+    pub y : i32,
+    pub glyph : u8,
+    pub fg : (u8, u8, u8),
+    pub bg : (u8, u8, u8)
+}
+
+#[derive(PartialEq, Clone, serde::Serialize, serde::Deserialize, Debug)]
+/// The changes to one console layer in a frame.
+pub struct StreamLayer {
+    /// Index of the console in the sender's console list.
+    pub index : usize,
+    /// If true, the layer should be cleared before the cells are applied.
+    pub keyframe : bool,
+    pub cells : Vec<StreamCell>
+}
+
+#[derive(PartialEq, Clone, serde::Serialize, serde::Deserialize, Debug)]
+/// Everything that changed in one frame.
+pub struct StreamFrame {
+    pub frame : u64,
+    pub layers : Vec<StreamLayer>
+}
+
+impl StreamCell {
+    fn new(x : i32, y : i32, glyph : u8, fg : XpColor, bg : XpColor) -> StreamCell {
+        StreamCell{ x, y, glyph, fg : (fg.r, fg.g, fg.b), bg : (bg.r, bg.g, bg.b) }
+    }
+}
+
+impl StreamLayer {
+    /// Applies the layer's cells to a console.
+    pub fn apply(&self, console : &mut dyn Console) {
+        if self.keyframe {
+            console.cls();
+        }
+        for cell in self.cells.iter() {
+            console.set(cell.x, cell.y,
+                RGB::from_u8(cell.fg.0, cell.fg.1, cell.fg.2),
+                RGB::from_u8(cell.bg.0, cell.bg.1, cell.bg.2),
+                cell.glyph);
+        }
+    }
+}
+
+impl StreamFrame {
+    /// Applies each layer to the console with the same index in an RLTK context. Layers the
+    /// viewer doesn't have are ignored.
+    pub fn apply(&self, rltk : &mut Rltk) {
+        for layer in self.layers.iter() {
+            if let Some(cons) = rltk.consoles.get_mut(layer.index) {
+                layer.apply(cons.console.as_mut());
+            }
+        }
+    }
+}
+
+/// Writes frames of console changes to a sink.
+pub struct ConsoleStreamWriter<W : Write> {
+    sink : W,
+    frame : u64,
+    sent_keyframe : bool
+}
+
+impl<W : Write> ConsoleStreamWriter<W> {
+    /// Creates a writer. The first frame written is always a keyframe.
+    pub fn new(sink : W) -> ConsoleStreamWriter<W> {
+        ConsoleStreamWriter{ sink, frame : 0, sent_keyframe : false }
+    }
+
+    /// Requests that the next frame is sent in full, for example when a new viewer joins.
+    pub fn request_keyframe(&mut self) {
+        self.sent_keyframe = false;
+    }
+
+    /// Serializes a frame. Consoles that track changes (see `Console::frame_diff`) send just
+    /// their changed cells after the first keyframe; others are sent in full every frame.
+    /// Call this after `Console::end_frame`.
+    pub fn write_frame(&mut self, consoles : &[&dyn Console]) -> io::Result<()> {
+        let mut layers = Vec::new();
+
+        for (index, console) in consoles.iter().enumerate() {
+            let diff = if self.sent_keyframe { console.frame_diff() } else { None };
+            match diff {
+                Some(changes) => {
+                    if changes.is_empty() { continue; }
+                    let cells = changes.iter()
+                        .map(|c| StreamCell::new(c.x, c.y, c.tile.glyph, c.tile.fg.to_xp(), c.tile.bg.to_xp()))
+                        .collect();
+                    layers.push(StreamLayer{ index, keyframe : false, cells });
+                }
+                None => {
+                    let layer = console.to_xp_layer();
+                    let mut cells = Vec::new();
+                    for y in 0 .. layer.height {
+                        for x in 0 .. layer.width {
+                            let cell = layer.get(x, y).unwrap();
+                            if !cell.bg.is_transparent() {
+                                cells.push(StreamCell::new(x as i32, y as i32, cell.ch as u8, cell.fg, cell.bg));
+                            }
+                        }
+                    }
+                    layers.push(StreamLayer{ index, keyframe : true, cells });
+                }
+            }
+        }
+
+        let frame = StreamFrame{ frame : self.frame, layers };
+        self.frame += 1;
+        self.sent_keyframe = true;
+
+        serde_json::to_writer(&mut self.sink, &frame)?;
+        self.sink.write_all(b"\n")?;
+        self.sink.flush()
+    }
+}
+
+/// Reads frames of console changes written by a `ConsoleStreamWriter`.
+pub struct ConsoleStreamReader<R : BufRead> {
+    source : R,
+    line : String
+}
+
+impl<R : BufRead> ConsoleStreamReader<R> {
+    pub fn new(source : R) -> ConsoleStreamReader<R> {
+        ConsoleStreamReader{ source, line : String::new() }
+    }
+
+    /// Reads the next frame, blocking until it arrives. Returns None at the end of the stream.
+    pub fn read_frame(&mut self) -> io::Result<Option<StreamFrame>> {
+        self.line.clear();
+        if self.source.read_line(&mut self.line)? == 0 {
+            return Ok(None);
+        }
+        let frame = serde_json::from_str(&self.line)?;
+        Ok(Some(frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConsoleStreamReader, ConsoleStreamWriter};
+    use super::super::{Console, SparseConsole, TerminalConsole, RGB};
+    use std::io::BufReader;
+    use std::net::{TcpListener, TcpStream};
+
+    #[test]
+    // Streams two frames over a loopback socket, and checks the viewer ends up identical.
+    fn test_loopback_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut writer = ConsoleStreamWriter::new(TcpStream::connect(listener.local_addr().unwrap()).unwrap());
+        let mut reader = ConsoleStreamReader::new(BufReader::new(listener.accept().unwrap().0));
+
+        let mut game = TerminalConsole::init(10, 5);
+        let mut viewer = TerminalConsole::init(10, 5);
+
+        game.print_color(1, 1, RGB::from_u8(255, 0, 0), RGB::from_u8(0, 0, 0), "Hello");
+        game.end_frame();
+        writer.write_frame(&[game.as_ref()]).unwrap();
+
+        let first = reader.read_frame().unwrap().unwrap();
+        assert_eq!(first.layers[0].keyframe, true);
+        first.layers[0].apply(viewer.as_mut());
+        assert!(game.tiles() == viewer.tiles());
+
+        game.set(9, 4, RGB::from_u8(0, 255, 0), RGB::from_u8(0, 0, 255), 64);
+        game.end_frame();
+        writer.write_frame(&[game.as_ref()]).unwrap();
+
+        let second = reader.read_frame().unwrap().unwrap();
+        assert_eq!(second.frame, 1);
+        assert_eq!(second.layers[0].keyframe, false);
+        assert_eq!(second.layers[0].cells.len(), 1);
+        second.layers[0].apply(viewer.as_mut());
+        assert!(game.tiles() == viewer.tiles());
+    }
+
+    #[test]
+    // Sparse layers are sent in full, and cells arrive where they were drawn.
+    fn test_sparse_layer() {
+        let mut sink = Vec::new();
+        let mut overlay = SparseConsole::headless(10, 5);
+        overlay.set(2, 1, RGB::from_u8(255, 255, 0), RGB::from_u8(0, 0, 0), 64);
+        ConsoleStreamWriter::new(&mut sink).write_frame(&[overlay.as_ref()]).unwrap();
+
+        let frame = ConsoleStreamReader::new(BufReader::new(&sink[..])).read_frame().unwrap().unwrap();
+        assert!(frame.layers[0].keyframe);
+        assert_eq!(frame.layers[0].cells.len(), 1);
+        let mut viewer = TerminalConsole::init(10, 5);
+        frame.layers[0].apply(viewer.as_mut());
+        assert_eq!(viewer.get(2, 1).unwrap().glyph, 64);
+        assert!(viewer.get(2, 1).unwrap().fg == RGB::from_u8(255, 255, 0));
+    }
+}
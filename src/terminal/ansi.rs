@@ -0,0 +1,145 @@
+use super::super::{Tile, RGB, codepage437::to_unicode};
+use std::fmt::Write;
+
+/// How colors are sent to the terminal.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum ColorMode {
+    /// 24-bit "truecolor" escapes. Supported by most modern terminals.
+    TrueColor,
+    /// The xterm 256 color palette, for older terminals.
+    Palette256
+}
+
+impl ColorMode {
+    /// Guesses the best color mode from the COLORTERM environment variable.
+    pub fn detect() -> ColorMode {
+        match std::env::var("COLORTERM") {
+            Ok(ref ct) if ct == "truecolor" || ct == "24bit" => ColorMode::TrueColor,
+            _ => ColorMode::Palette256
+        }
+    }
+}
+
+/// Converts a 0..1 color component to a 0..255 byte.
+fn to_byte(c : f32) -> u8 {
+    (c * 255.0).round() as u8
+}
+
+/// Finds the closest entry in the xterm 256 color palette (ignoring the 16 user-configurable
+/// colors at the start, which vary by terminal).
+pub fn rgb_to_palette256(col : RGB) -> u8 {
+    // The 6x6x6 color cube occupies entries 16 to 231
+    let cube_level = |c : f32| -> u8 { (c * 5.0).round() as u8 };
+    let (r, g, b) = (cube_level(col.r), cube_level(col.g), cube_level(col.b));
+    let cube_index = 16 + (36 * r) + (6 * g) + b;
+    let cube_value = |level : u8| -> f32 { if level == 0 { 0.0 } else { (55.0 + level as f32 * 40.0) / 255.0 } };
+    let cube_error = (cube_value(r) - col.r).powi(2) + (cube_value(g) - col.g).powi(2) + (cube_value(b) - col.b).powi(2);
+
+    // The greyscale ramp occupies entries 232 to 255
+    let grey = (col.r + col.g + col.b) / 3.0;
+    let grey_step = f32::min(23.0, f32::max(0.0, ((grey * 255.0) - 8.0) / 10.0)).round() as u8;
+    let grey_value = (8.0 + grey_step as f32 * 10.0) / 255.0;
+    let grey_error = (grey_value - col.r).powi(2) + (grey_value - col.g).powi(2) + (grey_value - col.b).powi(2);
+
+    if grey_error < cube_error { 232 + grey_step } else { cube_index }
+}
+
+/// Appends the escape sequence to select a foreground or background color.
+pub fn push_color(out : &mut String, col : RGB, background : bool, mode : ColorMode) {
+    let layer = if background { 48 } else { 38 };
+    match mode {
+        ColorMode::TrueColor => {
+            write!(out, "\x1b[{};2;{};{};{}m", layer, to_byte(col.r), to_byte(col.g), to_byte(col.b)).unwrap();
+        }
+        ColorMode::Palette256 => {
+            write!(out, "\x1b[{};5;{}m", layer, rgb_to_palette256(col)).unwrap();
+        }
+    }
+}
+
+/// Keeps track of what is currently displayed on the terminal, so that each frame only
+/// sends the cells that changed.
+pub struct AnsiScreen {
+    width : u32,
+    height : u32,
+    mode : ColorMode,
+    shown : Vec<Option<Tile>>
+}
+
+impl AnsiScreen {
+    /// Creates a screen tracker. Nothing is assumed to be displayed yet, so the first frame
+    /// is sent in full.
+    pub fn new(width : u32, height : u32, mode : ColorMode) -> AnsiScreen {
+        AnsiScreen{ width, height, mode, shown : vec![None; (width * height) as usize] }
+    }
+
+    /// Forgets what is displayed, forcing the next frame to be sent in full.
+    pub fn invalidate(&mut self) {
+        for cell in self.shown.iter_mut() {
+            *cell = None;
+        }
+    }
+
+    /// Builds the escape sequences needed to bring the terminal up to date with a frame of
+    /// cells (row-major, top-left first). Returns an empty string if nothing changed.
+    pub fn render(&mut self, frame : &[Tile]) -> String {
+        let mut out = String::new();
+        let mut cursor : Option<(u32, u32)> = None;
+        let mut fg : Option<RGB> = None;
+        let mut bg : Option<RGB> = None;
+
+        for y in 0 .. self.height {
+            for x in 0 .. self.width {
+                let idx = ((y * self.width) + x) as usize;
+                let tile = frame[idx];
+                if self.shown[idx] == Some(tile) { continue; }
+                self.shown[idx] = Some(tile);
+
+                if cursor != Some((x, y)) {
+                    write!(out, "\x1b[{};{}H", y + 1, x + 1).unwrap();
+                }
+                if fg != Some(tile.fg) {
+                    push_color(&mut out, tile.fg, false, self.mode);
+                    fg = Some(tile.fg);
+                }
+                if bg != Some(tile.bg) {
+                    push_color(&mut out, tile.bg, true, self.mode);
+                    bg = Some(tile.bg);
+                }
+                out.push(to_unicode(tile.glyph));
+                cursor = if x + 1 < self.width { Some((x + 1, y)) } else { None };
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnsiScreen, ColorMode, rgb_to_palette256};
+    use super::super::super::{Tile, RGB};
+
+    #[test]
+    // Pure colors should land on the corners of the color cube, and greys on the ramp.
+    fn test_palette256() {
+        assert_eq!(rgb_to_palette256(RGB::from_f32(1.0, 0.0, 0.0)), 196);
+        assert_eq!(rgb_to_palette256(RGB::from_f32(0.0, 0.0, 1.0)), 21);
+        assert_eq!(rgb_to_palette256(RGB::from_u8(128, 128, 128)), 244);
+    }
+
+    #[test]
+    // The first frame is sent in full, and an unchanged frame sends nothing.
+    fn test_only_changes_are_sent() {
+        let tile = Tile{ glyph: 65, fg: RGB::from_f32(1.0, 1.0, 1.0), bg: RGB::from_f32(0.0, 0.0, 0.0) };
+        let mut frame = vec![tile; 4];
+        let mut screen = AnsiScreen::new(2, 2, ColorMode::TrueColor);
+
+        let first = screen.render(&frame);
+        assert_eq!(first.matches('A').count(), 4);
+        assert_eq!(screen.render(&frame), "");
+
+        frame[3].glyph = 66;
+        assert_eq!(screen.render(&frame), "\x1b[2;2H\x1b[38;2;255;255;255m\x1b[48;2;0;0;0mB");
+    }
+}
@@ -0,0 +1,353 @@
+use super::super::VirtualKeyCode;
+
+/// An input event decoded from the terminal's input stream.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum TerminalEvent {
+    /// A key was pressed, possibly with Shift held.
+    Key{ key : VirtualKeyCode, shift : bool },
+    /// A character was typed. It comes just before the `Key` event for the same key press.
+    Char(char),
+    /// The mouse moved to a cell, optionally with the left button pressed.
+    Mouse{ x : i32, y : i32, left_click : bool },
+    /// The mouse wheel moved by a number of notches; positive is up.
+    Wheel(i32)
+}
+
+/// Maps a single printable or control byte to a key.
+fn byte_to_key(b : u8) -> Option<VirtualKeyCode> {
+    match b {
+        b'a' | b'A' => Some(VirtualKeyCode::A),
+        b'b' | b'B' => Some(VirtualKeyCode::B),
+        b'c' | b'C' => Some(VirtualKeyCode::C),
+        b'd' | b'D' => Some(VirtualKeyCode::D),
+        b'e' | b'E' => Some(VirtualKeyCode::E),
+        b'f' | b'F' => Some(VirtualKeyCode::F),
+        b'g' | b'G' => Some(VirtualKeyCode::G),
+        b'h' | b'H' => Some(VirtualKeyCode::H),
+        b'i' | b'I' => Some(VirtualKeyCode::I),
+        b'j' | b'J' => Some(VirtualKeyCode::J),
+        b'k' | b'K' => Some(VirtualKeyCode::K),
+        b'l' | b'L' => Some(VirtualKeyCode::L),
+        b'm' | b'M' => Some(VirtualKeyCode::M),
+        b'n' | b'N' => Some(VirtualKeyCode::N),
+        b'o' | b'O' => Some(VirtualKeyCode::O),
+        b'p' | b'P' => Some(VirtualKeyCode::P),
+        b'q' | b'Q' => Some(VirtualKeyCode::Q),
+        b'r' | b'R' => Some(VirtualKeyCode::R),
+        b's' | b'S' => Some(VirtualKeyCode::S),
+        b't' | b'T' => Some(VirtualKeyCode::T),
+        b'u' | b'U' => Some(VirtualKeyCode::U),
+        b'v' | b'V' => Some(VirtualKeyCode::V),
+        b'w' | b'W' => Some(VirtualKeyCode::W),
+        b'x' | b'X' => Some(VirtualKeyCode::X),
+        b'y' | b'Y' => Some(VirtualKeyCode::Y),
+        b'z' | b'Z' => Some(VirtualKeyCode::Z),
+        b'0' => Some(VirtualKeyCode::Key0),
+        b'1' => Some(VirtualKeyCode::Key1),
+        b'2' => Some(VirtualKeyCode::Key2),
+        b'3' => Some(VirtualKeyCode::Key3),
+        b'4' => Some(VirtualKeyCode::Key4),
+        b'5' => Some(VirtualKeyCode::Key5),
+        b'6' => Some(VirtualKeyCode::Key6),
+        b'7' => Some(VirtualKeyCode::Key7),
+        b'8' => Some(VirtualKeyCode::Key8),
+        b'9' => Some(VirtualKeyCode::Key9),
+        b' ' => Some(VirtualKeyCode::Space),
+        b'\r' | b'\n' => Some(VirtualKeyCode::Return),
+        b'\t' => Some(VirtualKeyCode::Tab),
+        0x08 | 0x7f => Some(VirtualKeyCode::Back),
+        b'\'' => Some(VirtualKeyCode::Apostrophe),
+        b'\\' => Some(VirtualKeyCode::Backslash),
+        b',' => Some(VirtualKeyCode::Comma),
+        b'=' => Some(VirtualKeyCode::Equals),
+        b'`' => Some(VirtualKeyCode::Grave),
+        b'[' => Some(VirtualKeyCode::LBracket),
+        b']' => Some(VirtualKeyCode::RBracket),
+        b'-' => Some(VirtualKeyCode::Minus),
+        b'.' => Some(VirtualKeyCode::Period),
+        b';' => Some(VirtualKeyCode::Semicolon),
+        b'/' => Some(VirtualKeyCode::Slash),
+        _ => None
+    }
+}
+
+/// Reads the modifier parameter of a CSI sequence (the second, as in ESC [ 1 ; 2 D), and
+/// returns true if it includes Shift.
+fn csi_shift(params : &[u8]) -> bool {
+    let modifier = params.split(|b| *b == b';').nth(1)
+        .and_then(|m| std::str::from_utf8(m).ok())
+        .and_then(|m| m.parse::<u32>().ok());
+    match modifier {
+        // The parameter is 1 plus a bitmask, in which Shift is 1
+        Some(m) if m > 1 => (m - 1) & 1 != 0,
+        _ => false
+    }
+}
+
+/// Maps the final byte of a CSI sequence (ESC [ ... final) to a key.
+fn csi_to_key(params : &[u8], last : u8) -> Option<VirtualKeyCode> {
+    match last {
+        b'Z' => Some(VirtualKeyCode::Tab),
+        b'A' => Some(VirtualKeyCode::Up),
+        b'B' => Some(VirtualKeyCode::Down),
+        b'C' => Some(VirtualKeyCode::Right),
+        b'D' => Some(VirtualKeyCode::Left),
+        b'H' => Some(VirtualKeyCode::Home),
+        b'F' => Some(VirtualKeyCode::End),
+        b'P' => Some(VirtualKeyCode::F1),
+        b'Q' => Some(VirtualKeyCode::F2),
+        b'R' => Some(VirtualKeyCode::F3),
+        b'S' => Some(VirtualKeyCode::F4),
+        b'~' => {
+            // Only the first parameter matters; the second (if any) carries modifiers
+            let code = params.split(|b| *b == b';').next().unwrap_or(&[]);
+            match code {
+                b"1" | b"7" => Some(VirtualKeyCode::Home),
+                b"2" => Some(VirtualKeyCode::Insert),
+                b"3" => Some(VirtualKeyCode::Delete),
+                b"4" | b"8" => Some(VirtualKeyCode::End),
+                b"5" => Some(VirtualKeyCode::PageUp),
+                b"6" => Some(VirtualKeyCode::PageDown),
+                b"11" => Some(VirtualKeyCode::F1),
+                b"12" => Some(VirtualKeyCode::F2),
+                b"13" => Some(VirtualKeyCode::F3),
+                b"14" => Some(VirtualKeyCode::F4),
+                b"15" => Some(VirtualKeyCode::F5),
+                b"17" => Some(VirtualKeyCode::F6),
+                b"18" => Some(VirtualKeyCode::F7),
+                b"19" => Some(VirtualKeyCode::F8),
+                b"20" => Some(VirtualKeyCode::F9),
+                b"21" => Some(VirtualKeyCode::F10),
+                b"23" => Some(VirtualKeyCode::F11),
+                b"24" => Some(VirtualKeyCode::F12),
+                _ => None
+            }
+        }
+        _ => None
+    }
+}
+
+/// Decodes an SGR (1006) mouse report: ESC [ < button ; x ; y (M|m)
+fn decode_sgr_mouse(params : &[u8], last : u8) -> Option<TerminalEvent> {
+    let text = std::str::from_utf8(&params[1..]).ok()?;
+    let mut parts = text.split(';').map(|p| p.parse::<i32>());
+    let button = parts.next()?.ok()?;
+    let x = parts.next()?.ok()?;
+    let y = parts.next()?.ok()?;
+
+    // Bit 6 flags the wheel, with button 0 for up and 1 for down
+    if button & 64 != 0 && button & 32 == 0 {
+        return Some(TerminalEvent::Wheel(if button & 1 == 0 { 1 } else { -1 }));
+    }
+
+    // Bit 5 flags motion; the low two bits give the button (0 is left)
+    let is_motion = button & 32 != 0;
+    let left_click = last == b'M' && !is_motion && button & 3 == 0 && button & 64 == 0;
+    Some(TerminalEvent::Mouse{ x : x - 1, y : y - 1, left_click })
+}
+
+/// Decodes a chunk of raw terminal input into key, character and mouse events, in the order
+/// they were typed. Unrecognized sequences are skipped.
+pub fn parse_input(bytes : &[u8]) -> Vec<TerminalEvent> {
+    let mut result = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != 0x1b {
+            let width = utf8_width(bytes[i]);
+            if width > 1 {
+                if let Some(c) = bytes.get(i .. i + width).and_then(|b| std::str::from_utf8(b).ok()).and_then(|t| t.chars().next()) {
+                    result.push(TerminalEvent::Char(c));
+                }
+                i += width;
+                continue;
+            }
+            if bytes[i] >= 0x20 && bytes[i] != 0x7f {
+                result.push(TerminalEvent::Char(bytes[i] as char));
+            }
+            if let Some(key) = byte_to_key(bytes[i]) {
+                result.push(TerminalEvent::Key{ key, shift : bytes[i].is_ascii_uppercase() });
+            }
+            i += 1;
+            continue;
+        }
+
+        match bytes.get(i + 1) {
+            Some(b'[') => {
+                // CSI: parameters run until a byte in the range @..~
+                let start = i + 2;
+                let mut end = start;
+                while end < bytes.len() && !(0x40 ..= 0x7e).contains(&bytes[end]) {
+                    end += 1;
+                }
+                if end >= bytes.len() { break; }
+
+                let params = &bytes[start .. end];
+                let last = bytes[end];
+                if params.first() == Some(&b'<') && (last == b'M' || last == b'm') {
+                    if let Some(event) = decode_sgr_mouse(params, last) {
+                        result.push(event);
+                    }
+                } else if let Some(key) = csi_to_key(params, last) {
+                    result.push(TerminalEvent::Key{ key, shift : last == b'Z' || csi_shift(params) });
+                }
+                i = end + 1;
+            }
+            Some(b'O') if i + 2 < bytes.len() => {
+                // SS3: used for F1-F4 and application-mode cursor keys
+                if let Some(key) = csi_to_key(&[], bytes[i + 2]) {
+                    result.push(TerminalEvent::Key{ key, shift : false });
+                }
+                i += 3;
+            }
+            _ => {
+                // A lone escape is the Escape key
+                result.push(TerminalEvent::Key{ key : VirtualKeyCode::Escape, shift : false });
+                i += 1;
+            }
+        }
+    }
+
+    result
+}
+
+/// The number of bytes in a UTF-8 character, from its first byte.
+fn utf8_width(first : u8) -> usize {
+    match first {
+        0xc0 ..= 0xdf => 2,
+        0xe0 ..= 0xef => 3,
+        0xf0 ..= 0xf7 => 4,
+        _ => 1
+    }
+}
+
+/// Finds where a chunk of raw terminal input stops being complete: the start of an escape
+/// sequence or UTF-8 character that was cut off at the end of a read. Keep the bytes from there
+/// on and put them in front of the next read. A lone escape at the very end is taken to be the
+/// Escape key, since that's how terminals send it.
+pub fn complete_len(bytes : &[u8]) -> usize {
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == 0x1b {
+            match bytes.get(i + 1) {
+                Some(b'[') => {
+                    let mut end = i + 2;
+                    while end < bytes.len() && !(0x40 ..= 0x7e).contains(&bytes[end]) {
+                        end += 1;
+                    }
+                    if end >= bytes.len() { return i; }
+                    i = end + 1;
+                }
+                Some(b'O') if i + 2 >= bytes.len() => return i,
+                Some(b'O') => i += 3,
+                _ => i += 1
+            }
+            continue;
+        }
+
+        let width = utf8_width(bytes[i]);
+        if i + width > bytes.len() { return i; }
+        i += width;
+    }
+    bytes.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, complete_len, TerminalEvent};
+    use super::super::super::VirtualKeyCode;
+
+    fn key(key : VirtualKeyCode) -> TerminalEvent { TerminalEvent::Key{ key, shift : false } }
+
+    fn keys(bytes : &[u8]) -> Vec<TerminalEvent> {
+        parse_input(bytes).into_iter().filter(|e| !matches!(e, TerminalEvent::Char(_))).collect()
+    }
+
+    #[test]
+    // Plain characters map to the same keys as the windowed backend.
+    fn test_plain_keys() {
+        assert_eq!(keys(b"aZ5 \r"), vec![
+            key(VirtualKeyCode::A),
+            TerminalEvent::Key{ key : VirtualKeyCode::Z, shift : true },
+            key(VirtualKeyCode::Key5),
+            key(VirtualKeyCode::Space),
+            key(VirtualKeyCode::Return)
+        ]);
+    }
+
+    #[test]
+    // Escape sequences for cursor, navigation and function keys.
+    fn test_escape_sequences() {
+        assert_eq!(parse_input(b"\x1b[A\x1b[D\x1b[5~\x1bOP\x1b[15~\x1b"), vec![
+            key(VirtualKeyCode::Up),
+            key(VirtualKeyCode::Left),
+            key(VirtualKeyCode::PageUp),
+            key(VirtualKeyCode::F1),
+            key(VirtualKeyCode::F5),
+            key(VirtualKeyCode::Escape)
+        ]);
+    }
+
+    #[test]
+    // The modifier parameter of a sequence says whether Shift was held.
+    fn test_shift() {
+        assert_eq!(parse_input(b"\x1b[1;2D\x1b[1;5C\x1b[1;6H\x1b[3;2~\x1b[Z"), vec![
+            TerminalEvent::Key{ key : VirtualKeyCode::Left, shift : true },
+            key(VirtualKeyCode::Right),
+            TerminalEvent::Key{ key : VirtualKeyCode::Home, shift : true },
+            TerminalEvent::Key{ key : VirtualKeyCode::Delete, shift : true },
+            TerminalEvent::Key{ key : VirtualKeyCode::Tab, shift : true }
+        ]);
+    }
+
+    #[test]
+    // SGR mouse reports are converted to zero-based cell coordinates.
+    fn test_mouse() {
+        assert_eq!(parse_input(b"\x1b[<0;10;5M\x1b[<35;3;4M"), vec![
+            TerminalEvent::Mouse{ x: 9, y: 4, left_click: true },
+            TerminalEvent::Mouse{ x: 2, y: 3, left_click: false }
+        ]);
+    }
+
+    #[test]
+    // Text and keys come out in the order they were typed, with each character just before
+    // its key; escape sequences and control characters aren't text.
+    fn test_chars() {
+        assert_eq!(parse_input("Hé\x1b[D\x7fo".as_bytes()), vec![
+            TerminalEvent::Char('H'),
+            TerminalEvent::Key{ key : VirtualKeyCode::H, shift : true },
+            TerminalEvent::Char('é'),
+            key(VirtualKeyCode::Left),
+            key(VirtualKeyCode::Back),
+            TerminalEvent::Char('o'),
+            key(VirtualKeyCode::O)
+        ]);
+    }
+
+    #[test]
+    // Wheel buttons 64 and 65 scroll up and down.
+    fn test_wheel() {
+        assert_eq!(parse_input(b"\x1b[<64;10;5M\x1b[<65;10;5M"), vec![
+            TerminalEvent::Wheel(1),
+            TerminalEvent::Wheel(-1)
+        ]);
+    }
+
+    #[test]
+    // Escape sequences and characters cut off at the end of a read are held back for the next.
+    fn test_split_reads() {
+        assert_eq!(complete_len(b"ab\x1b[A"), 5);
+        assert_eq!(complete_len(b"ab\x1b[<0;10"), 2);
+        assert_eq!(complete_len(b"a\x1bO"), 1);
+        assert_eq!(complete_len(b"a\x1b"), 2);
+        assert_eq!(complete_len("é".as_bytes()), 2);
+        assert_eq!(complete_len(&"é".as_bytes()[..1]), 0);
+
+        let mut input = b"\x1b[<35;3;4M\x1b[1".to_vec();
+        let end = complete_len(&input);
+        assert_eq!(parse_input(&input[..end]), vec![TerminalEvent::Mouse{ x: 2, y: 3, left_click: false }]);
+        input.drain(.. end);
+        input.extend_from_slice(b"5~");
+        assert_eq!(parse_input(&input), vec![key(VirtualKeyCode::F5)]);
+    }
+}
@@ -0,0 +1,244 @@
+//! A backend that runs an RLTK game in a text terminal (for example over SSH), using ANSI
+//! escape codes for output and decoding key/mouse sequences from stdin.
+//!
+//! Create the context with `Rltk::init_terminal`, and run it with `terminal::main_loop`
+//! instead of `rltk::main_loop`. Your `GameState` doesn't need to change.
+
+mod ansi;
+mod input;
+mod terminal_console;
+
+pub use self::ansi::{AnsiScreen, ColorMode, rgb_to_palette256};
+pub use self::input::{parse_input, complete_len, TerminalEvent};
+pub use self::terminal_console::TerminalConsole;
+
+use super::{GameState, Rltk, Tile, RGB, color};
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Target delay between frames; terminals can't usefully refresh much faster than this.
+const FRAME_DURATION : Duration = Duration::from_millis(33);
+
+#[cfg(unix)]
+/// Puts the terminal into raw mode (no echo, no line buffering), and restores the
+/// original settings when dropped.
+struct RawMode {
+    original : Option<libc::termios>
+}
+
+#[cfg(unix)]
+impl RawMode {
+    fn enable() -> RawMode {
+        unsafe {
+            let mut original : libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return RawMode{ original : None };
+            }
+            let mut raw = original;
+            libc::cfmakeraw(&mut raw);
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw);
+            RawMode{ original : Some(original) }
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        if let Some(original) = self.original {
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &original);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+/// Raw mode isn't supported on this platform; input will be line-buffered.
+struct RawMode {}
+
+#[cfg(not(unix))]
+impl RawMode {
+    fn enable() -> RawMode { RawMode{} }
+}
+
+/// Reads stdin on a background thread, so the game loop never blocks waiting for keys.
+fn spawn_input_reader() -> Receiver<Vec<u8>> {
+    let (tx, rx) = channel();
+    thread::spawn(move || {
+        let stdin = io::stdin();
+        let mut handle = stdin.lock();
+        let mut buffer = [0u8; 256];
+        loop {
+            match handle.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if tx.send(buffer[..n].to_vec()).is_err() { break; }
+                }
+            }
+        }
+    });
+    rx
+}
+
+/// Flattens the console stack into a single frame of cells, in the same way the OpenGL
+/// backend layers them: consoles without a background only contribute their glyphs.
+pub(crate) fn composite(rltk : &Rltk, width : u32, height : u32) -> Vec<Tile> {
+    let blank = Tile{ glyph: 32, fg: RGB::named(color::WHITE), bg: RGB::named(color::BLACK) };
+    let mut frame = vec![blank; (width * height) as usize];
+
+    for cons in rltk.consoles.iter() {
+        let layer = cons.console.to_xp_layer();
+        let has_background = cons.shader_index != 1;
+        for y in 0 .. u32::min(height, layer.height as u32) {
+            for x in 0 .. u32::min(width, layer.width as u32) {
+                let cell = layer.get(x as usize, y as usize).unwrap();
+                let idx = ((y * width) + x) as usize;
+                if has_background {
+                    if cell.bg.is_transparent() { continue; }
+                    frame[idx] = Tile{ glyph: cell.ch as u8, fg: RGB::from_xp(cell.fg), bg: RGB::from_xp(cell.bg) };
+                } else if cell.ch != 0 && cell.ch != 32 {
+                    frame[idx].glyph = cell.ch as u8;
+                    frame[idx].fg = RGB::from_xp(cell.fg);
+                }
+            }
+        }
+    }
+
+    frame
+}
+
+/// Runs the RLTK application in the terminal, calling into the provided gamestate handler
+/// every tick. The context should have been created with `Rltk::init_terminal`.
+pub fn main_loop(mut rltk : Rltk, mut gamestate: Box<dyn GameState>) {
+    let (width, height) = rltk.terminal_size();
+    let raw_mode = RawMode::enable();
+    let input = spawn_input_reader();
+    let mut screen = AnsiScreen::new(width, height, ColorMode::detect());
+    let mut pending_input = VecDeque::new();
+    let mut unparsed : Vec<u8> = Vec::new();
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    // Alternate screen, hidden cursor, clear, SGR mouse reporting (including motion with no
+    // button held, so that hovering works)
+    write!(out, "\x1b[?1049h\x1b[?25l\x1b[2J\x1b[?1003h\x1b[?1006h").unwrap();
+    out.flush().unwrap();
+
+    let now = Instant::now();
+    let mut prev_seconds = now.elapsed().as_secs();
+    let mut prev_ms = now.elapsed().as_millis();
+    let mut frames = 0;
+
+    while !rltk.quitting {
+        let frame_start = Instant::now();
+
+        // Input: one key per tick, so nothing typed quickly is lost. Text typed before the key
+        // comes with it, and anything typed after waits, so edits are applied in order.
+        rltk.key = None;
+        rltk.shift = false;
+        rltk.left_click = false;
+        rltk.left_pressed = false;
+        rltk.mouse_wheel = 0;
+        rltk.chars.clear();
+        while let Ok(bytes) = input.try_recv() {
+            // Raw mode swallows the interrupt signal, so treat Ctrl-C as a request to quit
+            if bytes.contains(&0x03) {
+                rltk.quit();
+            }
+            // A read can end part way through an escape sequence; keep the rest for next time
+            unparsed.extend_from_slice(&bytes);
+            let end = complete_len(&unparsed);
+            let bytes : Vec<u8> = unparsed.drain(.. end).collect();
+            for event in parse_input(&bytes) {
+                match event {
+                    TerminalEvent::Key{ .. } | TerminalEvent::Char(_) => pending_input.push_back(event),
+                    TerminalEvent::Mouse{ x, y, left_click } => {
+                        rltk.mouse_pos = (x, y);
+                        rltk.left_click |= left_click;
+                        rltk.left_pressed |= left_click;
+                    }
+                    TerminalEvent::Wheel(lines) => rltk.mouse_wheel += lines
+                }
+            }
+        }
+        while let Some(event) = pending_input.pop_front() {
+            match event {
+                TerminalEvent::Char(c) => rltk.chars.push(c),
+                TerminalEvent::Key{ key, shift } => {
+                    rltk.key = Some(key);
+                    rltk.shift = shift;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        // Timing
+        frames += 1;
+        let now_seconds = now.elapsed().as_secs();
+        if now_seconds > prev_seconds {
+            rltk.fps = frames as f32 / (now_seconds - prev_seconds) as f32;
+            frames = 0;
+            prev_seconds = now_seconds;
+        }
+        let now_ms = now.elapsed().as_millis();
+        if now_ms > prev_ms {
+            rltk.frame_time_ms = (now_ms - prev_ms) as f32;
+            prev_ms = now_ms;
+        }
+
+        gamestate.tick(&mut rltk);
+        rltk.apply_effects();
+
+        for cons in rltk.consoles.iter_mut() {
+            cons.console.end_frame();
+        }
+        rltk.publish_frame();
+        rltk.record_frame();
+
+        let update = screen.render(&composite(&rltk, width, height));
+        if !update.is_empty() {
+            out.write_all(update.as_bytes()).unwrap();
+            out.flush().unwrap();
+        }
+        rltk.restore_effects();
+
+        let elapsed = frame_start.elapsed();
+        if elapsed < FRAME_DURATION {
+            thread::sleep(FRAME_DURATION - elapsed);
+        }
+    }
+
+    let _ = rltk.stop_recording();
+
+    // Put the terminal back the way we found it
+    write!(out, "\x1b[0m\x1b[?1006l\x1b[?1003l\x1b[?25h\x1b[?1049l").unwrap();
+    out.flush().unwrap();
+    drop(raw_mode);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::composite;
+    use super::super::{Console, Rltk, SparseConsole, RGB};
+
+    #[test]
+    // Layers without a background add their glyphs on top, at the rows they were drawn on.
+    fn test_composite_sparse_layer() {
+        let mut rltk = Rltk::init_terminal(10, 5);
+        rltk.consoles[0].console.print(0, 4, "base");
+        let mut overlay = SparseConsole::headless(10, 5);
+        overlay.set(2, 1, RGB::from_u8(255, 255, 0), RGB::from_u8(0, 0, 0), 64);
+        rltk.register_console_no_bg(overlay, 0);
+
+        let frame = composite(&rltk, 10, 5);
+        assert_eq!(frame[12].glyph, 64);
+        assert!(frame[12].fg == RGB::from_u8(255, 255, 0));
+        assert_eq!(frame[32].glyph, 32);
+        assert_eq!(frame[40].glyph, b'b');
+    }
+}
@@ -0,0 +1,189 @@
+use super::super::{Console, Tile, TileChange, RGB, color, Font, Shader, rex::XpLayer, gui_helpers, gl};
+use super::super::tile_buffer::TileBuffer;
+
+/// A console that lives purely in memory, with no OpenGL resources. The terminal backend
+/// composites these onto the screen with ANSI escape codes.
+pub struct TerminalConsole {
+    pub width :u32,
+    pub height: u32,
+
+    // Private
+    buffer: TileBuffer,
+    previous_tiles: Vec<Tile>,
+    changes: Vec<TileChange>
+}
+
+impl TerminalConsole {
+    /// Initializes a terminal console, ready to add to RLTK's console list.
+    pub fn init(width:u32, height: u32) -> Box<TerminalConsole> {
+        let buffer = TileBuffer::new(width, height);
+        Box::new(TerminalConsole{
+            width,
+            height,
+            previous_tiles: buffer.tiles().to_vec(),
+            buffer,
+            changes: Vec::new()
+        })
+    }
+
+    /// Provides read access to the console's cells, in row-major order starting at the top-left.
+    pub fn tiles(&self) -> &[Tile] {
+        self.buffer.tiles()
+    }
+}
+
+impl Console for TerminalConsole {
+    /// Nothing to do: there is no OpenGL backing.
+    fn rebuild_if_dirty(&mut self, _gl : &gl::Gles2) {}
+
+    /// Nothing to do: the terminal backend draws the console itself.
+    fn gl_draw(&mut self, _font : &Font, _shader : &Shader, _gl : &gl::Gles2) {}
+
+    /// Translate an x/y into an array index. Terminal consoles are stored top-down.
+    fn at(&self, x:i32, y:i32) -> usize {
+        self.buffer.at(x, y)
+    }
+
+    /// Returns the console width, in characters.
+    fn width(&self) -> u32 { self.width }
+
+    /// Returns the console height, in characters.
+    fn height(&self) -> u32 { self.height }
+
+    /// Reads back the cell at x/y.
+    fn get(&self, x:i32, y:i32) -> Option<Tile> {
+        self.buffer.get(x, y)
+    }
+
+    /// Clears the screen.
+    fn cls(&mut self) {
+        self.cls_bg(RGB::named(color::BLACK));
+    }
+
+    /// Clears the screen with a background color.
+    fn cls_bg(&mut self, background : RGB) {
+        self.buffer.cls_bg(background);
+    }
+
+    /// Prints a string at x/y.
+    fn print(&mut self, x:i32, y:i32, output:&str) {
+        self.buffer.print(x, y, output);
+    }
+
+    /// Prints a string at x/y, with foreground and background colors.
+    fn print_color(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, output:&str) {
+        let bytes = super::super::string_to_cp437(output);
+        for (i, glyph) in bytes.iter().enumerate() {
+            self.set(x + i as i32, y, fg, bg, *glyph);
+        }
+    }
+
+    /// Sets a single cell in the console
+    fn set(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, glyph:u8) {
+        self.buffer.set(x, y, fg, bg, glyph);
+    }
+
+    /// Sets a single cell in the console's background
+    fn set_bg(&mut self, x:i32, y:i32, bg:RGB) {
+        self.buffer.set_bg(x, y, bg);
+    }
+
+    /// Fills a rectangle with a glyph and colors
+    fn fill_region(&mut self, sx:i32, sy:i32, width:i32, height:i32, fg:RGB, bg:RGB, glyph:u8) {
+        gui_helpers::fill_region(self, sx, sy, width, height, fg, bg, glyph);
+    }
+
+    /// Scrolls the console's contents by dx/dy cells
+    fn scroll(&mut self, dx:i32, dy:i32) {
+        let (width, height) = (self.width as i32, self.height as i32);
+        gui_helpers::scroll_region(self, 0, 0, width, height, dx, dy);
+    }
+
+    /// Draws a box, starting at x/y with the extents width/height using CP437 line characters
+    fn draw_box(&mut self, sx:i32, sy:i32, width:i32, height:i32, fg: RGB, bg: RGB) {
+        gui_helpers::draw_box(self, sx, sy, width, height, fg, bg);
+    }
+
+    /// Draws a box, starting at x/y with the extents width/height using CP437 double line characters
+    fn draw_box_double(&mut self, sx:i32, sy:i32, width:i32, height:i32, fg: RGB, bg: RGB) {
+        gui_helpers::draw_box_double(self, sx, sy, width, height, fg, bg);
+    }
+
+    /// Draws a horizontal progress bar
+    fn draw_bar_horizontal(&mut self, sx:i32, sy:i32, width:i32, n:i32, max:i32, fg:RGB, bg: RGB) {
+        gui_helpers::draw_bar_horizontal(self, sx, sy, width, n, max, fg, bg);
+    }
+
+    /// Draws a vertical progress bar
+    fn draw_bar_vertical(&mut self, sx:i32, sy:i32, height:i32, n:i32, max:i32, fg:RGB, bg: RGB) {
+        gui_helpers::draw_bar_vertical(self, sx, sy, height, n, max, fg, bg);
+    }
+
+    /// Prints text, centered to the whole console width, at vertical location y.
+    fn print_centered(&mut self, y:i32, text:&str) {
+        self.print((self.width as i32 / 2) - (text.to_string().len() as i32/2), y, text);
+    }
+
+    /// Prints text in color, centered to the whole console width, at vertical location y.
+    fn print_color_centered(&mut self, y:i32, fg:RGB, bg:RGB, text:&str) {
+        self.print_color((self.width as i32 / 2) - (text.to_string().len() as i32/2), y, fg, bg, text);
+    }
+
+    /// Saves the layer to an XpFile structure
+    fn to_xp_layer(&self) -> XpLayer {
+        self.buffer.to_xp_layer()
+    }
+
+    /// Terminals can't draw between cells, so offsets are ignored.
+    fn set_offset(&mut self, _x : f32, _y : f32) {}
+
+    /// Compares the console with the previous frame, and stores the changed cells.
+    fn end_frame(&mut self) {
+        self.changes.clear();
+        for (idx, tile) in self.buffer.tiles().iter().enumerate() {
+            if *tile != self.previous_tiles[idx] {
+                self.changes.push(TileChange{
+                    x: (idx % self.width as usize) as i32,
+                    y: (idx / self.width as usize) as i32,
+                    tile: *tile
+                });
+                self.previous_tiles[idx] = *tile;
+            }
+        }
+    }
+
+    /// Returns the cells that changed during the last frame.
+    fn frame_diff(&self) -> Option<&[TileChange]> {
+        Some(&self.changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TerminalConsole;
+    use super::super::super::{Console, RGB};
+
+    #[test]
+    // Cells read back as they were written, and off-console reads return None.
+    fn test_get() {
+        let mut console = TerminalConsole::init(10, 5);
+        console.set(3, 2, RGB::from_f32(1.0, 0.0, 0.0), RGB::from_f32(0.0, 0.0, 1.0), 64);
+        let tile = console.get(3, 2).unwrap();
+        assert_eq!(tile.glyph, 64);
+        assert!(tile.fg == RGB::from_f32(1.0, 0.0, 0.0));
+        assert!(tile.bg == RGB::from_f32(0.0, 0.0, 1.0));
+        assert!(console.get(10, 0).is_none());
+        assert!(console.get(0, -1).is_none());
+    }
+
+    #[test]
+    // Filling a region that hangs off the console only touches the visible part.
+    fn test_fill_region() {
+        let mut console = TerminalConsole::init(10, 5);
+        console.fill_region(8, 3, 5, 5, RGB::from_f32(1.0, 1.0, 1.0), RGB::from_f32(0.0, 0.0, 0.0), 35);
+        let filled = console.tiles().iter().filter(|t| t.glyph == 35).count();
+        assert_eq!(filled, 4);
+        assert_eq!(console.get(9, 4).unwrap().glyph, 35);
+        assert_eq!(console.get(7, 4).unwrap().glyph, 32);
+    }
+}
@@ -1,4 +1,31 @@
-use super::{RGB, Console, Tile, codepage437::string_to_cp437};
+use super::{RGB, Console, Tile, codepage437::string_to_cp437, color_by_name};
+
+/// What a `TextBlock` does with text that doesn't fit in its height.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Overflow {
+    /// Extra lines are thrown away.
+    Clip,
+    /// Extra lines are thrown away, and the last line ends with "..." to show that there was more.
+    Ellipsis,
+    /// Every line is kept, and `scroll_by`/`scroll_to` pick which are shown.
+    Scroll,
+    /// Every line is kept and shown a page at a time, with "--more--" at the bottom of each page
+    /// that has another after it. Move through the pages with `next_page`.
+    Paged
+}
+
+/// How the lines of a paragraph are lined up. Justified lines are stretched to the full width
+/// by widening the gaps between words, except for the last line of each paragraph.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Alignment { Left, Right, Center, Justify }
+
+/// Layout details remembered for each line, applied when the block is rendered.
+#[derive(Copy, Clone)]
+struct LineInfo {
+    align : Alignment,
+    margin : i32,
+    wrapped : bool
+}
 
 pub struct TextBlock {
     x:i32, 
@@ -8,7 +35,18 @@ pub struct TextBlock {
     fg : RGB,
     bg : RGB,
     buffer : Vec<Tile>,
-    cursor: (i32, i32)
+    lines : Vec<LineInfo>,
+    cursor: (i32, i32),
+    overflow : Overflow,
+    scroll : i32,
+    lines_used : i32,
+    truncated : bool,
+    columns : i32,
+    gutter : i32,
+    line_width : i32,
+    align : Alignment,
+    indent : (i32, i32),
+    tab_stops : Vec<i32>
 }
 
 impl TextBlock {
@@ -20,61 +58,299 @@ impl TextBlock {
             height:height, 
             fg : RGB::from_f32(1.0, 1.0, 1.0), 
             bg : RGB::from_f32(0.0, 0.0, 0.0),
-            buffer : vec![Tile{ glyph: 0, fg : RGB::from_f32(1.0, 1.0, 1.0), bg : RGB::from_f32(0.0, 0.0, 0.0) } ; width as usize*height as usize],
-            cursor : (0,0)
+            buffer : vec![TextBlock::blank() ; width as usize*height as usize],
+            lines : vec![TextBlock::plain_line() ; height as usize],
+            cursor : (0,0),
+            overflow : Overflow::Clip,
+            scroll : 0,
+            lines_used : 0,
+            truncated : false,
+            columns : 1,
+            gutter : 0,
+            line_width : width,
+            align : Alignment::Left,
+            indent : (0, 0),
+            tab_stops : Vec::new()
         }
     }
 
+    /// Works out how many lines `text` needs when printed into a block `width` characters wide,
+    /// so a window or panel can be sized to fit it.
+    pub fn measure(width : i32, text : &TextBuilder) -> i32 {
+        let mut block = TextBlock::new(0, 0, i32::max(1, width), 0);
+        block.overflow = Overflow::Scroll;
+        block.print(text);
+        block.line_count()
+    }
+
+    fn blank() -> Tile {
+        Tile{ glyph: 0, fg : RGB::from_f32(1.0, 1.0, 1.0), bg : RGB::from_f32(0.0, 0.0, 0.0) }
+    }
+
+    fn plain_line() -> LineInfo {
+        LineInfo{ align : Alignment::Left, margin : 0, wrapped : false }
+    }
+
     pub fn fg(&mut self, fg :RGB) { self.fg = fg; }
     pub fn bg(&mut self, bg :RGB) { self.bg = bg; }
     pub fn move_to(&mut self, x:i32, y:i32) { self.cursor = ( x, y); }
-    fn at(&self, x:i32, y:i32) -> usize { ((y * self.width) + x) as usize }
+    fn at(&self, x:i32, y:i32) -> usize { ((y * self.line_width) + x) as usize }
+
+    /// Sets what happens to text that doesn't fit in the block. The default is `Overflow::Clip`.
+    pub fn overflow(&mut self, overflow : Overflow) { self.overflow = overflow; }
+
+    /// Splits the block into `count` columns separated by `gutter` spaces. Text flows down the
+    /// first column, then the second, and so on, like a newspaper. This clears the block, so call
+    /// it before printing.
+    pub fn columns(&mut self, count : i32, gutter : i32) {
+        self.columns = i32::max(1, count);
+        self.gutter = i32::max(0, gutter);
+        self.line_width = i32::max(1, (self.width - self.gutter * (self.columns - 1)) / self.columns);
+        self.clear();
+    }
+
+    /// Empties the block, and moves the cursor and scroll position back to the top.
+    pub fn clear(&mut self) {
+        self.buffer = vec![TextBlock::blank() ; self.line_width as usize * self.capacity() as usize];
+        self.lines = vec![TextBlock::plain_line() ; self.capacity() as usize];
+        self.cursor = (0, 0);
+        self.scroll = 0;
+        self.lines_used = 0;
+        self.truncated = false;
+    }
+
+    /// The number of lines that fit in the block at once, across all its columns.
+    fn capacity(&self) -> i32 { self.height * self.columns }
+
+    /// How many lines of text the block holds. With `Overflow::Clip` or `Overflow::Ellipsis`,
+    /// lines that didn't fit are not counted.
+    pub fn line_count(&self) -> i32 { self.lines_used }
+
+    /// The first line shown, when scrolling.
+    pub fn scroll_position(&self) -> i32 { self.scroll }
+
+    /// Scrolls down (positive) or up (negative) by a number of lines.
+    pub fn scroll_by(&mut self, lines : i32) {
+        let target = self.scroll + lines;
+        self.scroll_to(target);
+    }
+
+    /// Scrolls so that `line` is at the top, as far as the text allows.
+    pub fn scroll_to(&mut self, line : i32) {
+        let max_scroll = i32::max(0, self.lines_used - self.capacity());
+        self.scroll = i32::max(0, i32::min(line, max_scroll));
+    }
+
+    /// The number of lines of text on each page, leaving room for "--more--".
+    fn page_lines(&self) -> i32 { i32::max(1, self.capacity() - 1) }
+
+    /// How many pages the text fills, in `Overflow::Paged` mode.
+    pub fn page_count(&self) -> i32 {
+        if self.lines_used <= self.capacity() { 1 } else { (self.lines_used + self.page_lines() - 1) / self.page_lines() }
+    }
+
+    /// The page being shown, in `Overflow::Paged` mode.
+    pub fn page(&self) -> i32 { self.scroll / self.page_lines() }
+
+    /// Moves on to the next page. Returns false if this was the last page, so a "--more--" prompt
+    /// can be dismissed.
+    pub fn next_page(&mut self) -> bool {
+        if self.page() + 1 >= self.page_count() { return false; }
+        self.scroll = (self.page() + 1) * self.page_lines();
+        true
+    }
 
     pub fn render(&self, console : &mut Box<Console>) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                console.set(x + self.x, y + self.y, self.buffer[self.at(x, y)].fg, self.buffer[self.at(x, y)].bg, self.buffer[self.at(x, y)].glyph);
+        self.render_to(console.as_mut());
+    }
+
+    /// Renders the block to any console, including an `Rltk` context (which draws to the active console).
+    pub fn render_to(&self, console : &mut dyn Console) {
+        let mut rows : Vec<Vec<Tile>> = Vec::new();
+        let first_line = match self.overflow {
+            Overflow::Clip | Overflow::Ellipsis => 0,
+            _ => self.scroll
+        };
+        let shown = if self.overflow == Overflow::Paged && self.page_count() > 1 { self.page_lines() } else { self.capacity() };
+        for i in 0..self.capacity() {
+            let line = first_line + i;
+            if i < shown && line < self.lines.len() as i32 {
+                rows.push(self.aligned_line(line));
+            } else {
+                rows.push(vec![TextBlock::blank() ; self.line_width as usize]);
+            }
+        }
+
+        if let Some(last) = rows.last_mut() {
+            if self.overflow == Overflow::Ellipsis && self.truncated {
+                let end = last.iter().rposition(|t| t.glyph != 0 && t.glyph != 32).map(|i| i + 1).unwrap_or(0);
+                let start = usize::min(end, last.len().saturating_sub(3));
+                let (fg, bg) = if start > 0 { (last[start - 1].fg, last[start - 1].bg) } else { (self.fg, self.bg) };
+                for tile in last.iter_mut().skip(start).take(3) {
+                    *tile = Tile{ glyph: b'.', fg, bg };
+                }
+            } else if self.overflow == Overflow::Paged && self.page() + 1 < self.page_count() {
+                let more = string_to_cp437("--more--");
+                let start = last.len().saturating_sub(more.len());
+                for (tile, glyph) in last.iter_mut().skip(start).zip(more.iter()) {
+                    *tile = Tile{ glyph: *glyph, fg: RGB::from_f32(1.0, 1.0, 0.0), bg: RGB::from_f32(0.0, 0.0, 0.0) };
+                }
+            }
+        }
+
+        for (i, row) in rows.iter().enumerate() {
+            let column = i as i32 / i32::max(1, self.height);
+            let left = self.x + column * (self.line_width + self.gutter);
+            let y = self.y + i as i32 % i32::max(1, self.height);
+            for (x, tile) in row.iter().enumerate() {
+                console.set(left + x as i32, y, tile.fg, tile.bg, tile.glyph);
+            }
+        }
+    }
+
+    /// A line of the buffer, moved or stretched according to its alignment.
+    fn aligned_line(&self, line : i32) -> Vec<Tile> {
+        let start = self.at(0, line);
+        let mut row = self.buffer[start .. start + self.line_width as usize].to_vec();
+        let info = self.lines[line as usize];
+        let margin = usize::min(row.len(), i32::max(0, info.margin) as usize);
+        let end = match row.iter().rposition(|t| t.glyph != 0 && t.glyph != 32) {
+            Some(end) if end >= margin => end + 1,
+            _ => return row
+        };
+        let extra = row.len() - end;
+
+        match info.align {
+            Alignment::Left => {}
+            Alignment::Right | Alignment::Center => {
+                let shift = if info.align == Alignment::Right { extra } else { extra / 2 };
+                row[margin .. end + shift].rotate_right(shift);
+                for tile in row[margin .. margin + shift].iter_mut() { *tile = TextBlock::blank(); }
+            }
+            Alignment::Justify => {
+                if !info.wrapped { return row; }
+                // Find the spaces that separate words, and spread the spare width between them
+                let gaps : Vec<usize> = (margin + 1 .. end).filter(|i| row[*i].glyph == 32 && row[*i - 1].glyph != 32).collect();
+                if gaps.is_empty() { return row; }
+                let mut justified = row[.. margin].to_vec();
+                for (i, tile) in row.iter().enumerate().take(end).skip(margin) {
+                    justified.push(*tile);
+                    if let Some(gap) = gaps.iter().position(|g| *g == i) {
+                        let widen = extra / gaps.len() + if gap < extra % gaps.len() { 1 } else { 0 };
+                        for _ in 0 .. widen { justified.push(*tile); }
+                    }
+                }
+                return justified;
+            }
+        }
+        row
+    }
+
+    /// Ends the current line. `wrapped` is true if the text carries on onto the next line, which
+    /// matters for justified text.
+    fn newline(&mut self, wrapped : bool) {
+        if wrapped {
+            if let Some(info) = self.lines.get_mut(self.cursor.1.max(0) as usize) { info.wrapped = true; }
+        }
+        self.cursor.0 = if wrapped { self.indent.0 } else { self.indent.0 + self.indent.1 };
+        self.cursor.1 += 1;
+    }
+
+    /// Writes a glyph at the cursor, wrapping onto the next line if the current one is full.
+    /// Text below the bottom of the block is kept or dropped according to the overflow policy.
+    fn put(&mut self, glyph : u8) {
+        if self.cursor.0 >= self.line_width { self.newline(true); }
+        let (x, y) = (i32::max(0, self.cursor.0), self.cursor.1);
+        self.cursor.0 = x + 1;
+        if y < 0 { return; }
+
+        if y >= self.capacity() {
+            match self.overflow {
+                Overflow::Clip | Overflow::Ellipsis => {
+                    self.truncated = true;
+                    return;
+                }
+                _ => {
+                    let needed = (y + 1) as usize * self.line_width as usize;
+                    if self.buffer.len() < needed { self.buffer.resize(needed, TextBlock::blank()); }
+                    if self.lines.len() < y as usize + 1 { self.lines.resize(y as usize + 1, TextBlock::plain_line()); }
+                }
+            }
+        }
+
+        let idx = self.at(x, y);
+        self.buffer[idx] = Tile{ glyph, fg : self.fg, bg : self.bg };
+        self.lines[y as usize] = LineInfo{ align : self.align, margin : self.indent.0, wrapped : false };
+        self.lines_used = i32::max(self.lines_used, y + 1);
+    }
+
+    fn put_all(&mut self, glyphs : &[u8]) {
+        for c in glyphs.iter() {
+            self.put(*c);
+        }
+    }
+
+    /// Moves the cursor to the next tab stop, or the next line if there are no more stops on this one.
+    fn tab(&mut self) {
+        let next = match self.tab_stops.iter().find(|stop| **stop > self.cursor.0) {
+            Some(stop) => *stop,
+            None => (self.cursor.0 / 8 + 1) * 8
+        };
+        if next >= self.line_width {
+            self.newline(true);
+        } else {
+            self.cursor.0 = next;
+        }
+    }
+
+    /// Writes a word, moving to the next line if it doesn't fit on this one. Words are broken
+    /// after a hyphen if that lets part of them fit, and words longer than a whole line are
+    /// hyphenated.
+    fn wrap_word(&mut self, word : &[u8]) {
+        let mut word = word;
+        while !word.is_empty() {
+            let line_start = i32::max(0, self.indent.0);
+            let remaining = i32::max(0, self.line_width - self.cursor.0) as usize;
+            if word.len() <= remaining {
+                self.put_all(word);
+                return;
+            }
+            if let Some(hyphen) = word[.. remaining].iter().rposition(|c| *c == b'-') {
+                self.put_all(&word[..= hyphen]);
+                word = &word[hyphen + 1 ..];
+                self.newline(true);
+            } else if self.cursor.0 > line_start {
+                self.newline(true);
+            } else if remaining >= 2 {
+                let split = remaining - 1;
+                self.put_all(&word[.. split]);
+                self.put(b'-');
+                word = &word[split ..];
+                self.newline(true);
+            } else {
+                self.put_all(word);
+                return;
             }
         }
     }
 
     pub fn print(&mut self, text : &TextBuilder) {
+        let mut saved_colors : Vec<(RGB, RGB)> = Vec::new();
         for cmd in text.commands.iter() {
             match cmd {
                 CommandType::Text{ block : t } => {
-                    for c in t.iter() {
-                        let idx = self.at(self.cursor.0, self.cursor.1);
-                        self.buffer[idx].glyph = *c;
-                        self.buffer[idx].fg = self.fg;
-                        self.buffer[idx].bg = self.bg;
-                        self.cursor.0 += 1;
-                        if self.cursor.0 >= self.width {
-                            self.cursor.0 = 0;
-                            self.cursor.1 += 1;
-                        }
-                    }
+                    self.put_all(t);
                 }
 
                 CommandType::Centered{ block : t } => {
                     let text_width = t.len() as i32;
                     let half_width = text_width / 2;
-                    self.cursor.0 = (self.width / 2) - half_width;
-                    for c in t.iter() {
-                        let idx = self.at(self.cursor.0, self.cursor.1);
-                        self.buffer[idx].glyph = *c;
-                        self.buffer[idx].fg = self.fg;
-                        self.buffer[idx].bg = self.bg;
-                        self.cursor.0 += 1;
-                        if self.cursor.0 >= self.width {
-                            self.cursor.0 = 0;
-                            self.cursor.1 += 1;
-                        }
-                    }
+                    self.cursor.0 = i32::max(0, (self.line_width / 2) - half_width);
+                    self.put_all(t);
                 }
 
                 CommandType::NewLine{} => {
-                    self.cursor.0 = 0;
-                    self.cursor.1 += 1;
+                    self.newline(false);
                 }
 
                 CommandType::Foreground{col} => { self.fg = *col }
@@ -83,31 +359,40 @@ impl TextBlock {
                     self.cursor = (0,0);
                     self.fg = RGB::from_f32(1.0, 1.0, 1.0);
                     self.bg = RGB::from_f32(0.0, 0.0, 0.0);
+                    self.align = Alignment::Left;
+                    self.indent = (0, 0);
+                    self.tab_stops.clear();
                 }
 
                 CommandType::TextWrapper{block : t} => {
-                    let words = t.split(' ');
-
-                    for word in words {
-                        let mut chrs = string_to_cp437(&word);
-                        chrs.push(32);
-                        if self.cursor.0 + chrs.len() as i32 >= self.width {
-                            self.cursor.0 = 0;
-                            self.cursor.1 += 1;
+                    for word in t.split(' ') {
+                        for (i, part) in word.split('\t').enumerate() {
+                            if i > 0 { self.tab(); }
+                            self.wrap_word(&string_to_cp437(part));
                         }
-                        for c in chrs.iter() {
-                            let idx = self.at(self.cursor.0, self.cursor.1);
-                            self.buffer[idx].glyph = *c;
-                            self.buffer[idx].fg = self.fg;
-                            self.buffer[idx].bg = self.bg;
-                            self.cursor.0 += 1;
-                            if self.cursor.0 >= self.width {
-                                self.cursor.0 = 0;
-                                self.cursor.1 += 1;
-                            }
+                        if self.cursor.0 > self.indent.0 && self.cursor.0 < self.line_width {
+                            self.put(32);
                         }
                     }
                 }
+
+                CommandType::Align{ align } => { self.align = *align }
+                CommandType::Indent{ left, first } => {
+                    // Indenting the line we are on only has an effect if nothing has been written to it yet
+                    if self.cursor.0 == self.indent.0 + self.indent.1 {
+                        self.cursor.0 = *left + *first;
+                    }
+                    self.indent = (*left, *first);
+                }
+                CommandType::TabStops{ stops } => { self.tab_stops = stops.clone() }
+                CommandType::Tab{} => { self.tab() }
+                CommandType::SaveColors{} => { saved_colors.push((self.fg, self.bg)) }
+                CommandType::RestoreColors{} => {
+                    if let Some((fg, bg)) = saved_colors.pop() {
+                        self.fg = fg;
+                        self.bg = bg;
+                    }
+                }
             }
         }
     }
@@ -120,7 +405,15 @@ pub enum CommandType {
     Foreground { col : RGB },
     Background { col : RGB },
     TextWrapper { block : String },
-    Reset {}
+    Reset {},
+    Align { align : Alignment },
+    Indent { left : i32, first : i32 },
+    TabStops { stops : Vec<i32> },
+    Tab {},
+    /// Remembers the current colors, to be put back by a later `RestoreColors`.
+    SaveColors {},
+    /// Goes back to the colors remembered by the most recent `SaveColors`.
+    RestoreColors {}
 }
 
 pub struct TextBuilder {
@@ -131,8 +424,11 @@ impl TextBuilder {
     pub fn empty() -> TextBuilder { TextBuilder{ commands : Vec::new() } }
 
     pub fn append(&mut self, text : &str) -> &mut Self {
-        let chrs = string_to_cp437(&text);
-        self.commands.push( CommandType::Text { block : chrs } );
+        for (i, part) in text.split('\t').enumerate() {
+            if i > 0 { self.commands.push( CommandType::Tab{} ); }
+            let chrs = string_to_cp437(&part);
+            self.commands.push( CommandType::Text { block : chrs } );
+        }
         self
     }
     pub fn centered(&mut self, text : &str) -> &mut Self {
@@ -145,4 +441,325 @@ impl TextBuilder {
     pub fn fg(&mut self, col : RGB) -> &mut Self { self.commands.push( CommandType::Foreground{ col : col } ); self }
     pub fn bg(&mut self, col : RGB) -> &mut Self { self.commands.push( CommandType::Background{ col : col } ); self }
     pub fn line_wrap(&mut self, text : &str) -> &mut Self { self.commands.push( CommandType::TextWrapper{ block : text.to_string() } ); self }
+
+    /// Sets the alignment of the lines that follow, until the next `align` or `reset`.
+    pub fn align(&mut self, align : Alignment) -> &mut Self { self.commands.push( CommandType::Align{ align } ); self }
+
+    /// Indents the lines that follow by `left` characters. The first line of each paragraph (the
+    /// line after `ln`) is indented by a further `first` characters; make it negative for a
+    /// hanging indent.
+    pub fn indent(&mut self, left : i32, first : i32) -> &mut Self { self.commands.push( CommandType::Indent{ left, first } ); self }
+
+    /// Sets the columns that tabs move to, counted from the left of the block. Past the last
+    /// stop (or with none set) tabs move to the next multiple of 8.
+    pub fn tab_stops(&mut self, stops : &[i32]) -> &mut Self {
+        let mut stops = stops.to_vec();
+        stops.sort();
+        self.commands.push( CommandType::TabStops{ stops } );
+        self
+    }
+
+    /// Moves to the next tab stop. A `\t` in `append`, `line_wrap` or `markup` text does the same.
+    pub fn tab(&mut self) -> &mut Self { self.commands.push( CommandType::Tab{} ); self }
+
+    /// Appends text containing color markup, so colored text can be written in data files.
+    /// Either style of tag may be used:
+    ///
+    /// * `#[red]` or `{fg:red}` sets the foreground, and `#[bg:blue]` or `{bg:blue}` the background.
+    ///   Several settings can be combined with commas: `#[yellow,bg:dark_red]`.
+    /// * `#[]` or `{/}` goes back to the colors in use before the matching tag, or before the
+    ///   markup if no tag is open.
+    ///
+    /// Colors are the names in `rltk::color` (any case, so `DarkRed`, `dark_red` and `dark red`
+    /// all work) or HTML codes such as `#ff8000`. Write `{{` for a literal `{` and `##[` for a
+    /// literal `#[`; a newline starts a new line and a tab moves to the next tab stop. Tags that can't be understood, such as an
+    /// unknown color, are printed as they are so that mistakes are easy to spot.
+    pub fn markup(&mut self, text : &str) -> &mut Self {
+        self.commands.extend(parse_markup(text));
+        self
+    }
+}
+
+/// Looks up a color from markup: a color name, or an HTML color code.
+fn markup_color(name : &str) -> Option<RGB> {
+    let name = name.trim();
+    if name.starts_with('#') {
+        return RGB::from_hex(name).ok();
+    }
+    color_by_name(name).map(RGB::named)
+}
+
+/// Turns the contents of a color tag (e.g. `yellow,bg:dark_red`) into the commands that set
+/// those colors. Returns None if any part of it isn't understood.
+fn markup_tag_commands(tag : &str) -> Option<Vec<CommandType>> {
+    let mut commands = Vec::new();
+    for part in tag.split(',') {
+        let part = part.trim();
+        if let Some(name) = part.strip_prefix("bg:") {
+            commands.push(CommandType::Background{ col : markup_color(name)? });
+        } else if let Some(name) = part.strip_prefix("fg:") {
+            commands.push(CommandType::Foreground{ col : markup_color(name)? });
+        } else {
+            commands.push(CommandType::Foreground{ col : markup_color(part)? });
+        }
+    }
+    Some(commands)
+}
+
+/// Moves any text collected so far into a command.
+fn flush_markup_text(pending : &mut String, commands : &mut Vec<CommandType>) {
+    if !pending.is_empty() {
+        commands.push(CommandType::Text{ block : string_to_cp437(pending.as_str()) });
+        pending.clear();
+    }
+}
+
+/// Turns marked-up text (see `TextBuilder::markup`) into commands. Each tag saves the colors
+/// before setting its own, and each closing tag restores them; the colors in use before the
+/// markup are saved first, so that a closing tag with nothing open goes back to them.
+fn parse_markup(text : &str) -> Vec<CommandType> {
+    let mut commands = vec![CommandType::SaveColors{}];
+    let mut open_tags = 0;
+    let mut pending = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("##[") {
+            pending.push_str("#[");
+            rest = &rest[3..];
+            continue;
+        }
+        if rest.starts_with("{{") {
+            pending.push('{');
+            rest = &rest[2..];
+            continue;
+        }
+
+        // Find the contents and total length of a #[...] or {fg:...}, {bg:...} or {/} tag
+        let tag = if let Some(after) = rest.strip_prefix("#[") {
+            after.find(']').map(|end| (&after[.. end], end + 3))
+        } else if let Some(after) = rest.strip_prefix('{') {
+            after.find('}').map(|end| (&after[.. end], end + 2))
+                .filter(|(inner, _)| *inner == "/" || inner.starts_with("fg:") || inner.starts_with("bg:"))
+        } else {
+            None
+        };
+
+        if let Some((inner, length)) = tag {
+            if inner.is_empty() || inner == "/" {
+                flush_markup_text(&mut pending, &mut commands);
+                commands.push(CommandType::RestoreColors{});
+                if open_tags > 0 {
+                    open_tags -= 1;
+                } else {
+                    // That was the colors from before the markup; keep them for the next one
+                    commands.push(CommandType::SaveColors{});
+                }
+                rest = &rest[length..];
+                continue;
+            }
+            if let Some(tag_commands) = markup_tag_commands(inner) {
+                flush_markup_text(&mut pending, &mut commands);
+                commands.push(CommandType::SaveColors{});
+                commands.extend(tag_commands);
+                open_tags += 1;
+                rest = &rest[length..];
+                continue;
+            }
+        }
+
+        if c == '\n' || c == '\t' {
+            flush_markup_text(&mut pending, &mut commands);
+            commands.push(if c == '\n' { CommandType::NewLine{} } else { CommandType::Tab{} });
+        } else {
+            pending.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+
+    flush_markup_text(&mut pending, &mut commands);
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TextBlock, TextBuilder, Overflow, Alignment};
+    use super::super::{Console, VirtualConsole, RGB, RED, BLUE, DARK_RED, YELLOW, CYAN, WHITE};
+
+    fn glyphs(block : &TextBlock, y : i32, len : i32) -> String {
+        (0 .. len).map(|x| block.buffer[block.at(x, y)].glyph as char).collect()
+    }
+
+    fn print_wrapped(block : &mut TextBlock, text : &str) {
+        let mut buf = TextBuilder::empty();
+        buf.line_wrap(text);
+        block.print(&buf);
+    }
+
+    #[test]
+    // Text that runs off the bottom is dropped instead of panicking, and Ellipsis marks it.
+    fn test_clip_and_ellipsis() {
+        let mut block = TextBlock::new(0, 0, 10, 2);
+        print_wrapped(&mut block, "one two three four five six seven");
+        assert_eq!(block.line_count(), 2);
+        assert_eq!(glyphs(&block, 1, 10), "three four");
+
+        block.overflow(Overflow::Ellipsis);
+        let mut console = VirtualConsole::new(10, 2);
+        block.render_to(&mut console);
+        let last : String = (0 .. 10).map(|x| console.get(x, 1).unwrap().glyph as char).collect();
+        assert_eq!(last, "three f...");
+
+        // A short last line only gets the three dots, and the rest of the row stays blank
+        let mut block = TextBlock::new(0, 0, 20, 2);
+        block.overflow(Overflow::Ellipsis);
+        let mut buf = TextBuilder::empty();
+        buf.append("one").ln().append("ab").ln().append("three");
+        block.print(&buf);
+        let mut console = VirtualConsole::new(20, 2);
+        block.render_to(&mut console);
+        let last : String = (0 .. 20).map(|x| match console.get(x, 1).unwrap().glyph { 0 => ' ', g => g as char }).collect();
+        assert_eq!(last, "ab...               ");
+    }
+
+    #[test]
+    // Long words are hyphenated, and hyphenated words break after the hyphen.
+    fn test_word_breaking() {
+        let mut block = TextBlock::new(0, 0, 6, 5);
+        print_wrapped(&mut block, "Aaaaaaaaargh well-known");
+        assert_eq!(glyphs(&block, 0, 6), "Aaaaa-");
+        assert_eq!(glyphs(&block, 1, 6), "aaaar-");
+        assert_eq!(glyphs(&block, 2, 2), "gh");
+        assert_eq!(glyphs(&block, 3, 5), "well-");
+        assert_eq!(glyphs(&block, 4, 5), "known");
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut buf = TextBuilder::empty();
+        buf.line_wrap("The quick brown fox jumped over the lazy dog").ln().append("Done");
+        assert_eq!(TextBlock::measure(10, &buf), 6);
+        assert_eq!(TextBlock::measure(80, &buf), 2);
+    }
+
+    #[test]
+    // Scrolling and paging show later lines; pages end in --more-- until the last.
+    fn test_scroll_and_pages() {
+        let mut block = TextBlock::new(0, 0, 8, 3);
+        block.overflow(Overflow::Scroll);
+        let mut buf = TextBuilder::empty();
+        for i in 0 .. 7 {
+            buf.append(&format!("Line {}", i)).ln();
+        }
+        block.print(&buf);
+        assert_eq!(block.line_count(), 7);
+        block.scroll_by(10);
+        assert_eq!(block.scroll_position(), 4);
+
+        let mut console = VirtualConsole::new(8, 3);
+        block.render_to(&mut console);
+        assert_eq!(console.get(5, 0).unwrap().glyph, b'4');
+
+        block.overflow(Overflow::Paged);
+        block.scroll_to(0);
+        assert_eq!(block.page_count(), 4);
+        block.render_to(&mut console);
+        assert_eq!(console.get(5, 1).unwrap().glyph, b'1');
+        assert_eq!(console.get(0, 2).unwrap().glyph, b'-');
+        assert!(block.next_page() && block.next_page() && block.next_page());
+        assert!(!block.next_page());
+        block.render_to(&mut console);
+        assert_eq!(console.get(5, 0).unwrap().glyph, b'6');
+        assert_eq!(console.get(0, 2).unwrap().glyph, 0);
+    }
+
+    fn rendered(block : &TextBlock, width : i32, height : i32) -> Vec<String> {
+        let mut console = VirtualConsole::new(width as u32, height as u32);
+        block.render_to(&mut console);
+        (0 .. height).map(|y| (0 .. width).map(|x| match console.get(x, y).unwrap().glyph { 0 => ' ', g => g as char }).collect()).collect()
+    }
+
+    #[test]
+    fn test_alignment() {
+        let mut block = TextBlock::new(0, 0, 12, 5);
+        let mut buf = TextBuilder::empty();
+        buf.align(Alignment::Right).line_wrap("Hit points").ln()
+            .align(Alignment::Center).append("Level 3").ln()
+            .align(Alignment::Justify).line_wrap("a bb ccc dd e ffff");
+        block.print(&buf);
+        assert_eq!(rendered(&block, 12, 5), vec!["  Hit points", "  Level 3   ", "a  bb ccc dd", "e ffff      ", "            "]);
+    }
+
+    #[test]
+    // Paragraph indents, hanging indents and tab stops.
+    fn test_indent_and_tabs() {
+        let mut block = TextBlock::new(0, 0, 12, 4);
+        let mut buf = TextBuilder::empty();
+        buf.indent(2, 2).line_wrap("One two three").ln()
+            .indent(0, 0).tab_stops(&[4, 9]).append("a\tb\tc");
+        block.print(&buf);
+        assert_eq!(rendered(&block, 12, 4), vec!["    One two ", "  three     ", "a   b    c  ", "            "]);
+    }
+
+    #[test]
+    // Text flows down one column and into the next.
+    fn test_columns() {
+        let mut block = TextBlock::new(0, 0, 11, 2);
+        block.columns(2, 1);
+        let mut buf = TextBuilder::empty();
+        buf.line_wrap("aa bb cc dd ee");
+        block.print(&buf);
+        assert_eq!(rendered(&block, 11, 2), vec!["aa bb ee   ", "cc dd      "]);
+    }
+
+    #[test]
+    // Both tag styles set colors, and closing tags restore the previous colors.
+    fn test_markup_colors() {
+        let mut block = TextBlock::new(0, 0, 30, 2);
+        let mut text = TextBuilder::empty();
+        text.markup("#[red]Danger#[] {bg:blue}ok {fg:Dark Red}x{/}y{/}z");
+        block.print(&text);
+
+        assert_eq!(glyphs(&block, 0, 12), "Danger ok xy");
+        assert!(block.buffer[0].fg == RGB::named(RED));
+        assert!(block.buffer[6].fg == RGB::from_f32(1.0, 1.0, 1.0));
+        assert!(block.buffer[7].bg == RGB::named(BLUE));
+        assert!(block.buffer[10].fg == RGB::named(DARK_RED));
+        assert!(block.buffer[10].bg == RGB::named(BLUE));
+        assert!(block.buffer[11].fg == RGB::from_f32(1.0, 1.0, 1.0));
+        assert!(block.buffer[11].bg == RGB::named(BLUE));
+        assert!(block.buffer[12].bg == RGB::from_f32(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    // Tags apply even if they match the colors from before the markup, and closing tags go back
+    // to those colors rather than to white on black.
+    fn test_markup_after_colors() {
+        let mut block = TextBlock::new(0, 0, 30, 1);
+        let mut text = TextBuilder::empty();
+        text.fg(RGB::named(CYAN)).bg(RGB::named(BLUE)).markup("#[white]a#[]b#[]c").append("d");
+        block.print(&text);
+
+        assert_eq!(glyphs(&block, 0, 4), "abcd");
+        assert!(block.buffer[0].fg == RGB::named(WHITE));
+        assert!(block.buffer[0].bg == RGB::named(BLUE));
+        for i in 1 .. 4 {
+            assert!(block.buffer[i].fg == RGB::named(CYAN));
+            assert!(block.buffer[i].bg == RGB::named(BLUE));
+        }
+    }
+
+    #[test]
+    // Combined settings, HTML codes, escapes, newlines and unknown tags.
+    fn test_markup_syntax() {
+        let mut block = TextBlock::new(0, 0, 30, 2);
+        let mut text = TextBuilder::empty();
+        text.markup("#[yellow,bg:#ff0000]a#[] ##[b] {{c} #[nope]\n{x}");
+        block.print(&text);
+
+        assert_eq!(glyphs(&block, 0, 20), "a #[b] {c} #[nope]\0\0");
+        assert_eq!(glyphs(&block, 1, 3), "{x}");
+        assert!(block.buffer[0].fg == RGB::named(YELLOW));
+        assert!(block.buffer[0].bg == RGB::from_f32(1.0, 0.0, 0.0));
+    }
 }
@@ -0,0 +1,91 @@
+use super::{Tile, RGB, color, rex::XpLayer};
+
+/// A grid of tiles stored in memory, top-down, with no OpenGL resources. This is the storage
+/// behind `VirtualConsole` and `TerminalConsole`; writes that fall off the grid are ignored.
+pub(crate) struct TileBuffer {
+    width : u32,
+    height : u32,
+    tiles : Vec<Tile>
+}
+
+impl TileBuffer {
+    /// Creates a buffer, cleared to white-on-black spaces.
+    pub fn new(width : u32, height : u32) -> TileBuffer {
+        let blank = Tile{glyph: 32, fg: RGB::named(color::WHITE), bg: RGB::named(color::BLACK)};
+        TileBuffer{ width, height, tiles: vec![blank; (width * height) as usize] }
+    }
+
+    /// Returns true if x/y lies on the buffer.
+    pub fn in_bounds(&self, x:i32, y:i32) -> bool {
+        x >= 0 && y >= 0 && x < self.width as i32 && y < self.height as i32
+    }
+
+    /// Translates an x/y into an index into `tiles`.
+    pub fn at(&self, x:i32, y:i32) -> usize {
+        ((y * self.width as i32) + x) as usize
+    }
+
+    /// The cells, in row-major order starting at the top-left.
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    /// Reads back the cell at x/y.
+    pub fn get(&self, x:i32, y:i32) -> Option<Tile> {
+        if !self.in_bounds(x, y) { return None; }
+        Some(self.tiles[self.at(x, y)])
+    }
+
+    /// Clears every cell to a white-on-`background` space.
+    pub fn cls_bg(&mut self, background : RGB) {
+        for tile in self.tiles.iter_mut() {
+            tile.glyph = 32;
+            tile.fg = RGB::named(color::WHITE);
+            tile.bg = background;
+        }
+    }
+
+    /// Writes a string's glyphs from x/y, keeping the cells' colors.
+    pub fn print(&mut self, x:i32, y:i32, output:&str) {
+        let bytes = super::string_to_cp437(output);
+        for (i, glyph) in bytes.iter().enumerate() {
+            if self.in_bounds(x + i as i32, y) {
+                let idx = self.at(x + i as i32, y);
+                self.tiles[idx].glyph = *glyph;
+            }
+        }
+    }
+
+    /// Sets a single cell.
+    pub fn set(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, glyph:u8) {
+        if self.in_bounds(x, y) {
+            let idx = self.at(x, y);
+            self.tiles[idx] = Tile{ glyph, fg, bg };
+        }
+    }
+
+    /// Sets a single cell's background.
+    pub fn set_bg(&mut self, x:i32, y:i32, bg:RGB) {
+        if self.in_bounds(x, y) {
+            let idx = self.at(x, y);
+            self.tiles[idx].bg = bg;
+        }
+    }
+
+    /// Saves the buffer to an XpFile layer.
+    pub fn to_xp_layer(&self) -> XpLayer {
+        let mut layer = XpLayer::new(self.width as usize, self.height as usize);
+
+        for y in 0 .. self.height {
+            for x in 0 .. self.width {
+                let cell = layer.get_mut(x as usize, y as usize).unwrap();
+                let idx = self.at(x as i32, y as i32);
+                cell.ch = self.tiles[idx].glyph as u32;
+                cell.fg = self.tiles[idx].fg.to_xp();
+                cell.bg = self.tiles[idx].bg.to_xp();
+            }
+        }
+
+        layer
+    }
+}
@@ -0,0 +1,230 @@
+use super::{Point, RGB};
+use std::f32::consts::PI;
+
+/// The shape of a tween: how quickly it moves at the start, middle and end. "In" easings start
+/// slowly, "Out" easings finish slowly, and "InOut" do both.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Easing {
+    Linear,
+    QuadIn, QuadOut, QuadInOut,
+    CubicIn, CubicOut, CubicInOut,
+    /// Overshoots and springs back, like a rubber band.
+    ElasticIn, ElasticOut, ElasticInOut,
+    /// Bounces against the end, like a dropped ball.
+    BounceIn, BounceOut, BounceInOut
+}
+
+impl Easing {
+    /// Maps progress through a tween (0 to 1) to how far the value has moved (0 at the start
+    /// and 1 at the end, though elastic easings briefly go outside that range).
+    pub fn apply(&self, t : f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t).powi(2),
+            Easing::QuadInOut => if t < 0.5 { 2.0 * t * t } else { 1.0 - (2.0 - 2.0 * t).powi(2) / 2.0 },
+            Easing::CubicIn => t.powi(3),
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => if t < 0.5 { 4.0 * t.powi(3) } else { 1.0 - (2.0 - 2.0 * t).powi(3) / 2.0 },
+            Easing::ElasticIn | Easing::ElasticOut | Easing::ElasticInOut if t == 0.0 || t == 1.0 => t,
+            Easing::ElasticIn => -(2.0f32).powf(10.0 * t - 10.0) * ((t * 10.0 - 10.75) * (2.0 * PI / 3.0)).sin(),
+            Easing::ElasticOut => (2.0f32).powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0,
+            Easing::ElasticInOut => {
+                let wave = ((20.0 * t - 11.125) * (2.0 * PI / 4.5)).sin();
+                if t < 0.5 { -(2.0f32).powf(20.0 * t - 10.0) * wave / 2.0 } else { (2.0f32).powf(10.0 - 20.0 * t) * wave / 2.0 + 1.0 }
+            }
+            Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => if t < 0.5 { (1.0 - bounce_out(1.0 - 2.0 * t)) / 2.0 } else { (1.0 + bounce_out(2.0 * t - 1.0)) / 2.0 }
+        }
+    }
+}
+
+fn bounce_out(t : f32) -> f32 {
+    const N : f32 = 7.5625;
+    const D : f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984_375
+    }
+}
+
+/// A value that can be tweened: anything that can be blended between two values.
+pub trait Tweenable : Copy {
+    /// The value `amount` of the way from `self` to `to`. `amount` may be a little outside 0..1
+    /// for elastic easings.
+    fn blend(&self, to : &Self, amount : f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    fn blend(&self, to : &f32, amount : f32) -> f32 { self + (to - self) * amount }
+}
+
+impl Tweenable for (f32, f32) {
+    fn blend(&self, to : &(f32, f32), amount : f32) -> (f32, f32) { (self.0.blend(&to.0, amount), self.1.blend(&to.1, amount)) }
+}
+
+impl Tweenable for RGB {
+    fn blend(&self, to : &RGB, amount : f32) -> RGB {
+        let mixed = self.lerp(*to, amount);
+        RGB::from_f32(mixed.r, mixed.g, mixed.b)
+    }
+}
+
+impl Tweenable for Point {
+    fn blend(&self, to : &Point, amount : f32) -> Point {
+        Point::new((self.x as f32).blend(&(to.x as f32), amount).round() as i32, (self.y as f32).blend(&(to.y as f32), amount).round() as i32)
+    }
+}
+
+/// One leg of a tween.
+struct Segment<T : Tweenable> {
+    to : T,
+    duration_ms : f32,
+    easing : Easing
+}
+
+/// Moves a value (a number, color or position) smoothly to a target over time. Create one with
+/// `Tween::new`, chain more legs with `then`, and call `update` with `frame_time_ms` every tick
+/// to get the current value. Use it for menus sliding in with `set_offset`, color fades and
+/// camera moves.
+pub struct Tween<T : Tweenable> {
+    from : T,
+    segments : Vec<Segment<T>>,
+    delay_ms : f32,
+    elapsed_ms : f32,
+    finished : bool,
+    on_complete : Option<Box<dyn FnMut()>>
+}
+
+impl<T : Tweenable> Tween<T> {
+    /// A tween from one value to another, taking `duration_ms` milliseconds.
+    pub fn new(from : T, to : T, duration_ms : f32, easing : Easing) -> Tween<T> {
+        Tween{ from, segments : vec![Segment{ to, duration_ms, easing }], delay_ms : 0.0, elapsed_ms : 0.0, finished : false, on_complete : None }
+    }
+
+    /// Adds another leg, from where the previous one ended to `to`.
+    pub fn then(mut self, to : T, duration_ms : f32, easing : Easing) -> Tween<T> {
+        self.segments.push(Segment{ to, duration_ms, easing });
+        self
+    }
+
+    /// Holds the starting value for a while before moving, to stagger several tweens.
+    pub fn with_delay(mut self, delay_ms : f32) -> Tween<T> {
+        self.delay_ms = delay_ms;
+        self
+    }
+
+    /// Calls a function once, on the update that finishes the tween.
+    pub fn on_complete<F : FnMut() + 'static>(mut self, callback : F) -> Tween<T> {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// The total time the tween takes, including the delay.
+    pub fn duration_ms(&self) -> f32 {
+        self.delay_ms + self.segments.iter().map(|s| s.duration_ms).sum::<f32>()
+    }
+
+    /// True once the tween has reached its final value.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Starts the tween again from the beginning.
+    pub fn reset(&mut self) {
+        self.elapsed_ms = 0.0;
+        self.finished = false;
+    }
+
+    /// Moves time on by `frame_time_ms`, and returns the new value.
+    pub fn update(&mut self, frame_time_ms : f32) -> T {
+        self.elapsed_ms += frame_time_ms;
+        if !self.finished && self.elapsed_ms >= self.duration_ms() {
+            self.finished = true;
+            if let Some(callback) = self.on_complete.as_mut() {
+                callback();
+            }
+        }
+        self.value()
+    }
+
+    /// The value at the current time.
+    pub fn value(&self) -> T {
+        let mut time = self.elapsed_ms - self.delay_ms;
+        let mut start = self.from;
+        for segment in self.segments.iter() {
+            if time < segment.duration_ms {
+                let progress = if time <= 0.0 { 0.0 } else { time / segment.duration_ms };
+                return start.blend(&segment.to, segment.easing.apply(progress));
+            }
+            time -= segment.duration_ms;
+            start = segment.to;
+        }
+        start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Easing, Tween};
+    use super::super::{Point, RGB};
+    use std::rc::Rc;
+    use std::cell::Cell;
+
+    #[test]
+    // Every easing starts at 0 and ends at 1; the "In" curves lag behind linear at the midpoint.
+    fn test_easings() {
+        let all = [Easing::Linear, Easing::QuadIn, Easing::QuadOut, Easing::QuadInOut, Easing::CubicIn, Easing::CubicOut,
+            Easing::CubicInOut, Easing::ElasticIn, Easing::ElasticOut, Easing::ElasticInOut, Easing::BounceIn,
+            Easing::BounceOut, Easing::BounceInOut];
+        for easing in all.iter() {
+            assert!(easing.apply(0.0).abs() < 0.001, "{:?}", easing);
+            assert!((easing.apply(1.0) - 1.0).abs() < 0.001, "{:?}", easing);
+        }
+        assert_eq!(Easing::QuadIn.apply(0.5), 0.25);
+        assert_eq!(Easing::CubicOut.apply(0.5), 0.875);
+        assert_eq!(Easing::QuadInOut.apply(0.5), 0.5);
+        assert!(Easing::ElasticOut.apply(0.2) > 1.0);
+        assert!((Easing::BounceOut.apply(1.0 / 2.75) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    // Legs run one after another, after the delay.
+    fn test_sequence_and_delay() {
+        let mut tween = Tween::new(0.0f32, 10.0, 100.0, Easing::Linear).then(20.0, 100.0, Easing::Linear).with_delay(50.0);
+        assert_eq!(tween.duration_ms(), 250.0);
+        assert_eq!(tween.update(50.0), 0.0);
+        assert_eq!(tween.update(50.0), 5.0);
+        assert_eq!(tween.update(100.0), 15.0);
+        assert!(!tween.is_finished());
+        assert_eq!(tween.update(100.0), 20.0);
+        assert!(tween.is_finished());
+        tween.reset();
+        assert_eq!(tween.value(), 0.0);
+    }
+
+    #[test]
+    // Colors and points can be tweened, and the callback runs once when the tween finishes.
+    fn test_values_and_callback() {
+        let mut color = Tween::new(RGB::from_f32(0.0, 0.0, 0.0), RGB::from_f32(1.0, 1.0, 1.0), 100.0, Easing::Linear);
+        assert!(color.update(50.0) == RGB::from_f32(0.5, 0.5, 0.5));
+
+        let done = Rc::new(Cell::new(0));
+        let counter = done.clone();
+        let mut point = Tween::new(Point::new(0, 0), Point::new(10, -4), 100.0, Easing::Linear).on_complete(move || counter.set(counter.get() + 1));
+        assert_eq!(point.update(50.0), Point::new(5, -2));
+        assert_eq!(point.update(60.0), Point::new(10, -4));
+        point.update(10.0);
+        assert_eq!(done.get(), 1);
+    }
+}
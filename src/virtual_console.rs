@@ -0,0 +1,243 @@
+use super::{Console, Tile, TileChange, RGB, color, Font, Shader, Point, Rect, rex::XpLayer, gui_helpers, gl};
+use super::tile_buffer::TileBuffer;
+
+/// An off-screen console of any size, stored in memory with no OpenGL resources. Draw to it
+/// with the usual `Console` functions (for example, a whole world map), and then `blit` the
+/// part you want to see onto a displayed console.
+pub struct VirtualConsole {
+    pub width :u32,
+    pub height: u32,
+
+    // Private
+    buffer: TileBuffer
+}
+
+impl VirtualConsole {
+    /// Creates a virtual console, cleared to white-on-black spaces.
+    pub fn new(width:u32, height: u32) -> VirtualConsole {
+        VirtualConsole{
+            width,
+            height,
+            buffer: TileBuffer::new(width, height)
+        }
+    }
+
+    /// Provides read access to the console's cells, in row-major order starting at the top-left.
+    pub fn tiles(&self) -> &[Tile] {
+        self.buffer.tiles()
+    }
+
+    /// Copies the `source` region of this console onto `target`, with its top-left corner at `dest`.
+    /// Anything that falls off either console is skipped.
+    pub fn blit(&self, source : Rect, target : &mut dyn Console, dest : Point) {
+        self.blit_ex(source, target, dest, None, None);
+    }
+
+    /// Copies the `source` region of this console onto `target`, with its top-left corner at `dest`.
+    /// Cells whose background matches `transparent` are skipped, leaving whatever is already on
+    /// the target. If a `color_transform` is given, it is applied to each cell's foreground and
+    /// background on the way (e.g. to darken remembered-but-not-visible map areas).
+    pub fn blit_ex(&self, source : Rect, target : &mut dyn Console, dest : Point,
+        transparent : Option<RGB>, color_transform : Option<&dyn Fn(RGB) -> RGB>)
+    {
+        let source = source.clip_to(&Rect::with_size(0, 0, self.width as i32, self.height as i32));
+        let target_area = Rect::with_size(0, 0, target.width() as i32, target.height() as i32);
+
+        for y in source.y1 .. source.y2 {
+            for x in source.x1 .. source.x2 {
+                let tx = dest.x + x - source.x1;
+                let ty = dest.y + y - source.y1;
+                if !target_area.point_in_rect(Point::new(tx, ty)) { continue; }
+
+                let tile = self.tiles()[self.at(x, y)];
+                if let Some(key) = transparent {
+                    if tile.bg == key { continue; }
+                }
+                match color_transform {
+                    Some(transform) => target.set(tx, ty, transform(tile.fg), transform(tile.bg), tile.glyph),
+                    None => target.set(tx, ty, tile.fg, tile.bg, tile.glyph)
+                }
+            }
+        }
+    }
+}
+
+impl Console for VirtualConsole {
+    /// Nothing to do: there is no OpenGL backing.
+    fn rebuild_if_dirty(&mut self, _gl : &gl::Gles2) {}
+
+    /// Nothing to do: virtual consoles are displayed by blitting them to another console.
+    fn gl_draw(&mut self, _font : &Font, _shader : &Shader, _gl : &gl::Gles2) {}
+
+    /// Translate an x/y into an array index. Virtual consoles are stored top-down.
+    fn at(&self, x:i32, y:i32) -> usize {
+        self.buffer.at(x, y)
+    }
+
+    /// Returns the console width, in characters.
+    fn width(&self) -> u32 { self.width }
+
+    /// Returns the console height, in characters.
+    fn height(&self) -> u32 { self.height }
+
+    /// Reads back the cell at x/y.
+    fn get(&self, x:i32, y:i32) -> Option<Tile> {
+        self.buffer.get(x, y)
+    }
+
+    /// Clears the console.
+    fn cls(&mut self) {
+        self.cls_bg(RGB::named(color::BLACK));
+    }
+
+    /// Clears the console with a background color.
+    fn cls_bg(&mut self, background : RGB) {
+        self.buffer.cls_bg(background);
+    }
+
+    /// Prints a string at x/y.
+    fn print(&mut self, x:i32, y:i32, output:&str) {
+        self.buffer.print(x, y, output);
+    }
+
+    /// Prints a string at x/y, with foreground and background colors.
+    fn print_color(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, output:&str) {
+        let bytes = super::string_to_cp437(output);
+        for (i, glyph) in bytes.iter().enumerate() {
+            self.set(x + i as i32, y, fg, bg, *glyph);
+        }
+    }
+
+    /// Sets a single cell in the console
+    fn set(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, glyph:u8) {
+        self.buffer.set(x, y, fg, bg, glyph);
+    }
+
+    /// Sets a single cell in the console's background
+    fn set_bg(&mut self, x:i32, y:i32, bg:RGB) {
+        self.buffer.set_bg(x, y, bg);
+    }
+
+    /// Fills a rectangle with a glyph and colors
+    fn fill_region(&mut self, sx:i32, sy:i32, width:i32, height:i32, fg:RGB, bg:RGB, glyph:u8) {
+        gui_helpers::fill_region(self, sx, sy, width, height, fg, bg, glyph);
+    }
+
+    /// Scrolls the console's contents by dx/dy cells
+    fn scroll(&mut self, dx:i32, dy:i32) {
+        let (width, height) = (self.width as i32, self.height as i32);
+        gui_helpers::scroll_region(self, 0, 0, width, height, dx, dy);
+    }
+
+    /// Draws a box, starting at x/y with the extents width/height using CP437 line characters
+    fn draw_box(&mut self, sx:i32, sy:i32, width:i32, height:i32, fg: RGB, bg: RGB) {
+        gui_helpers::draw_box(self, sx, sy, width, height, fg, bg);
+    }
+
+    /// Draws a box, starting at x/y with the extents width/height using CP437 double line characters
+    fn draw_box_double(&mut self, sx:i32, sy:i32, width:i32, height:i32, fg: RGB, bg: RGB) {
+        gui_helpers::draw_box_double(self, sx, sy, width, height, fg, bg);
+    }
+
+    /// Draws a horizontal progress bar
+    fn draw_bar_horizontal(&mut self, sx:i32, sy:i32, width:i32, n:i32, max:i32, fg:RGB, bg: RGB) {
+        gui_helpers::draw_bar_horizontal(self, sx, sy, width, n, max, fg, bg);
+    }
+
+    /// Draws a vertical progress bar
+    fn draw_bar_vertical(&mut self, sx:i32, sy:i32, height:i32, n:i32, max:i32, fg:RGB, bg: RGB) {
+        gui_helpers::draw_bar_vertical(self, sx, sy, height, n, max, fg, bg);
+    }
+
+    /// Prints text, centered to the whole console width, at vertical location y.
+    fn print_centered(&mut self, y:i32, text:&str) {
+        self.print((self.width as i32 / 2) - (text.to_string().len() as i32/2), y, text);
+    }
+
+    /// Prints text in color, centered to the whole console width, at vertical location y.
+    fn print_color_centered(&mut self, y:i32, fg:RGB, bg:RGB, text:&str) {
+        self.print_color((self.width as i32 / 2) - (text.to_string().len() as i32/2), y, fg, bg, text);
+    }
+
+    /// Saves the layer to an XpFile structure
+    fn to_xp_layer(&self) -> XpLayer {
+        self.buffer.to_xp_layer()
+    }
+
+    /// Virtual consoles aren't rendered directly, so offsets are ignored.
+    fn set_offset(&mut self, _x : f32, _y : f32) {}
+
+    /// Virtual consoles don't keep a previous frame.
+    fn end_frame(&mut self) {}
+
+    /// Virtual consoles don't track changes between frames.
+    fn frame_diff(&self) -> Option<&[TileChange]> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VirtualConsole;
+    use super::super::{Console, Point, Rect, RGB, color};
+
+    #[test]
+    // A window into a large console lands at the destination, clipped to the target.
+    fn test_blit_window() {
+        let mut world = VirtualConsole::new(500, 500);
+        world.set(250, 250, RGB::named(color::YELLOW), RGB::named(color::BLACK), b'@');
+        world.set(252, 251, RGB::named(color::WHITE), RGB::named(color::BLACK), b'#');
+
+        let mut screen = VirtualConsole::new(10, 5);
+        world.blit(Rect::with_size(245, 248, 10, 10), &mut screen, Point::new(0, 0));
+        assert_eq!(screen.get(5, 2).unwrap().glyph, b'@');
+        assert_eq!(screen.get(7, 3).unwrap().glyph, b'#');
+
+        // Only the world's last 5x5 cells exist, and the first two columns land off the target
+        world.print(495, 495, "abcde");
+        world.print(495, 499, "vwxyz");
+        let mut edge = VirtualConsole::new(10, 5);
+        edge.cls_bg(RGB::named(color::BLUE));
+        world.blit(Rect::with_size(495, 495, 10, 10), &mut edge, Point::new(-2, 0));
+        let rows : Vec<String> = (0 .. 5).map(|y| (0 .. 10).map(|x| edge.get(x, y).unwrap().glyph as char).collect()).collect();
+        assert_eq!(rows[0], "cde       ");
+        assert_eq!(rows[4], "xyz       ");
+        for y in 0 .. 5 {
+            assert!(edge.get(2, y).unwrap().bg == RGB::named(color::BLACK));
+            assert!(edge.get(3, y).unwrap().bg == RGB::named(color::BLUE));
+        }
+    }
+
+    #[test]
+    // Cells with the transparent background are skipped; others pass through the transform.
+    fn test_blit_key_and_transform() {
+        let mut sprite = VirtualConsole::new(3, 1);
+        sprite.cls_bg(RGB::named(color::MAGENTA));
+        sprite.set(1, 0, RGB::named(color::WHITE), RGB::named(color::BLUE), b'X');
+
+        let mut screen = VirtualConsole::new(3, 1);
+        screen.print(0, 0, "abc");
+        let darken = |c : RGB| c * 0.5;
+        sprite.blit_ex(Rect::with_size(0, 0, 3, 1), &mut screen, Point::new(0, 0), Some(RGB::named(color::MAGENTA)), Some(&darken));
+
+        assert_eq!(screen.get(0, 0).unwrap().glyph, b'a');
+        assert_eq!(screen.get(2, 0).unwrap().glyph, b'c');
+        let cell = screen.get(1, 0).unwrap();
+        assert_eq!(cell.glyph, b'X');
+        assert!(cell.bg == RGB::named(color::BLUE) * 0.5);
+    }
+
+    #[test]
+    // Scrolling moves the contents and clears the cells left behind.
+    fn test_scroll() {
+        let mut console = VirtualConsole::new(4, 3);
+        console.print(0, 0, "abcd");
+        console.print(0, 2, "wxyz");
+        console.scroll(1, -1);
+        assert_eq!(console.get(0, 1).unwrap().glyph, 32);
+        assert_eq!(console.get(1, 1).unwrap().glyph, b'w');
+        assert_eq!(console.get(3, 1).unwrap().glyph, b'y');
+        assert_eq!(console.get(1, 0).unwrap().glyph, 32);
+        assert_eq!(console.get(1, 2).unwrap().glyph, 32);
+    }
+}
@@ -0,0 +1,214 @@
+use super::Ui;
+use super::super::{Console, Point, Rect, RGB, VirtualKeyCode, letter_to_option, string_to_cp437};
+
+/// What the player did with a menu this frame.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum MenuResult { Selected(usize), Cancel, NoResponse }
+
+/// One line in a menu.
+pub struct MenuItem {
+    pub label: String,
+    pub fg: RGB
+}
+
+/// The page a menu is showing. Keep it between frames.
+#[derive(Copy, Clone, Default)]
+pub struct MenuState {
+    pub page: usize
+}
+
+/// An inventory-style menu: a bordered box with a title, and items prefixed `(a)`, `(b)` and so
+/// on. Pressing a letter (or clicking an item) selects it; Escape, or clicking outside the box,
+/// cancels. Menus with more than 26 items (or `page_size`) are split into pages, changed with
+/// PageUp/PageDown or the cursor keys. Build one each frame and draw it with `Ui::menu`.
+pub struct Menu {
+    title: String,
+    items: Vec<MenuItem>,
+    position: Option<Point>,
+    page_size: usize
+}
+
+impl Menu {
+    /// Starts a menu with a title.
+    pub fn new<S: ToString>(title : S) -> Menu {
+        Menu{ title: title.to_string(), items: Vec::new(), position: None, page_size: 26 }
+    }
+
+    /// Adds an item in white.
+    pub fn item<S: ToString>(&mut self, label : S) -> &mut Self {
+        self.item_color(label, RGB::from_f32(1.0, 1.0, 1.0))
+    }
+
+    /// Adds an item in a color.
+    pub fn item_color<S: ToString>(&mut self, label : S, fg : RGB) -> &mut Self {
+        self.items.push(MenuItem{ label: label.to_string(), fg });
+        self
+    }
+
+    /// Places the top-left corner of the box; by default it is centered on the console.
+    pub fn at(&mut self, x : i32, y : i32) -> &mut Self {
+        self.position = Some(Point::new(x, y));
+        self
+    }
+
+    /// Sets how many items are shown per page (at most 26, one per letter).
+    pub fn page_size(&mut self, size : usize) -> &mut Self {
+        self.page_size = usize::max(1, usize::min(26, size));
+        self
+    }
+
+    /// The items, in the order they were added.
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    /// The number of pages needed to show every item.
+    pub fn pages(&self) -> usize {
+        usize::max(1, self.items.len().div_ceil(self.page_size))
+    }
+}
+
+impl<'a> Ui<'a> {
+    /// Draws a lettered menu and handles its input.
+    pub fn menu(&mut self, menu : &Menu, state : &mut MenuState) -> MenuResult {
+        let pages = menu.pages();
+        if self.take_key(VirtualKeyCode::PageDown) || self.take_key(VirtualKeyCode::Right) {
+            state.page += 1;
+        }
+        if (self.take_key(VirtualKeyCode::PageUp) || self.take_key(VirtualKeyCode::Left)) && state.page > 0 {
+            state.page -= 1;
+        }
+        state.page = usize::min(state.page, pages - 1);
+
+        let first = state.page * menu.page_size;
+        let shown = &menu.items[first .. usize::min(menu.items.len(), first + menu.page_size)];
+
+        // Size the box to fit the widest line, including the title, and the bottom line with both
+        // "ESC to cancel" and the page footer
+        let footer = if pages > 1 { format!(" Page {}/{} ", state.page + 1, pages) } else { String::new() };
+        let label_width = shown.iter().map(|i| string_to_cp437(&i.label).len() as i32).max().unwrap_or(0) + 4;
+        let bottom_width = if pages > 1 { 13 + footer.len() as i32 + 1 } else { 0 };
+        let width = *[label_width, string_to_cp437(&menu.title).len() as i32 + 2, bottom_width, 14].iter().max().unwrap() + 2;
+        let height = i32::max(1, shown.len() as i32) + 1;
+        let origin = menu.position.unwrap_or_else(|| Point::new(
+            (self.console.width() as i32 - width) / 2,
+            (self.console.height() as i32 - height) / 2
+        ));
+
+        self.console.draw_box(origin.x, origin.y, width, height, self.theme.border, self.theme.bg);
+        self.console.print_color(origin.x + 2, origin.y, self.theme.hover_fg, self.theme.bg, &menu.title);
+        self.console.print_color(origin.x + 2, origin.y + height, self.theme.fg, self.theme.bg, "ESC to cancel");
+        if pages > 1 {
+            self.console.print_color(origin.x + width - footer.len() as i32, origin.y + height, self.theme.fg, self.theme.bg, &footer);
+        }
+
+        let mut result = MenuResult::NoResponse;
+        for (i, item) in shown.iter().enumerate() {
+            let y = origin.y + 1 + i as i32;
+            let row = Rect::with_size(origin.x + 1, y, width - 1, 1);
+            let bg = if self.hovered(&row) { self.theme.hover_bg } else { self.theme.bg };
+            self.console.fill_region(row.x1, y, row.width(), 1, item.fg, bg, 32);
+            self.console.print_color(origin.x + 1, y, self.theme.fg, bg, &format!("({})", (b'a' + i as u8) as char));
+            self.console.print_color(origin.x + 5, y, item.fg, bg, &item.label);
+            if self.clicked(&row) {
+                result = MenuResult::Selected(first + i);
+            }
+        }
+
+        if result == MenuResult::NoResponse {
+            if let Some(key) = self.input.key {
+                let option = letter_to_option(key);
+                if option >= 0 && (option as usize) < shown.len() && !self.blocked {
+                    self.input.key = None;
+                    result = MenuResult::Selected(first + option as usize);
+                }
+            }
+        }
+        if result == MenuResult::NoResponse {
+            let outside = self.input.left_click && !self.hovered(&Rect::with_size(origin.x, origin.y, width + 1, height + 1));
+            if self.take_key(VirtualKeyCode::Escape) || (outside && !self.blocked) {
+                self.input.left_click = false;
+                result = MenuResult::Cancel;
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Menu, MenuState, MenuResult};
+    use super::super::{Ui, test_input as input};
+    use super::super::super::{Console, VirtualConsole, VirtualKeyCode};
+
+    fn inventory(count : usize) -> Menu {
+        let mut menu = Menu::new("Inventory");
+        for i in 0 .. count {
+            menu.item(format!("Item {}", i));
+        }
+        menu.at(0, 0);
+        menu
+    }
+
+    #[test]
+    // Letters pick items on the current page, and the page is offset into the whole list.
+    fn test_letters_and_paging() {
+        let mut console = VirtualConsole::new(80, 50);
+        let menu = inventory(30);
+        let mut state = MenuState::default();
+        assert_eq!(menu.pages(), 2);
+
+        let mut ui = Ui::with_console(&mut console, input(-1, -1, false, Some(VirtualKeyCode::C)));
+        assert_eq!(ui.menu(&menu, &mut state), MenuResult::Selected(2));
+
+        let mut ui = Ui::with_console(&mut console, input(-1, -1, false, Some(VirtualKeyCode::PageDown)));
+        assert_eq!(ui.menu(&menu, &mut state), MenuResult::NoResponse);
+        assert_eq!(state.page, 1);
+
+        let mut ui = Ui::with_console(&mut console, input(-1, -1, false, Some(VirtualKeyCode::D)));
+        assert_eq!(ui.menu(&menu, &mut state), MenuResult::Selected(29));
+        let mut ui = Ui::with_console(&mut console, input(-1, -1, false, Some(VirtualKeyCode::E)));
+        assert_eq!(ui.menu(&menu, &mut state), MenuResult::NoResponse);
+        drop(ui);
+        assert_eq!(console.get(2, 1).unwrap().glyph, b'a');
+        assert_eq!(console.get(5, 1).unwrap().glyph, b'I');
+    }
+
+    #[test]
+    // Clicking an item selects it; clicking outside or Escape cancels.
+    fn test_mouse_and_cancel() {
+        let mut console = VirtualConsole::new(80, 50);
+        let menu = inventory(3);
+        let mut state = MenuState::default();
+
+        let mut ui = Ui::with_console(&mut console, input(6, 2, true, None));
+        assert_eq!(ui.menu(&menu, &mut state), MenuResult::Selected(1));
+
+        let mut ui = Ui::with_console(&mut console, input(60, 40, true, None));
+        assert_eq!(ui.menu(&menu, &mut state), MenuResult::Cancel);
+
+        let mut ui = Ui::with_console(&mut console, input(-1, -1, false, Some(VirtualKeyCode::Escape)));
+        assert_eq!(ui.menu(&menu, &mut state), MenuResult::Cancel);
+    }
+
+    #[test]
+    // On a paged menu, the page footer fits beside "ESC to cancel" without overwriting it.
+    fn test_footer_fits() {
+        let mut console = VirtualConsole::new(80, 50);
+        let mut menu = Menu::new("Bag");
+        for i in 0 .. 30 {
+            menu.item(format!("{}", i));
+        }
+        menu.at(0, 0);
+        let mut state = MenuState::default();
+        let mut ui = Ui::with_console(&mut console, input(-1, -1, false, None));
+        ui.menu(&menu, &mut state);
+        drop(ui);
+
+        // Box-drawing glyphs aren't ASCII, so show them as '-'
+        let bottom : String = (0 .. 80).map(|x| console.get(x, 27).unwrap().glyph).map(|g| if g < 128 { g as char } else { '-' }).collect();
+        assert!(bottom[2 ..].starts_with("ESC to cancel"), "{}", bottom);
+        assert!(bottom.contains(" Page 1/2 "));
+        assert!(bottom.find(" Page 1/2 ").unwrap() >= 15);
+    }
+}
@@ -0,0 +1,477 @@
+//! Immediate-mode widgets. Each frame, create a `Ui` and call a function per widget: it draws
+//! the widget and reports what the player did to it. Widgets that need to remember anything
+//! between frames (such as a list's selection) keep it in a small state struct that you own.
+
+mod text_input;
+mod menu;
+pub use self::text_input::{TextInput, TextInputResult};
+pub use self::menu::{Menu, MenuItem, MenuState, MenuResult};
+
+use super::{Console, DrawTarget, Point, Rect, Rltk, RGB, VirtualKeyCode, string_to_cp437, message_log::wrap_text};
+
+/// The mouse and keyboard state the widgets react to. A click or key is consumed by the first
+/// widget that uses it, so two widgets never respond to the same input.
+#[derive(Clone)]
+pub struct UiInput {
+    pub mouse: Point,
+    pub left_click: bool,
+    pub key: Option<VirtualKeyCode>,
+    pub wheel: i32,
+    pub chars: Vec<char>,
+    pub shift: bool
+}
+
+impl UiInput {
+    /// Takes this frame's input from the context, with the mouse in active-console coordinates.
+    pub fn from_context(ctx : &Rltk) -> UiInput {
+        let (x, y) = ctx.mouse_pos();
+        UiInput{
+            mouse: Point::new(x, y),
+            left_click: ctx.left_pressed,
+            key: ctx.key,
+            wheel: ctx.mouse_wheel,
+            chars: ctx.chars.clone(),
+            shift: ctx.shift
+        }
+    }
+}
+
+/// The colors widgets are drawn with.
+#[derive(Copy, Clone)]
+pub struct Theme {
+    pub fg: RGB,
+    pub bg: RGB,
+    pub hover_fg: RGB,
+    pub hover_bg: RGB,
+    pub active_fg: RGB,
+    pub active_bg: RGB,
+    pub border: RGB
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme{
+            fg: RGB::from_f32(0.8, 0.8, 0.8),
+            bg: RGB::from_f32(0.0, 0.0, 0.0),
+            hover_fg: RGB::from_f32(1.0, 1.0, 1.0),
+            hover_bg: RGB::from_f32(0.2, 0.2, 0.4),
+            active_fg: RGB::from_f32(0.0, 0.0, 0.0),
+            active_bg: RGB::from_f32(0.0, 1.0, 1.0),
+            border: RGB::from_f32(0.5, 0.5, 0.5)
+        }
+    }
+}
+
+/// Selection and scroll position of a `list`.
+#[derive(Copy, Clone, Default)]
+pub struct ListState {
+    pub selected: usize,
+    pub scroll: usize
+}
+
+/// Scroll position of a `scroll_panel`.
+#[derive(Copy, Clone, Default)]
+pub struct PanelState {
+    pub scroll: i32
+}
+
+/// Draws widgets onto a console and feeds them input.
+pub struct Ui<'a> {
+    // Everything is drawn clipped to the console, so widgets placed partly off it can't panic
+    console: DrawTarget<'a>,
+    pub input: UiInput,
+    pub theme: Theme,
+    blocked: bool
+}
+
+impl<'a> Ui<'a> {
+    /// Draws to the context's active console, using its mouse and keyboard state.
+    pub fn new(ctx : &'a mut Rltk) -> Ui<'a> {
+        let input = UiInput::from_context(ctx);
+        Ui{ console: DrawTarget::new(ctx), input, theme: Theme::default(), blocked: false }
+    }
+
+    /// Draws to any console, with input you supply (e.g. with the mouse translated into that
+    /// console's coordinates).
+    pub fn with_console(console : &'a mut dyn Console, input : UiInput) -> Ui<'a> {
+        Ui{ console: DrawTarget::new(console), input, theme: Theme::default(), blocked: false }
+    }
+
+    /// The console being drawn to, for anything that isn't a widget.
+    pub fn console(&mut self) -> &mut dyn Console {
+        &mut self.console
+    }
+
+    /// Stops widgets reacting to input until a `dialog` is drawn. Call this before drawing the
+    /// rest of the screen while a modal dialog is open, so clicks can't reach what's behind it.
+    pub fn block_input(&mut self) {
+        self.blocked = true;
+    }
+
+    fn hovered(&self, area : &Rect) -> bool {
+        !self.blocked && area.point_in_rect(self.input.mouse)
+    }
+
+    fn clicked(&mut self, area : &Rect) -> bool {
+        if self.input.left_click && self.hovered(area) {
+            self.input.left_click = false;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn take_key(&mut self, key : VirtualKeyCode) -> bool {
+        if !self.blocked && self.input.key == Some(key) {
+            self.input.key = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn take_wheel(&mut self, area : &Rect) -> i32 {
+        if self.hovered(area) {
+            let wheel = self.input.wheel;
+            self.input.wheel = 0;
+            wheel
+        } else {
+            0
+        }
+    }
+
+    /// A clickable button, drawn as `[ label ]`. Returns true when clicked.
+    pub fn button(&mut self, x:i32, y:i32, label:&str) -> bool {
+        let text = format!("[ {} ]", label);
+        let area = Rect::with_size(x, y, string_to_cp437(&text).len() as i32, 1);
+        let (fg, bg) = if self.hovered(&area) { (self.theme.hover_fg, self.theme.hover_bg) } else { (self.theme.fg, self.theme.bg) };
+        self.console.print_color(x, y, fg, bg, &text);
+        self.clicked(&area)
+    }
+
+    /// A checkbox, drawn as `[x] label`. Clicking it toggles `value`; returns true if it changed.
+    pub fn checkbox(&mut self, x:i32, y:i32, label:&str, value:&mut bool) -> bool {
+        let text = format!("[{}] {}", if *value { 'x' } else { ' ' }, label);
+        let area = Rect::with_size(x, y, string_to_cp437(&text).len() as i32, 1);
+        let (fg, bg) = if self.hovered(&area) { (self.theme.hover_fg, self.theme.hover_bg) } else { (self.theme.fg, self.theme.bg) };
+        self.console.print_color(x, y, fg, bg, &text);
+        if self.clicked(&area) {
+            *value = !*value;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// A scrolling list of items. Up/Down (or the mouse) moves the selection and the wheel
+    /// scrolls. Returns the index of an item when it is clicked or Enter is pressed.
+    pub fn list<S: ToString>(&mut self, area:Rect, items:&[S], state:&mut ListState) -> Option<usize> {
+        if items.is_empty() || area.is_empty() { return None; }
+        let height = area.height() as usize;
+        let overflow = items.len() > height;
+        let row_width = if overflow { area.width() - 1 } else { area.width() };
+        let mut result = None;
+
+        state.selected = usize::min(state.selected, items.len() - 1);
+        if self.take_key(VirtualKeyCode::Up) && state.selected > 0 { state.selected -= 1; }
+        if self.take_key(VirtualKeyCode::Down) && state.selected + 1 < items.len() { state.selected += 1; }
+        if self.take_key(VirtualKeyCode::Return) { result = Some(state.selected); }
+
+        // Keep the selection in view, then let the wheel scroll freely
+        if state.selected < state.scroll { state.scroll = state.selected; }
+        if state.selected >= state.scroll + height { state.scroll = state.selected + 1 - height; }
+        let wheel = self.take_wheel(&area);
+        state.scroll = i32::max(0, state.scroll as i32 - wheel) as usize;
+        state.scroll = usize::min(state.scroll, items.len().saturating_sub(height));
+
+        for row in 0 .. height {
+            let index = state.scroll + row;
+            let y = area.y1 + row as i32;
+            let row_area = Rect::with_size(area.x1, y, row_width, 1);
+            if index >= items.len() {
+                self.console.fill_region(area.x1, y, row_width, 1, self.theme.fg, self.theme.bg, 32);
+                continue;
+            }
+            if self.clicked(&row_area) {
+                state.selected = index;
+                result = Some(index);
+            }
+
+            let (fg, bg) = if index == state.selected {
+                (self.theme.active_fg, self.theme.active_bg)
+            } else if self.hovered(&row_area) {
+                (self.theme.hover_fg, self.theme.hover_bg)
+            } else {
+                (self.theme.fg, self.theme.bg)
+            };
+            let mut text : Vec<u8> = string_to_cp437(items[index].to_string());
+            text.resize(row_width as usize, 32);
+            for (i, glyph) in text.iter().enumerate() {
+                self.console.set(area.x1 + i as i32, y, fg, bg, *glyph);
+            }
+        }
+
+        if overflow {
+            self.scrollbar(area.x2 - 1, area.y1, area.height(), state.scroll as i32, items.len() as i32);
+        }
+        result
+    }
+
+    /// A panel whose content is taller than the area it is shown in. `draw` renders the content
+    /// in local coordinates (0,0 is the top of the content) and is clipped to the panel; the
+    /// wheel scrolls it when the mouse is over the panel.
+    pub fn scroll_panel<F: FnOnce(&mut DrawTarget)>(&mut self, area:Rect, content_height:i32, state:&mut PanelState, draw:F) {
+        if area.is_empty() { return; }
+        let overflow = content_height > area.height();
+        let content_width = if overflow { area.width() - 1 } else { area.width() };
+
+        let wheel = self.take_wheel(&area);
+        state.scroll = i32::max(0, i32::min(state.scroll - wheel, content_height - area.height()));
+
+        self.console.fill_region(area.x1, area.y1, area.width(), area.height(), self.theme.fg, self.theme.bg, 32);
+        {
+            let mut target = DrawTarget::new(&mut self.console);
+            target.push_clip(Rect::with_size(area.x1, area.y1, content_width, area.height()));
+            target.push(Rect::with_size(area.x1, area.y1 - state.scroll, content_width, i32::max(content_height, area.height())));
+            draw(&mut target);
+        }
+
+        if overflow {
+            self.scrollbar(area.x2 - 1, area.y1, area.height(), state.scroll, content_height);
+        }
+    }
+
+    /// Draws a vertical scrollbar for content of `total` lines, `position` lines down.
+    fn scrollbar(&mut self, x:i32, y:i32, height:i32, position:i32, total:i32) {
+        let thumb_size = i32::max(1, height * height / i32::max(1, total));
+        let travel = i32::max(1, total - height);
+        let thumb_pos = (height - thumb_size) * position / travel;
+        for i in 0 .. height {
+            let glyph = if i >= thumb_pos && i < thumb_pos + thumb_size { 219 } else { 176 };
+            self.console.set(x, y + i, self.theme.border, self.theme.bg, glyph);
+        }
+    }
+
+    /// Shows a boxed tooltip next to the mouse while it is over `anchor`. Draw tooltips last,
+    /// so they appear on top of everything else.
+    pub fn tooltip(&mut self, anchor:Rect, lines:&[&str]) {
+        if lines.is_empty() || !self.hovered(&anchor) { return; }
+        let width = lines.iter().map(|l| string_to_cp437(l).len() as i32).max().unwrap_or(0) + 1;
+        let height = lines.len() as i32 + 1;
+
+        // Prefer below-right of the cursor, flipping if that would run off the console
+        let mut x = self.input.mouse.x + 1;
+        let mut y = self.input.mouse.y + 1;
+        if x + width >= self.console.width() as i32 { x = self.input.mouse.x - width - 1; }
+        if y + height >= self.console.height() as i32 { y = self.input.mouse.y - height - 1; }
+        x = i32::max(0, i32::min(x, self.console.width() as i32 - width - 1));
+        y = i32::max(0, i32::min(y, self.console.height() as i32 - height - 1));
+
+        self.console.draw_box(x, y, width, height, self.theme.border, self.theme.bg);
+        for (i, line) in lines.iter().enumerate() {
+            self.console.print_color(x + 1, y + 1 + i as i32, self.theme.hover_fg, self.theme.bg, line);
+        }
+    }
+
+    /// A single-line text field, `width` cells wide. Clicking the field focuses it and moves the
+    /// cursor; clicking elsewhere takes the focus away. While focused, it consumes the frame's
+    /// typed characters and editing keys.
+    pub fn text_input(&mut self, x:i32, y:i32, width:i32, field:&mut TextInput) -> TextInputResult {
+        if width < 1 { return TextInputResult::NoResponse; }
+        let area = Rect::with_size(x, y, width, 1);
+        if self.input.left_click && !self.blocked {
+            if self.clicked(&area) {
+                field.focused = true;
+                let position = field.scroll + (self.input.mouse.x - x) as usize;
+                field.set_cursor(position);
+            } else {
+                field.focused = false;
+            }
+        }
+
+        let result = if field.focused && !self.blocked {
+            field.handle_input(&mut self.input)
+        } else {
+            TextInputResult::NoResponse
+        };
+
+        // Scroll sideways so the cursor (which can sit just past the last character) stays visible
+        let width = width as usize;
+        if field.cursor() < field.scroll { field.scroll = field.cursor(); }
+        if field.cursor() >= field.scroll + width { field.scroll = field.cursor() + 1 - width; }
+
+        let text = string_to_cp437(field.text());
+        let selection = field.selection();
+        for i in 0 .. width {
+            let position = field.scroll + i;
+            let glyph = if position < text.len() { text[position] } else { 32 };
+            let selected = matches!(selection, Some((start, end)) if position >= start && position < end);
+            let (fg, bg) = if field.focused && position == field.cursor() {
+                (self.theme.active_fg, self.theme.hover_fg)
+            } else if selected {
+                (self.theme.active_fg, self.theme.active_bg)
+            } else if field.focused {
+                (self.theme.hover_fg, self.theme.hover_bg)
+            } else {
+                (self.theme.fg, self.theme.hover_bg)
+            };
+            self.console.set(x + i as i32, y, fg, bg, glyph);
+        }
+        result
+    }
+
+    /// A row of tabs. Clicking a tab selects it; returns true if the selection changed.
+    pub fn tab_bar(&mut self, x:i32, y:i32, tabs:&[&str], selected:&mut usize) -> bool {
+        let mut changed = false;
+        let mut cx = x;
+        for (i, tab) in tabs.iter().enumerate() {
+            let text = format!(" {} ", tab);
+            let area = Rect::with_size(cx, y, string_to_cp437(&text).len() as i32, 1);
+            if self.clicked(&area) && *selected != i {
+                *selected = i;
+                changed = true;
+            }
+            let (fg, bg) = if *selected == i {
+                (self.theme.active_fg, self.theme.active_bg)
+            } else if self.hovered(&area) {
+                (self.theme.hover_fg, self.theme.hover_bg)
+            } else {
+                (self.theme.fg, self.theme.bg)
+            };
+            self.console.print_color(cx, y, fg, bg, &text);
+            cx = area.x2;
+            if i + 1 < tabs.len() {
+                self.console.set(cx, y, self.theme.border, self.theme.bg, 179);
+                cx += 1;
+            }
+        }
+        changed
+    }
+
+    /// A modal dialog, centered on the console, with a wrapped message and a row of buttons.
+    /// Returns the index of the button that was clicked. Enter picks the first button and
+    /// Escape the last. Input blocked with `block_input` is released for the dialog.
+    pub fn dialog(&mut self, title:&str, message:&str, buttons:&[&str]) -> Option<usize> {
+        self.blocked = false;
+
+        let lines = wrap_text(message, 40);
+        let buttons_width : i32 = buttons.iter().map(|b| string_to_cp437(b).len() as i32 + 5).sum();
+        let text_width = lines.iter().map(|l| string_to_cp437(l).len() as i32).max().unwrap_or(0);
+        let width = *[text_width, buttons_width, string_to_cp437(title).len() as i32 + 2].iter().max().unwrap() + 3;
+        let height = lines.len() as i32 + 4;
+        let x = i32::max(0, (self.console.width() as i32 - width) / 2);
+        let y = i32::max(0, (self.console.height() as i32 - height) / 2);
+
+        self.console.draw_box_double(x, y, width, height, self.theme.border, self.theme.bg);
+        self.console.fill_region(x + 1, y + 1, width - 1, height - 1, self.theme.fg, self.theme.bg, 32);
+        self.console.print_color(x + 2, y, self.theme.hover_fg, self.theme.bg, &format!(" {} ", title));
+        for (i, line) in lines.iter().enumerate() {
+            self.console.print_color(x + 2, y + 1 + i as i32, self.theme.fg, self.theme.bg, line);
+        }
+
+        let mut result = None;
+        let mut bx = x + (width - buttons_width) / 2 + 1;
+        for (i, label) in buttons.iter().enumerate() {
+            if self.button(bx, y + height - 2, label) {
+                result = Some(i);
+            }
+            bx += string_to_cp437(label).len() as i32 + 5;
+        }
+
+        if !buttons.is_empty() {
+            if self.take_key(VirtualKeyCode::Return) { result = Some(0); }
+            if self.take_key(VirtualKeyCode::Escape) { result = Some(buttons.len() - 1); }
+        }
+        result
+    }
+}
+
+/// Input with the mouse at x/y and nothing typed, for the widget tests.
+#[cfg(test)]
+pub(crate) fn test_input(x:i32, y:i32, left_click:bool, key:Option<VirtualKeyCode>) -> UiInput {
+    UiInput{ mouse: Point::new(x, y), left_click, key, wheel: 0, chars: Vec::new(), shift: false }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Ui, ListState, PanelState, test_input as input};
+    use super::super::{Console, VirtualConsole, Rect, VirtualKeyCode, to_cp437};
+
+    #[test]
+    // A click is reported by the widget under the mouse, and only once.
+    fn test_button_and_checkbox() {
+        let mut console = VirtualConsole::new(40, 10);
+        let mut ui = Ui::with_console(&mut console, input(3, 1, true, None));
+        let mut checked = false;
+        assert!(!ui.button(0, 0, "OK"));
+        assert!(ui.checkbox(0, 1, "Sound", &mut checked));
+        assert!(checked);
+        assert!(!ui.checkbox(0, 1, "Sound", &mut checked));
+        drop(ui);
+        assert_eq!(console.get(1, 1).unwrap().glyph, b'x');
+    }
+
+    #[test]
+    // Keys move the selection and keep it in view; Enter activates it.
+    fn test_list_keyboard() {
+        let mut console = VirtualConsole::new(20, 10);
+        let items = ["a", "b", "c", "d", "e"];
+        let mut state = ListState::default();
+        for _ in 0 .. 3 {
+            let mut ui = Ui::with_console(&mut console, input(-1, -1, false, Some(VirtualKeyCode::Down)));
+            assert_eq!(ui.list(Rect::with_size(0, 0, 10, 2), &items, &mut state), None);
+        }
+        assert_eq!(state.selected, 3);
+        assert_eq!(state.scroll, 2);
+
+        let mut ui = Ui::with_console(&mut console, input(-1, -1, false, Some(VirtualKeyCode::Return)));
+        assert_eq!(ui.list(Rect::with_size(0, 0, 10, 2), &items, &mut state), Some(3));
+        drop(ui);
+        assert_eq!(console.get(0, 1).unwrap().glyph, b'd');
+    }
+
+    #[test]
+    // Panel content is drawn scrolled and clipped to the panel.
+    fn test_scroll_panel() {
+        let mut console = VirtualConsole::new(20, 10);
+        let mut state = PanelState{ scroll: 2 };
+        {
+            let mut ui = Ui::with_console(&mut console, input(-1, -1, false, None));
+            ui.scroll_panel(Rect::with_size(2, 2, 5, 3), 10, &mut state, |target| {
+                for line in 0 .. 10 {
+                    target.print(0, line, &line.to_string());
+                }
+            });
+        }
+        assert_eq!(console.get(2, 2).unwrap().glyph, b'2');
+        assert_eq!(console.get(2, 4).unwrap().glyph, b'4');
+        assert_eq!(console.get(2, 5).unwrap().glyph, 32);
+        assert_eq!(console.get(2, 1).unwrap().glyph, 32);
+    }
+
+    #[test]
+    // While input is blocked, only the dialog responds.
+    fn test_modal_dialog() {
+        let mut console = VirtualConsole::new(40, 20);
+        let mut ui = Ui::with_console(&mut console, input(1, 0, true, Some(VirtualKeyCode::Escape)));
+        ui.block_input();
+        assert!(!ui.button(0, 0, "Behind"));
+        assert_eq!(ui.dialog("Quit?", "Really quit?", &["Yes", "No"]), Some(1));
+    }
+
+    #[test]
+    // Tooltips and dialogs that don't fit are kept on the console, and clipped to it.
+    fn test_oversized_popups() {
+        let mut console = VirtualConsole::new(10, 5);
+        let mut ui = Ui::with_console(&mut console, input(0, 2, false, None));
+        ui.tooltip(Rect::with_size(0, 0, 10, 5), &["Much too wide"]);
+        assert_eq!(console.get(0, 0).unwrap().glyph, to_cp437('┌'));
+        assert_eq!(console.get(1, 1).unwrap().glyph, b'M');
+
+        let mut console = VirtualConsole::new(12, 4);
+        let mut ui = Ui::with_console(&mut console, input(0, 0, false, None));
+        assert_eq!(ui.dialog("Quit?", "Really quit?", &["Yes", "No"]), None);
+        assert_eq!(console.get(0, 0).unwrap().glyph, to_cp437('╔'));
+        assert_eq!(console.get(2, 1).unwrap().glyph, b'R');
+    }
+}
@@ -0,0 +1,264 @@
+use super::UiInput;
+use super::super::VirtualKeyCode;
+
+/// What happened to a text field this frame.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum TextInputResult { NoResponse, Submitted, Cancel }
+
+/// The contents and editing state of a text field, such as a character name or a wish. Draw it
+/// with `Ui::text_input`, or drive it yourself with `handle_input` and the editing functions.
+///
+/// The cursor and selection are measured in characters. Typing or pasting replaces the
+/// selection; anything rejected by the filter, or beyond the maximum length, is dropped.
+pub struct TextInput {
+    text: Vec<char>,
+    cursor: usize,
+    anchor: Option<usize>,
+    max_length: usize,
+    filter: Option<Box<dyn Fn(char) -> bool>>,
+    pub(crate) scroll: usize,
+    pub focused: bool
+}
+
+impl TextInput {
+    /// Creates an empty, focused field that accepts up to `max_length` characters.
+    pub fn new(max_length : usize) -> TextInput {
+        TextInput{ text: Vec::new(), cursor: 0, anchor: None, max_length, filter: None, scroll: 0, focused: true }
+    }
+
+    /// Only accept characters for which `filter` returns true (e.g. `char::is_alphanumeric`).
+    pub fn with_filter<F: Fn(char) -> bool + 'static>(mut self, filter : F) -> TextInput {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Starts the field with some text, with the cursor at the end.
+    pub fn with_text(mut self, text : &str) -> TextInput {
+        self.set_text(text);
+        self
+    }
+
+    /// The current text.
+    pub fn text(&self) -> String {
+        self.text.iter().collect()
+    }
+
+    /// Replaces the text (subject to the filter and maximum length) and moves the cursor to the end.
+    pub fn set_text(&mut self, text : &str) {
+        self.clear();
+        self.paste(text);
+    }
+
+    /// Removes all the text.
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+        self.anchor = None;
+        self.scroll = 0;
+    }
+
+    /// The cursor position, in characters from the start.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Moves the cursor, clearing the selection.
+    pub fn set_cursor(&mut self, position : usize) {
+        self.cursor = usize::min(position, self.text.len());
+        self.anchor = None;
+    }
+
+    /// The selected range (start inclusive, end exclusive), if anything is selected.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        match self.anchor {
+            Some(anchor) if anchor != self.cursor => Some((usize::min(anchor, self.cursor), usize::max(anchor, self.cursor))),
+            _ => None
+        }
+    }
+
+    /// The selected text, or an empty string.
+    pub fn selected_text(&self) -> String {
+        match self.selection() {
+            Some((start, end)) => self.text[start .. end].iter().collect(),
+            None => String::new()
+        }
+    }
+
+    /// Selects everything.
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.text.len();
+    }
+
+    /// Deletes the selection, if there is one. Returns true if anything was removed.
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some((start, end)) => {
+                self.text.drain(start .. end);
+                self.cursor = start;
+                self.anchor = None;
+                true
+            }
+            None => {
+                self.anchor = None;
+                false
+            }
+        }
+    }
+
+    fn accepts(&self, c : char) -> bool {
+        if c.is_control() { return false; }
+        match &self.filter {
+            Some(filter) => filter(c),
+            None => true
+        }
+    }
+
+    /// Types a character at the cursor, replacing any selection. Returns false if the character
+    /// was rejected.
+    pub fn insert_char(&mut self, c : char) -> bool {
+        if !self.accepts(c) { return false; }
+        self.delete_selection();
+        if self.text.len() >= self.max_length { return false; }
+        self.text.insert(self.cursor, c);
+        self.cursor += 1;
+        true
+    }
+
+    /// Inserts a string at the cursor as though it was typed, replacing any selection. Use this
+    /// to paste text from wherever you got it; rejected characters are skipped and the rest is
+    /// cut off at the maximum length.
+    pub fn paste(&mut self, text : &str) {
+        self.delete_selection();
+        for c in text.chars() {
+            if self.text.len() >= self.max_length { break; }
+            if self.accepts(c) {
+                self.text.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+        }
+    }
+
+    /// Deletes the selection, or the character before the cursor.
+    pub fn backspace(&mut self) {
+        if !self.delete_selection() && self.cursor > 0 {
+            self.cursor -= 1;
+            self.text.remove(self.cursor);
+        }
+    }
+
+    /// Deletes the selection, or the character after the cursor.
+    pub fn delete(&mut self) {
+        if !self.delete_selection() && self.cursor < self.text.len() {
+            self.text.remove(self.cursor);
+        }
+    }
+
+    /// Moves the cursor to `position`, extending the selection if `select` is true.
+    fn move_cursor(&mut self, position : usize, select : bool) {
+        if select {
+            if self.anchor.is_none() { self.anchor = Some(self.cursor); }
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = usize::min(position, self.text.len());
+    }
+
+    /// Moves the cursor one character left, optionally extending the selection.
+    pub fn move_left(&mut self, select : bool) {
+        let position = self.cursor.saturating_sub(1);
+        self.move_cursor(position, select);
+    }
+
+    /// Moves the cursor one character right, optionally extending the selection.
+    pub fn move_right(&mut self, select : bool) {
+        let position = self.cursor + 1;
+        self.move_cursor(position, select);
+    }
+
+    /// Moves the cursor to the start, optionally extending the selection.
+    pub fn home(&mut self, select : bool) {
+        self.move_cursor(0, select);
+    }
+
+    /// Moves the cursor to the end, optionally extending the selection.
+    pub fn end(&mut self, select : bool) {
+        let position = self.text.len();
+        self.move_cursor(position, select);
+    }
+
+    /// Applies a frame's typed characters and editing key. Shift with the cursor keys, Home or
+    /// End selects; Enter submits and Escape cancels. The characters and key are consumed.
+    pub fn handle_input(&mut self, input : &mut UiInput) -> TextInputResult {
+        for c in std::mem::take(&mut input.chars) {
+            self.insert_char(c);
+        }
+
+        let mut result = TextInputResult::NoResponse;
+        let shift = input.shift;
+        let consumed = match input.key {
+            Some(VirtualKeyCode::Left) => { self.move_left(shift); true }
+            Some(VirtualKeyCode::Right) => { self.move_right(shift); true }
+            Some(VirtualKeyCode::Home) => { self.home(shift); true }
+            Some(VirtualKeyCode::End) => { self.end(shift); true }
+            Some(VirtualKeyCode::Back) => { self.backspace(); true }
+            Some(VirtualKeyCode::Delete) => { self.delete(); true }
+            Some(VirtualKeyCode::Return) | Some(VirtualKeyCode::NumpadEnter) => { result = TextInputResult::Submitted; true }
+            Some(VirtualKeyCode::Escape) => { result = TextInputResult::Cancel; true }
+            _ => false
+        };
+        if consumed {
+            input.key = None;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TextInput, TextInputResult};
+    use super::super::{UiInput, test_input};
+    use super::super::super::VirtualKeyCode;
+
+    fn input(chars : &str, key : Option<VirtualKeyCode>, shift : bool) -> UiInput {
+        UiInput{ chars: chars.chars().collect(), shift, ..test_input(-1, -1, false, key) }
+    }
+
+    #[test]
+    fn test_typing_and_editing() {
+        let mut field = TextInput::new(20);
+        field.handle_input(&mut input("Hello", None, false));
+        field.handle_input(&mut input("", Some(VirtualKeyCode::Left), false));
+        field.handle_input(&mut input("", Some(VirtualKeyCode::Back), false));
+        assert_eq!(field.text(), "Helo");
+        field.handle_input(&mut input("", Some(VirtualKeyCode::Home), false));
+        field.handle_input(&mut input("", Some(VirtualKeyCode::Delete), false));
+        assert_eq!(field.text(), "elo");
+        assert_eq!(field.handle_input(&mut input("", Some(VirtualKeyCode::Return), false)), TextInputResult::Submitted);
+    }
+
+    #[test]
+    // Shift extends the selection, and typing replaces it.
+    fn test_selection() {
+        let mut field = TextInput::new(20).with_text("Grognak");
+        field.handle_input(&mut input("", Some(VirtualKeyCode::Left), true));
+        field.handle_input(&mut input("", Some(VirtualKeyCode::Left), true));
+        field.handle_input(&mut input("", Some(VirtualKeyCode::Left), true));
+        assert_eq!(field.selected_text(), "nak");
+        field.handle_input(&mut input("!", None, false));
+        assert_eq!(field.text(), "Grog!");
+        assert_eq!(field.selection(), None);
+    }
+
+    #[test]
+    // Pasted text is filtered and truncated to the maximum length.
+    fn test_filter_and_max_length() {
+        let mut field = TextInput::new(5).with_filter(|c| c.is_ascii_digit());
+        field.paste("1a2b3\n4567");
+        assert_eq!(field.text(), "12345");
+        assert!(!field.insert_char('8'));
+        field.select_all();
+        assert!(field.insert_char('9'));
+        assert_eq!(field.text(), "9");
+    }
+}